@@ -0,0 +1,192 @@
+use crate::release::tag_version;
+use semver::Version;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Details of a tag for auditing.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TagInfo {
+	/// Name of the tag.
+	pub name:      String,
+	/// SHA1 of the commit that the tag points to.
+	pub commit_id: String,
+	/// Committer timestamp of the commit.
+	pub timestamp: i64,
+	/// Whether the tag is an annotated tag.
+	pub annotated: bool,
+	/// Message of the annotated tag.
+	pub message:   Option<String>,
+	/// Whether the commit is reachable from the audited branch.
+	pub reachable: bool,
+}
+
+/// Issue that is found in the tag history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditIssue {
+	/// Tag has the same version as another tag.
+	DuplicateVersion,
+	/// Tag points to the same commit as another tag.
+	DuplicateCommit,
+	/// Tag has a lower version than a tag that is created before it.
+	NonMonotonicVersion,
+	/// Tag points to a commit that is not in the branch.
+	OutsideBranch,
+	/// Tag is not annotated or it does not have a message.
+	MissingMessage,
+}
+
+impl fmt::Display for AuditIssue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::DuplicateVersion => write!(f, "duplicate_version"),
+			Self::DuplicateCommit => write!(f, "duplicate_commit"),
+			Self::NonMonotonicVersion => write!(f, "non_monotonic_version"),
+			Self::OutsideBranch => write!(f, "outside_branch"),
+			Self::MissingMessage => write!(f, "missing_message"),
+		}
+	}
+}
+
+/// Finding of the audit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditFinding {
+	/// Name of the tag.
+	pub tag:     String,
+	/// Found issue.
+	pub issue:   AuditIssue,
+	/// Description of the issue.
+	pub message: String,
+}
+
+impl fmt::Display for AuditFinding {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} [{}]: {}", self.tag, self.issue, self.message)
+	}
+}
+
+/// Audits the given tags and returns the findings.
+///
+/// The tags are expected to be sorted from the oldest to the newest.
+pub fn audit(tags: &[TagInfo]) -> Vec<AuditFinding> {
+	let mut findings = Vec::new();
+	let mut versions: HashMap<Version, &str> = HashMap::new();
+	let mut commits: HashMap<&str, &str> = HashMap::new();
+	let mut latest: Option<(Version, &str)> = None;
+	for tag in tags {
+		let mut report = |issue: AuditIssue, message: String| {
+			findings.push(AuditFinding {
+				tag: tag.name.to_string(),
+				issue,
+				message,
+			})
+		};
+		if let Some(version) = tag_version(&tag.name) {
+			if let Some(other) = versions.get(&version) {
+				report(
+					AuditIssue::DuplicateVersion,
+					format!("same version as {other}"),
+				);
+			} else if let Some((latest_version, other)) =
+				latest.as_ref().filter(|(v, _)| &version < v)
+			{
+				report(
+					AuditIssue::NonMonotonicVersion,
+					format!(
+						"version is lower than {latest_version} ({other}) which is \
+						 tagged before"
+					),
+				);
+			}
+			versions.entry(version.clone()).or_insert(&tag.name);
+			if latest.as_ref().map_or(true, |(v, _)| &version > v) {
+				latest = Some((version, &tag.name));
+			}
+		}
+		if let Some(other) = commits.get(tag.commit_id.as_str()) {
+			report(
+				AuditIssue::DuplicateCommit,
+				format!("points to the same commit as {other}"),
+			);
+		} else {
+			commits.insert(&tag.commit_id, &tag.name);
+		}
+		if !tag.reachable {
+			report(
+				AuditIssue::OutsideBranch,
+				format!(
+					"points to a commit that is not in the branch ({})",
+					tag.commit_id.get(..7).unwrap_or(&tag.commit_id)
+				),
+			);
+		}
+		if !tag.annotated {
+			report(
+				AuditIssue::MissingMessage,
+				String::from("tag is not annotated"),
+			);
+		} else if tag.message.is_none() {
+			report(
+				AuditIssue::MissingMessage,
+				String::from("annotated tag does not have a message"),
+			);
+		}
+	}
+	findings
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn audit_tags() {
+		let tag = |name: &str, commit_id: &str| TagInfo {
+			name: name.to_string(),
+			commit_id: commit_id.to_string(),
+			annotated: true,
+			message: Some(format!("Release {name}")),
+			reachable: true,
+			..Default::default()
+		};
+		let tags = vec![
+			tag("v1.0.0", "a1"),
+			tag("v1.1.0", "a2"),
+			tag("1.1.0", "a3"),
+			tag("v1.0.1", "a4"),
+			tag("v1.2.0", "a4"),
+			TagInfo {
+				reachable: false,
+				..tag("v1.3.0", "a5")
+			},
+			TagInfo {
+				annotated: false,
+				message: None,
+				..tag("v1.4.0", "a6")
+			},
+			TagInfo {
+				message: None,
+				..tag("latest", "a7")
+			},
+		];
+		assert_eq!(
+			vec![
+				("1.1.0", AuditIssue::DuplicateVersion),
+				("v1.0.1", AuditIssue::NonMonotonicVersion),
+				("v1.2.0", AuditIssue::DuplicateCommit),
+				("v1.3.0", AuditIssue::OutsideBranch),
+				("v1.4.0", AuditIssue::MissingMessage),
+				("latest", AuditIssue::MissingMessage),
+			],
+			audit(&tags)
+				.iter()
+				.map(|v| (v.tag.as_str(), v.issue))
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			"v1.0.1 [non_monotonic_version]: version is lower than 1.1.0 (v1.1.0) \
+			 which is tagged before",
+			audit(&tags)[1].to_string()
+		);
+	}
+}
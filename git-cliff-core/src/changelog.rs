@@ -1,86 +1,326 @@
+use crate::collation::Collator;
 use crate::commit::Commit;
-use crate::config::Config;
-use crate::error::Result;
+#[cfg(feature = "github")]
+use crate::config::{
+	CommitDate,
+	Remote,
+};
+use crate::config::{
+	CommitSortKey,
+	Config,
+	PaginationConfig,
+};
+use crate::error::{
+	Error,
+	Result,
+};
+use crate::filter::CommitFilter;
 #[cfg(feature = "github")]
 use crate::github::{
 	GitHubClient,
 	GitHubCommit,
+	GitHubMilestone,
 	GitHubPullRequest,
+	GitHubRepository,
 	FINISHED_FETCHING_MSG,
 	START_FETCHING_MSG,
 };
+use crate::markdown;
+use crate::query;
 use crate::release::{
+	BumpRationale,
+	GraphFormat,
 	Release,
+	ReleaseAsset,
+	ReleaseAuthors,
+	ReleaseStats,
 	Releases,
+	RepositoryMetadata,
+};
+use crate::template::{
+	Template,
+	TemplateCache,
+};
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+#[cfg(feature = "github")]
+use std::cell::OnceCell;
+use std::cell::{
+	Cell,
+	RefCell,
 };
-use crate::template::Template;
 use std::collections::HashMap;
 use std::io::Write;
+use std::path::Path;
+use std::str;
+#[cfg(feature = "github")]
+use std::sync::atomic::{
+	AtomicUsize,
+	Ordering,
+};
+#[cfg(feature = "github")]
+use std::sync::mpsc::{
+	self,
+	Receiver,
+};
+#[cfg(feature = "github")]
+use std::sync::Arc;
+#[cfg(feature = "github")]
+use std::thread;
 use std::time::{
 	SystemTime,
 	UNIX_EPOCH,
 };
 
+/// Metadata that is fetched from GitHub.
+#[cfg(feature = "github")]
+#[derive(Debug, Default, Clone)]
+struct GitHubMetadata {
+	/// Commits of the repository.
+	commits:       Vec<GitHubCommit>,
+	/// Pull requests of the repository.
+	pull_requests: Vec<GitHubPullRequest>,
+	/// Milestones of the repository.
+	milestones:    Vec<GitHubMilestone>,
+	/// Repository itself.
+	repository:    Option<GitHubRepository>,
+}
+
+/// Variables of the templates that need the GitHub milestones.
+#[cfg(feature = "github")]
+const MILESTONE_VARIABLES: &[&str] =
+	&["github.milestone", "release.github.milestone"];
+
+/// Variables of the templates that need the GitHub repository.
+#[cfg(feature = "github")]
+const REPOSITORY_VARIABLES: &[&str] =
+	&["repository.description", "repository.topics"];
+
+/// Data that is optionally fetched from GitHub along with the commits and
+/// the pull requests.
+#[cfg(feature = "github")]
+#[derive(Debug, Clone, Copy)]
+struct GitHubMetadataOptions {
+	/// Whether to fetch the milestones.
+	milestones: bool,
+	/// Whether to fetch the repository.
+	repository: bool,
+}
+
+/// Default template of the note for the groups that are elided for fitting
+/// the size budget (see [`ChangelogConfig::elision_note`]).
+///
+/// [`ChangelogConfig::elision_note`]: crate::config::ChangelogConfig::elision_note
+const DEFAULT_ELISION_NOTE: &str =
+	"\n… {{ groups | join(sep=\", \") }} omitted, see full changelog";
+
+/// Regex for matching the versions in the release headings.
+static VERSION_REGEX: Lazy<Regex> = lazy_regex!(r"\d+\.\d+");
+
 /// Changelog generator.
 #[derive(Debug)]
 pub struct Changelog<'a> {
 	releases:        Vec<Release<'a>>,
 	body_template:   Template,
 	footer_template: Option<Template>,
+	commit_template: Option<Template>,
+	tag_template:    Option<Template>,
+	/// Templates for the path of the continuation pages and the link to the
+	/// next page.
+	page_templates:  Option<(Template, Template)>,
+	era_templates:   Vec<Option<Template>>,
+	/// Collator of the configured locale.
+	collator:        Option<Collator>,
 	config:          &'a Config,
+	remote_degraded: Cell<bool>,
+	/// Shortened URLs of the URL shortener.
+	short_urls:      RefCell<HashMap<String, String>>,
+	/// Metadata of the repository.
+	repository:      RepositoryMetadata,
+	/// Receiver of the GitHub metadata that is fetched in the background
+	/// along with whether if it is degraded.
+	#[cfg(feature = "github")]
+	github_receiver: Option<Receiver<Result<(GitHubMetadata, bool)>>>,
+	/// GitHub metadata that is received.
+	#[cfg(feature = "github")]
+	github_metadata: OnceCell<GitHubMetadata>,
+	/// Number of the requests that are sent to GitHub.
+	#[cfg(feature = "github")]
+	github_requests: Arc<AtomicUsize>,
 }
 
 impl<'a> Changelog<'a> {
 	/// Constructs a new instance.
+	///
+	/// See [`with_template_cache`](Self::with_template_cache) for reusing the
+	/// parsed templates between the instances.
 	pub fn new(releases: Vec<Release<'a>>, config: &'a Config) -> Result<Self> {
+		Self::with_template_cache(releases, config, &mut TemplateCache::default())
+	}
+
+	/// Constructs a new instance with the templates from the given cache.
+	///
+	/// The templates are parsed only if they are not in the cache, e.g. when
+	/// the changelog is generated repeatedly in watch mode.
+	pub fn with_template_cache(
+		releases: Vec<Release<'a>>,
+		config: &'a Config,
+		cache: &mut TemplateCache,
+	) -> Result<Self> {
 		let trim = config.changelog.trim.unwrap_or(true);
+		let collator = config.changelog.collation.as_deref().map(Collator::new);
+		let page_templates = match &config.changelog.pagination {
+			Some(pagination) => Some((
+				cache.get(
+					pagination
+						.path
+						.clone()
+						.unwrap_or(String::from(PaginationConfig::DEFAULT_PATH)),
+					false,
+				)?,
+				cache.get(
+					pagination.continuation.clone().unwrap_or(String::from(
+						PaginationConfig::DEFAULT_CONTINUATION,
+					)),
+					false,
+				)?,
+			)),
+			None => None,
+		};
+		let mut new_template = |template: &str| -> Result<Template> {
+			let mut template = cache.get(template.to_string(), trim)?;
+			if let Some(path) = &config.changelog.git_read_path {
+				template
+					.allow_git_read(path.clone(), config.git.tag_pattern.clone());
+			}
+			if config.changelog.commit_groups.is_some() || collator.is_some() {
+				template.set_commit_groups(
+					config.changelog.commit_groups.clone().unwrap_or_default(),
+					collator.clone(),
+				);
+			}
+			if let Some(flavor) = config.changelog.output_flavor {
+				template.set_output_flavor(flavor);
+			}
+			if let Some(limits) = config.changelog.template_limits {
+				template.set_limits(limits)?;
+			}
+			Ok(template)
+		};
 		let mut changelog = Self {
 			releases,
-			body_template: Template::new(
-				config
-					.changelog
-					.body
-					.as_deref()
-					.unwrap_or_default()
-					.to_string(),
-				trim,
+			body_template: new_template(
+				config.changelog.body.as_deref().unwrap_or_default(),
 			)?,
 			footer_template: match &config.changelog.footer {
-				Some(footer) => Some(Template::new(footer.to_string(), trim)?),
+				Some(footer) => Some(new_template(footer)?),
+				None => None,
+			},
+			commit_template: match &config.changelog.commit_template {
+				Some(template) => Some(new_template(template)?),
 				None => None,
 			},
+			tag_template: match &config.changelog.tag_message {
+				Some(template) => Some(new_template(template)?),
+				None => None,
+			},
+			page_templates,
+			era_templates: config
+				.eras
+				.iter()
+				.flatten()
+				.map(|era| era.body.as_deref().map(&mut new_template).transpose())
+				.collect::<Result<Vec<Option<Template>>>>()?,
+			collator,
 			config,
+			remote_degraded: Cell::new(false),
+			short_urls: RefCell::new(HashMap::new()),
+			repository: RepositoryMetadata::default(),
+			#[cfg(feature = "github")]
+			github_receiver: None,
+			#[cfg(feature = "github")]
+			github_metadata: OnceCell::new(),
+			#[cfg(feature = "github")]
+			github_requests: Arc::new(AtomicUsize::new(0)),
 		};
-		changelog.process_commits();
+		if config.remote.offline && changelog.uses_remote() {
+			return Err(Error::OfflineError(String::from(
+				"The changelog uses remote data which requires network access",
+			)));
+		}
+		changelog.process_commits()?;
 		changelog.process_releases();
+		changelog.format_commits()?;
 		Ok(changelog)
 	}
 
+	/// Renders the commits with the commit template (if set) and stores the
+	/// output in [`formatted`].
+	///
+	/// [`formatted`]: Commit::formatted
+	fn format_commits(&mut self) -> Result<()> {
+		if let Some(commit_template) = &self.commit_template {
+			debug!("Formatting the commits...");
+			for commit in self
+				.releases
+				.iter_mut()
+				.flat_map(|release| release.commits.iter_mut())
+			{
+				commit.formatted = Some(commit_template.render(
+					commit,
+					Option::<HashMap<&str, String>>::None.as_ref(),
+					&[],
+				)?);
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the body template of the given release.
+	///
+	/// The template of the era that contains the release is used if it is set.
+	fn body_template(&self, release: &Release) -> &Template {
+		self.config
+			.era(release.version.as_deref())
+			.and_then(|(index, _)| self.era_templates[index].as_ref())
+			.unwrap_or(&self.body_template)
+	}
+
 	/// Processes the commits and omits the ones that doesn't match the
 	/// criteria set by configuration file.
-	fn process_commits(&mut self) {
+	///
+	/// The git configuration of the era that contains the release is used if
+	/// it is set.
+	fn process_commits(&mut self) -> Result<()> {
 		debug!("Processing the commits...");
-		self.releases.iter_mut().for_each(|release| {
+		#[cfg(feature = "github")]
+		if self.processes_remote_data() {
+			self.start_fetching_github_metadata();
+		}
+		let config = self.config;
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)?
+			.as_secs()
+			.try_into()?;
+		for release in self.releases.iter_mut() {
+			let git_config = config
+				.era(release.version.as_deref())
+				.and_then(|(_, era)| era.git.as_ref())
+				.unwrap_or(&config.git);
+			let filter = git_config
+				.commit_filter
+				.as_deref()
+				.map(CommitFilter::parse)
+				.transpose()?;
+			let embargoes = git_config.active_embargoes(now)?;
 			release.commits = release
 				.commits
 				.iter()
-				.cloned()
-				.flat_map(|commit| {
-					if self.config.git.split_commits.unwrap_or(false) {
-						commit
-							.message
-							.lines()
-							.map(|line| {
-								let mut c = commit.clone();
-								c.message = line.to_string();
-								c
-							})
-							.collect()
-					} else {
-						vec![commit]
-					}
-				})
-				.filter_map(|commit| match commit.process(&self.config.git) {
+				.flat_map(|commit| commit.split(git_config))
+				.filter_map(|commit| match commit.process(git_config) {
 					Ok(commit) => Some(commit),
 					Err(e) => {
 						trace!(
@@ -92,8 +332,164 @@ impl<'a> Changelog<'a> {
 						None
 					}
 				})
+				.filter(|commit| {
+					filter
+						.as_ref()
+						.map_or(true, |filter| filter.matches(commit))
+				})
+				.filter(|commit| {
+					let embargoed = embargoes
+						.iter()
+						.any(|embargo| embargo.matches(&commit.id, &commit.message));
+					if embargoed {
+						debug!("Commit is under embargo: {}", commit.id);
+					}
+					!embargoed
+				})
 				.collect::<Vec<Commit>>();
-		});
+		}
+		#[cfg(feature = "github")]
+		self.process_remote_data()?;
+		for commit in self
+			.releases
+			.iter_mut()
+			.flat_map(|release| release.commits.iter_mut())
+		{
+			if let Some(normalization) = config
+				.changelog
+				.entry_normalization(commit.group_name().as_deref())
+			{
+				*commit = commit.clone().normalize(&normalization)?;
+			}
+		}
+		if let Some(min_scope_commits) = self.config.git.min_scope_commits {
+			self.merge_scopes(min_scope_commits);
+		}
+		if let Some(sort_key) = self.config.git.sort_group_commits {
+			self.sort_commits(sort_key);
+		}
+		Ok(())
+	}
+
+	/// Updates the commits of the releases with the remote data that changes
+	/// them (e.g. skips the commits by the labels of their pull requests).
+	///
+	/// Nothing is fetched if the remote data does not change the commits.
+	#[cfg(feature = "github")]
+	fn process_remote_data(&mut self) -> Result<()> {
+		if !self.processes_remote_data() {
+			return Ok(());
+		}
+		let github_metadata = self.get_github_metadata_or_default()?;
+		for release in self.releases.iter_mut() {
+			release.update_github_metadata(
+				github_metadata.commits.clone(),
+				github_metadata.pull_requests.clone(),
+			)?;
+			if self.config.git.commit_date == Some(CommitDate::Merge) {
+				release.set_merge_dates();
+			}
+			if let Some(labels) = &self.config.remote.skip_labels {
+				release.skip_labeled_commits(labels);
+			}
+			if let Some(pattern) = &self.config.remote.group_labels {
+				release.group_by_labels(pattern);
+			}
+			if self.config.remote.merge_pr_commits.unwrap_or(false) {
+				release.merge_pull_request_commits();
+			}
+		}
+		Ok(())
+	}
+
+	/// Moves the commits of the scopes that have less than the given number
+	/// of commits in their group into the [`other_scope`].
+	///
+	/// The commits are removed instead if [`hide_small_scopes`] is enabled.
+	///
+	/// [`other_scope`]: crate::config::GitConfig::other_scope
+	/// [`hide_small_scopes`]: crate::config::GitConfig::hide_small_scopes
+	fn merge_scopes(&mut self, min_scope_commits: usize) {
+		let other_scope = self
+			.config
+			.git
+			.other_scope
+			.clone()
+			.unwrap_or_else(|| String::from("Other"));
+		let hide_small_scopes = self.config.git.hide_small_scopes.unwrap_or(false);
+		for release in self.releases.iter_mut() {
+			let mut scope_counts = HashMap::new();
+			for commit in &release.commits {
+				if let Some(scope) = commit.scope_name() {
+					*scope_counts
+						.entry((commit.group_name(), scope))
+						.or_insert(0) += 1;
+				}
+			}
+			let is_small = |commit: &Commit| {
+				commit.scope_name().is_some_and(|scope| {
+					scope_counts[&(commit.group_name(), scope)] < min_scope_commits
+				})
+			};
+			if hide_small_scopes {
+				release.commits.retain(|commit| {
+					let is_small = is_small(commit);
+					if is_small {
+						trace!("Hiding the commit of a small scope: {}", commit.id);
+					}
+					!is_small
+				});
+				continue;
+			}
+			for commit in release.commits.iter_mut() {
+				if is_small(commit) {
+					trace!(
+						"Merging scope into {other_scope}: {}",
+						commit.scope_name().unwrap_or_default()
+					);
+					commit.scope = Some(other_scope.clone());
+				}
+			}
+		}
+	}
+
+	/// Sorts the commits of the releases by the given key.
+	///
+	/// The sort is stable so the commits with the same key keep their
+	/// original order. Since the commits are grouped in the template, this
+	/// determines the order of the commits within each group.
+	fn sort_commits(&mut self, sort_key: CommitSortKey) {
+		debug!("Sorting the commits by {sort_key:?}...");
+		let message = |commit: &Commit| {
+			commit
+				.conv
+				.as_ref()
+				.map(|conv| conv.description().to_string())
+				.unwrap_or_else(|| commit.message.clone())
+				.to_lowercase()
+		};
+		let scope =
+			|commit: &Commit| commit.scope_name().map(|scope| scope.to_lowercase());
+		let collator = self.collator.as_ref();
+		let collate = |value: String| (collator.map(|v| v.sort_key(&value)), value);
+		for release in self.releases.iter_mut() {
+			match sort_key {
+				CommitSortKey::Message => release
+					.commits
+					.sort_by_cached_key(|commit| collate(message(commit))),
+				CommitSortKey::Scope => release
+					.commits
+					.sort_by_cached_key(|commit| scope(commit).map(collate)),
+				CommitSortKey::Date => {
+					release.commits.sort_by_key(|commit| commit.date)
+				}
+				CommitSortKey::Author => {
+					release.commits.sort_by_cached_key(|commit| {
+						commit.author.name.clone().map(collate)
+					})
+				}
+			}
+		}
 	}
 
 	/// Processes the releases and filters them out based on the configuration.
@@ -147,78 +543,643 @@ impl<'a> Changelog<'a> {
 				}
 			}
 		}
+		if let Some(internal_domains) = &self.config.changelog.internal_domains {
+			for release in self.releases.iter_mut() {
+				release.authors =
+					Some(ReleaseAuthors::new(&release.commits, internal_domains));
+			}
+		}
+		for release in self.releases.iter_mut() {
+			release.stats = ReleaseStats::new(&release.commits);
+		}
 	}
 
-	/// Returns the GitHub metadata needed for the changelog.
+	/// Starts fetching the GitHub metadata in the background so that the
+	/// requests overlap with processing the commits.
+	///
+	/// This is only done if the remote data changes the commits (see
+	/// [`Changelog::processes_remote_data`]). The metadata is sent over a
+	/// channel and received in
+	/// [`get_github_metadata`](Self::get_github_metadata).
+	#[cfg(feature = "github")]
+	fn start_fetching_github_metadata(&mut self) {
+		let remote = self.config.remote.github.clone();
+		let search_users = self.config.remote.search_users.unwrap_or(false);
+		let options = self.github_metadata_options();
+		let requests = Arc::clone(&self.github_requests);
+		let (sender, receiver) = mpsc::channel();
+		thread::spawn(move || {
+			let _ = sender.send(Self::fetch_github_metadata(
+				remote,
+				search_users,
+				options,
+				requests,
+			));
+		});
+		self.github_receiver = Some(receiver);
+	}
+
+	/// Fetches the GitHub metadata along with whether if it is fetched
+	/// partially.
 	///
 	/// This function creates a multithread async runtime for handling the
 	/// requests. The following are fetched from the GitHub REST API:
 	///
 	/// - Commits
 	/// - Pull requests
+	/// - Milestones (if they are needed, see [`GitHubMetadataOptions`])
+	/// - Repository (if it is needed, see [`GitHubMetadataOptions`])
 	///
 	/// Each of these are paginated requests so they are being run in parallel
 	/// for speedup.
 	///
-	/// If no GitHub related variable is used in the template then this function
-	/// returns empty vectors.
+	/// The usernames of the commit authors that are not linked to a GitHub
+	/// account are resolved afterwards (see
+	/// [`GitHubClient::resolve_usernames`]).
 	#[cfg(feature = "github")]
-	fn get_github_metadata(
-		&self,
-	) -> Result<(Vec<GitHubCommit>, Vec<GitHubPullRequest>)> {
-		if self.body_template.contains_github_variable() ||
-			self.footer_template
-				.as_ref()
-				.map(|v| v.contains_github_variable())
-				.unwrap_or(false)
-		{
-			warn!("You are using an experimental feature! Please report bugs at <https://github.com/orhun/git-cliff/issues/new/choose>");
-			let github_client =
-				GitHubClient::try_from(self.config.remote.github.clone())?;
-			info!("{START_FETCHING_MSG} ({})", self.config.remote.github);
-			let data = tokio::runtime::Builder::new_multi_thread()
-				.enable_all()
-				.build()?
-				.block_on(async {
-					let (commits, pull_requests) = tokio::try_join!(
-						github_client.get_commits(),
-						github_client.get_pull_requests(),
-					)?;
-					debug!("Number of GitHub commits: {}", commits.len());
-					debug!("Number of GitHub pull requests: {}", commits.len());
-					Ok((commits, pull_requests))
-				});
-			info!("{FINISHED_FETCHING_MSG}");
-			data
-		} else {
-			Ok((vec![], vec![]))
+	fn fetch_github_metadata(
+		remote: Remote,
+		search_users: bool,
+		options: GitHubMetadataOptions,
+		requests: Arc<AtomicUsize>,
+	) -> Result<(GitHubMetadata, bool)> {
+		warn!("You are using an experimental feature! Please report bugs at <https://github.com/orhun/git-cliff/issues/new/choose>");
+		let github_client =
+			GitHubClient::try_from(remote.clone())?.with_request_counter(requests);
+		info!("{START_FETCHING_MSG} ({remote})");
+		let metadata = tokio::runtime::Builder::new_multi_thread()
+			.enable_all()
+			.build()?
+			.block_on(async {
+				let (mut commits, pull_requests, milestones, repository) = tokio::try_join!(
+					github_client.get_commits(),
+					github_client.get_pull_requests(),
+					async {
+						if options.milestones {
+							github_client.get_milestones().await
+						} else {
+							Ok(Vec::new())
+						}
+					},
+					async {
+						if options.repository {
+							github_client.get_repository().await
+						} else {
+							Ok(None)
+						}
+					},
+				)?;
+				github_client
+					.resolve_usernames(&mut commits, &pull_requests, search_users)
+					.await;
+				Ok::<_, Error>(GitHubMetadata {
+					commits,
+					pull_requests,
+					milestones,
+					repository,
+				})
+			})?;
+		debug!("Number of GitHub commits: {}", metadata.commits.len());
+		debug!(
+			"Number of GitHub pull requests: {}",
+			metadata.pull_requests.len()
+		);
+		debug!("Number of GitHub milestones: {}", metadata.milestones.len());
+		info!("{FINISHED_FETCHING_MSG}");
+		Ok((metadata, github_client.is_degraded()))
+	}
+
+	/// Returns the GitHub metadata needed for the changelog.
+	///
+	/// This waits for the metadata that is being fetched in the background or
+	/// fetches it when it is first needed. Empty metadata is returned if the
+	/// changelog does not use the remote data.
+	#[cfg(feature = "github")]
+	fn get_github_metadata(&self) -> Result<GitHubMetadata> {
+		if let Some(metadata) = self.github_metadata.get() {
+			return Ok(metadata.clone());
 		}
+		let (metadata, degraded) = match &self.github_receiver {
+			Some(receiver) => receiver.recv().map_err(|_| {
+				Error::ChangelogError(String::from(
+					"the GitHub metadata is not received",
+				))
+			})??,
+			None if self.uses_remote() => Self::fetch_github_metadata(
+				self.config.remote.github.clone(),
+				self.config.remote.search_users.unwrap_or(false),
+				self.github_metadata_options(),
+				Arc::clone(&self.github_requests),
+			)?,
+			None => return Ok(GitHubMetadata::default()),
+		};
+		if degraded {
+			self.remote_degraded.set(true);
+		}
+		Ok(self.github_metadata.get_or_init(|| metadata).clone())
+	}
+
+	/// Returns the GitHub metadata or empty metadata if it cannot be fetched
+	/// and the remote data is not [`required`].
+	///
+	/// [`required`]: crate::config::RemoteConfig::required
+	#[cfg(feature = "github")]
+	fn get_github_metadata_or_default(&self) -> Result<GitHubMetadata> {
+		match self.get_github_metadata() {
+			Ok(metadata) => Ok(metadata),
+			Err(e) if !self.config.remote.required.unwrap_or(true) => {
+				warn!("Generating the changelog without the remote data: {e}");
+				self.remote_degraded.set(true);
+				Ok(GitHubMetadata::default())
+			}
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Returns `true` if the commits are changed by the remote data (e.g.
+	/// they are skipped by the labels of the pull requests).
+	///
+	/// The merge time of the pull requests is only fetched for the date of
+	/// the commits if the GitHub remote is set.
+	#[cfg(feature = "github")]
+	fn processes_remote_data(&self) -> bool {
+		(self.config.git.commit_date == Some(CommitDate::Merge) &&
+			self.config.remote.github.is_set()) ||
+			self.config.remote.skip_labels.is_some() ||
+			self.config.remote.group_labels.is_some() ||
+			self.config.remote.merge_pr_commits.unwrap_or(false)
+	}
+
+	/// Returns `true` if the remote data needs to be fetched for generating
+	/// the changelog (i.e. the templates use remote related variables or the
+	/// commits are changed by the remote data).
+	#[cfg(feature = "github")]
+	pub fn uses_remote(&self) -> bool {
+		self.processes_remote_data() ||
+			self.templates().any(|v| v.contains_github_variable())
+	}
+
+	/// Returns the templates that are rendered with the remote data.
+	#[cfg(feature = "github")]
+	fn templates(&self) -> impl Iterator<Item = &Template> {
+		std::iter::once(&self.body_template)
+			.chain(self.footer_template.iter())
+			.chain(self.tag_template.iter())
+			.chain(self.era_templates.iter().flatten())
+	}
+
+	/// Returns the optional data that needs to be fetched from GitHub.
+	///
+	/// The milestones are only fetched if they are used in the templates or
+	/// the milestone of the latest release is closed (see
+	/// [`close_milestone`](crate::config::RemoteConfig::close_milestone)).
+	/// The repository is only fetched if its values that are only known by
+	/// the forge are used in the templates (e.g. the description).
+	#[cfg(feature = "github")]
+	fn github_metadata_options(&self) -> GitHubMetadataOptions {
+		GitHubMetadataOptions {
+			milestones: self.config.remote.close_milestone.unwrap_or(false) ||
+				self.templates()
+					.any(|v| v.contains_variable(MILESTONE_VARIABLES)),
+			repository: self
+				.templates()
+				.any(|v| v.contains_variable(REPOSITORY_VARIABLES)),
+		}
+	}
+
+	/// Returns `true` if the remote data needs to be fetched for generating
+	/// the changelog.
+	///
+	/// Always returns `false` since the remote integrations are not enabled.
+	#[cfg(not(feature = "github"))]
+	pub fn uses_remote(&self) -> bool {
+		false
+	}
+
+	/// Returns the number of the requests that are sent to GitHub so far.
+	#[cfg(feature = "github")]
+	pub fn github_requests(&self) -> usize {
+		self.github_requests.load(Ordering::Relaxed)
+	}
+
+	/// Returns `true` if the remote data is not fetched completely while
+	/// generating the changelog.
+	pub fn is_remote_degraded(&self) -> bool {
+		self.remote_degraded.get()
+	}
+
+	/// Returns `true` if there are commits that do not belong to a release.
+	pub fn has_unreleased_changes(&self) -> bool {
+		self.releases
+			.iter()
+			.any(|release| release.version.is_none() && !release.commits.is_empty())
+	}
+
+	/// Returns `true` if any of the releases contains a conventional commit.
+	pub fn has_conventional_commits(&self) -> bool {
+		self.releases
+			.iter()
+			.flat_map(|release| release.commits.iter())
+			.any(|commit| commit.conv.is_some())
 	}
 
 	/// Increments the version for the unreleased changes based on semver.
 	pub fn bump_version(&mut self) -> Result<Option<String>> {
+		Ok(self
+			.bump_version_with_rationale()?
+			.map(|rationale| rationale.next_version))
+	}
+
+	/// Increments the version for the unreleased changes based on semver and
+	/// returns the explanation of the increment.
+	pub fn bump_version_with_rationale(&mut self) -> Result<Option<BumpRationale>> {
 		if let Some(ref mut last_release) = self.releases.iter_mut().next() {
 			if last_release.version.is_none() {
-				let next_version = last_release.calculate_next_version()?;
-				debug!("Bumping the version to {next_version}");
-				last_release.version = Some(next_version.to_string());
+				let mut bump_config = self.config.bump.clone();
+				for commit_type in self.config.git.commit_types.iter().flatten() {
+					if let Some(bump) = commit_type.bump {
+						bump_config
+							.types
+							.entry(commit_type.name.to_lowercase())
+							.or_insert(bump);
+					}
+				}
+				let rationale = last_release.bump(&bump_config)?;
+				debug!("Bumping the version to {}", rationale.next_version);
+				last_release.version = Some(rationale.next_version.to_string());
 				last_release.timestamp = SystemTime::now()
 					.duration_since(UNIX_EPOCH)?
 					.as_secs()
 					.try_into()?;
-				return Ok(Some(next_version));
+				return Ok(Some(rationale));
 			}
 		}
 		Ok(None)
 	}
 
-	/// Generates the changelog and writes it to the given output.
-	pub fn generate<W: Write>(&self, out: &mut W) -> Result<()> {
-		debug!("Generating changelog...");
-		let mut additional_context = HashMap::new();
-		additional_context.insert("remote", self.config.remote.clone());
-		#[cfg(feature = "github")]
-		let (github_commits, github_pull_requests) = self.get_github_metadata()?;
+	/// Generates the changelog and writes it to the given output.
+	pub fn generate<W: Write>(&self, out: &mut W) -> Result<()> {
+		debug!("Generating changelog...");
+		let releases = self.releases_with_remote_data()?;
+		if let Some(max_body_size) = self.config.changelog.max_body_size {
+			let output = self.generate_with_budget(releases, max_body_size)?;
+			if let Err(e) = out.write_all(&output) {
+				if e.kind() != std::io::ErrorKind::BrokenPipe {
+					return Err(e.into());
+				}
+			}
+			Ok(())
+		} else {
+			self.write_releases(releases, out)
+		}
+	}
+
+	/// Sets the metadata of the repository that is available as `repository`
+	/// in the templates.
+	pub fn set_repository(&mut self, repository: RepositoryMetadata) {
+		self.repository = repository;
+	}
+
+	/// Sets the artifacts of the latest release that are available as
+	/// `assets` in the templates.
+	pub fn set_assets(&mut self, assets: Vec<ReleaseAsset>) {
+		if let Some(release) = self.releases.first_mut() {
+			release.assets = assets;
+		}
+	}
+
+	/// Returns the releases of the changelog (newest first).
+	pub fn releases(&self) -> &[Release<'a>] {
+		&self.releases
+	}
+
+	/// Returns the latest release.
+	pub fn latest_release(&self) -> Option<&Release<'a>> {
+		self.releases.first()
+	}
+
+	/// Returns the open GitHub milestone that is titled after the version of
+	/// the latest release.
+	///
+	/// Returns `None` if there is no such milestone (e.g. the latest release
+	/// is unreleased or it is already closed).
+	#[cfg(feature = "github")]
+	pub fn open_github_milestone(&self) -> Result<Option<GitHubMilestone>> {
+		let Some(version) = self.latest_release().and_then(|v| v.version.clone())
+		else {
+			return Ok(None);
+		};
+		let milestones = match self.github_metadata.get() {
+			Some(metadata) if self.github_metadata_options().milestones => {
+				metadata.milestones.clone()
+			}
+			_ => {
+				let github_client =
+					GitHubClient::try_from(self.config.remote.github.clone())?
+						.with_request_counter(Arc::clone(&self.github_requests));
+				tokio::runtime::Builder::new_multi_thread()
+					.enable_all()
+					.build()?
+					.block_on(github_client.get_milestones())?
+			}
+		};
+		Ok(milestones
+			.into_iter()
+			.find(|v| v.is_open() && v.matches_version(&version)))
+	}
+
+	/// Closes the open GitHub milestone that is titled after the version of
+	/// the latest release (see [`Changelog::open_github_milestone`]).
+	///
+	/// Returns the closed milestone or `None` if there is no such milestone.
+	#[cfg(feature = "github")]
+	pub fn close_github_milestone(&self) -> Result<Option<GitHubMilestone>> {
+		let Some(milestone) = self.open_github_milestone()? else {
+			return Ok(None);
+		};
+		let github_client =
+			GitHubClient::try_from(self.config.remote.github.clone())?
+				.with_request_counter(Arc::clone(&self.github_requests));
+		tokio::runtime::Builder::new_multi_thread()
+			.enable_all()
+			.build()?
+			.block_on(async {
+				Ok(Some(github_client.close_milestone(milestone.number).await?))
+			})
+	}
+
+	/// Renders the notes of the latest release with the body template.
+	///
+	/// The header and the footer are not included, which makes the notes
+	/// suitable for pasting into a release form. Returns `None` if there are
+	/// no releases.
+	pub fn release_notes(&self) -> Result<Option<String>> {
+		let releases = self.releases_with_remote_data()?;
+		let Some(release) = releases.first() else {
+			return Ok(None);
+		};
+		let additional_context = self.additional_context()?;
+		let notes = self.body_template(release).render(
+			release,
+			Some(&additional_context),
+			self.config
+				.changelog
+				.postprocessors
+				.as_deref()
+				.unwrap_or_default(),
+		)?;
+		Ok(Some(self.shorten_urls(notes)?.trim().to_string()))
+	}
+
+	/// Renders the message of the annotated tag for the latest release and
+	/// returns it along with the version.
+	///
+	/// The [`tag_message`] template is used if it is set, otherwise the
+	/// release is rendered with the body template. Returns `None` if the
+	/// latest release does not have a version.
+	///
+	/// [`tag_message`]: crate::config::ChangelogConfig::tag_message
+	pub fn tag_message(&self) -> Result<Option<(String, String)>> {
+		let releases = self.releases_with_remote_data()?;
+		let Some(release) = releases.first() else {
+			return Ok(None);
+		};
+		let Some(version) = &release.version else {
+			return Ok(None);
+		};
+		let additional_context = self.additional_context()?;
+		let message = self
+			.tag_template
+			.as_ref()
+			.unwrap_or_else(|| self.body_template(release))
+			.render(
+				release,
+				Some(&additional_context),
+				self.config
+					.changelog
+					.postprocessors
+					.as_deref()
+					.unwrap_or_default(),
+			)?;
+		Ok(Some((version.to_string(), message.trim().to_string())))
+	}
+
+	/// Returns the additional context of the templates.
+	///
+	/// The metadata of the repository is completed with the data from GitHub
+	/// if it is fetched (e.g. the description and topics).
+	fn additional_context(
+		&self,
+	) -> Result<HashMap<&'static str, serde_json::Value>> {
+		#[cfg_attr(not(feature = "github"), allow(unused_mut))]
+		let mut repository = self.repository.clone();
+		#[cfg(feature = "github")]
+		if let Some(github) = self
+			.github_metadata
+			.get()
+			.and_then(|metadata| metadata.repository.as_ref())
+		{
+			repository
+				.name
+				.get_or_insert_with(|| github.full_name.to_string());
+			if repository.default_branch.is_none() {
+				repository.default_branch = github.default_branch.clone();
+			}
+			if repository.url.is_none() {
+				repository.url = github.html_url.clone();
+			}
+			repository.description = github.description.clone();
+			repository.topics = github.topics.clone();
+		}
+		Ok(HashMap::from([
+			("remote", serde_json::to_value(&self.config.remote)?),
+			("repository", serde_json::to_value(repository)?),
+		]))
+	}
+
+	/// Returns the releases that are updated with the remote data.
+	///
+	/// The commits are already updated while they are processed if the
+	/// remote data changes them (see [`Changelog::process_remote_data`]).
+	fn releases_with_remote_data(&self) -> Result<Vec<Release<'a>>> {
+		#[cfg(feature = "github")]
+		let github_metadata = self.get_github_metadata_or_default()?;
+		#[cfg_attr(not(feature = "github"), allow(unused_mut))]
+		let mut releases = self.releases.clone();
+		#[cfg(feature = "github")]
+		for release in releases.iter_mut() {
+			release.update_github_metadata(
+				github_metadata.commits.clone(),
+				github_metadata.pull_requests.clone(),
+			)?;
+			release.update_github_milestone(&github_metadata.milestones);
+		}
+		Ok(releases)
+	}
+
+	/// Generates the changelog so that it fits into the given size (in bytes).
+	///
+	/// The groups are elided in the reverse order of their priority (i.e. the
+	/// order of [`commit_parsers`]) until the output fits. The groups that are
+	/// not set by the commit parsers are elided first, the ones with the most
+	/// words in the first place. A note is appended to the output for the
+	/// elided groups (see [`elision_note`]).
+	///
+	/// [`elision_note`]: crate::config::ChangelogConfig::elision_note
+	/// [`commit_parsers`]: crate::config::GitConfig::commit_parsers
+	fn generate_with_budget(
+		&self,
+		mut releases: Vec<Release<'a>>,
+		max_body_size: usize,
+	) -> Result<Vec<u8>> {
+		let mut groups: Vec<String> = self
+			.config
+			.git
+			.commit_parsers
+			.iter()
+			.flatten()
+			.filter_map(|parser| parser.group.clone())
+			.collect();
+		let mut words = HashMap::<String, usize>::new();
+		for release in &releases {
+			for (group, stats) in &release.stats.groups {
+				*words.entry(group.to_string()).or_insert(0) += stats.words;
+			}
+		}
+		let mut other_groups = words
+			.into_iter()
+			.filter(|(group, _)| !groups.contains(group))
+			.collect::<Vec<(String, usize)>>();
+		other_groups.sort_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)));
+		groups.extend(other_groups.into_iter().map(|(group, _)| group));
+		let mut seen_groups = Vec::new();
+		groups.retain(|group| {
+			if seen_groups.contains(group) {
+				false
+			} else {
+				seen_groups.push(group.clone());
+				true
+			}
+		});
+		let note_template = Template::new(
+			self.config
+				.changelog
+				.elision_note
+				.clone()
+				.unwrap_or_else(|| String::from(DEFAULT_ELISION_NOTE)),
+			false,
+		)?;
+		let mut elided_groups = Vec::new();
+		loop {
+			let mut output = Vec::new();
+			self.write_releases(releases.clone(), &mut output)?;
+			if !elided_groups.is_empty() {
+				writeln!(
+					output,
+					"{}",
+					self.elision_note(&note_template, &elided_groups)?
+				)?;
+			}
+			if output.len() <= max_body_size {
+				return Ok(output);
+			}
+			let Some(group) = groups.pop() else {
+				warn!(
+					"Changelog does not fit into {max_body_size} bytes ({} bytes)",
+					output.len()
+				);
+				return Ok(output);
+			};
+			let mut elided = false;
+			for release in releases.iter_mut() {
+				let commit_count = release.commits.len();
+				release
+					.commits
+					.retain(|commit| commit.group_name().as_ref() != Some(&group));
+				elided |= release.commits.len() != commit_count;
+				release.stats = ReleaseStats::new(&release.commits);
+			}
+			if elided {
+				debug!("Eliding group to fit the size budget: {group}");
+				elided_groups.push(group);
+			}
+		}
+	}
+
+	/// Renders the note that is appended to the changelog when groups are
+	/// elided for fitting the size budget.
+	fn elision_note(
+		&self,
+		template: &Template,
+		elided_groups: &[String],
+	) -> Result<String> {
+		template.render(
+			&serde_json::json!({ "groups": elided_groups }),
+			Some(&self.additional_context()?),
+			&[],
+		)
+	}
+
+	/// Shifts the markdown headings of a rendered release so that the release
+	/// heading is at [`heading_level`] and numbers them if
+	/// [`heading_numbering`] is enabled.
+	///
+	/// The numbers of the previous headings are kept in `numbers` so that the
+	/// releases are numbered consecutively.
+	///
+	/// [`heading_level`]: crate::config::ChangelogConfig::heading_level
+	/// [`heading_numbering`]: crate::config::ChangelogConfig::heading_numbering
+	fn adjust_headings(&self, body: &str, numbers: &mut Vec<usize>) -> String {
+		let headings = markdown::headings(body)
+			.into_iter()
+			.map(|v| (v.line, v.depth))
+			.collect::<Vec<(usize, usize)>>();
+		let Some(min_depth) = headings.iter().map(|(_, depth)| *depth).min() else {
+			return body.to_string();
+		};
+		let level = self
+			.config
+			.changelog
+			.heading_level
+			.unwrap_or(min_depth)
+			.clamp(1, 6);
+		let numbering = self.config.changelog.heading_numbering.unwrap_or(false);
+		let mut lines: Vec<String> = body.lines().map(String::from).collect();
+		for (i, depth) in headings {
+			let relative_depth = depth - min_depth;
+			let heading = lines[i][depth..].trim_start().to_string();
+			let prefix = if numbering {
+				numbers.resize(relative_depth + 1, 0);
+				numbers[relative_depth] += 1;
+				let number = numbers
+					.iter()
+					.map(|v| v.to_string())
+					.collect::<Vec<String>>()
+					.join(".");
+				format!("{number}. ")
+			} else {
+				String::new()
+			};
+			lines[i] = format!(
+				"{} {prefix}{heading}",
+				"#".repeat((level + relative_depth).min(6))
+			);
+		}
+		let mut output = lines.join("\n");
+		if body.ends_with('\n') {
+			output.push('\n');
+		}
+		output
+	}
+
+	/// Renders the given releases along with the header and footer and writes
+	/// them to the given output.
+	fn write_releases<W: Write>(
+		&self,
+		releases: Vec<Release<'a>>,
+		out: &mut W,
+	) -> Result<()> {
+		let additional_context = self.additional_context()?;
 		let postprocessors = self
 			.config
 			.changelog
@@ -233,22 +1194,24 @@ impl<'a> Changelog<'a> {
 				}
 			}
 		}
-		let mut releases = self.releases.clone();
-		for release in releases.iter_mut() {
-			#[cfg(feature = "github")]
-			release.update_github_metadata(
-				github_commits.clone(),
-				github_pull_requests.clone(),
+		let mut heading_numbers = Vec::new();
+		for release in releases.iter() {
+			let pages = self.paginate(release)?;
+			let mut output = self.body_template(release).render(
+				&pages[0].0,
+				Some(&additional_context),
+				&postprocessors,
 			)?;
-			let write_result = write!(
-				out,
-				"{}",
-				self.body_template.render(
-					&release,
-					Some(&additional_context),
-					&postprocessors
-				)?
-			);
+			if let Some((_, Some(path))) = pages.get(1) {
+				output += &self.continuation(release, 2, pages.len(), path)?;
+			}
+			if self.config.changelog.heading_level.is_some() ||
+				self.config.changelog.heading_numbering.unwrap_or(false)
+			{
+				output = self.adjust_headings(&output, &mut heading_numbers);
+			}
+			output = self.shorten_urls(output)?;
+			let write_result = write!(out, "{output}");
 			if let Err(e) = write_result {
 				if e.kind() != std::io::ErrorKind::BrokenPipe {
 					return Err(e.into());
@@ -259,13 +1222,13 @@ impl<'a> Changelog<'a> {
 			let write_result = writeln!(
 				out,
 				"{}",
-				footer_template.render(
+				self.shorten_urls(footer_template.render(
 					&Releases {
 						releases: &releases,
 					},
 					Some(&additional_context),
 					&postprocessors,
-				)?
+				)?)?
 			);
 			if let Err(e) = write_result {
 				if e.kind() != std::io::ErrorKind::BrokenPipe {
@@ -276,18 +1239,212 @@ impl<'a> Changelog<'a> {
 		Ok(())
 	}
 
+	/// Splits the commits of the release into pages of the configured size
+	/// and returns the releases of the pages along with their paths.
+	///
+	/// The first page does not have a path since it is rendered in the
+	/// changelog.
+	fn paginate(
+		&self,
+		release: &Release<'a>,
+	) -> Result<Vec<(Release<'a>, Option<String>)>> {
+		let max_commits = self
+			.config
+			.changelog
+			.pagination
+			.as_ref()
+			.map(|v| v.max_commits)
+			.unwrap_or_default();
+		let Some((path_template, _)) = &self.page_templates else {
+			return Ok(vec![(release.clone(), None)]);
+		};
+		if max_commits == 0 || release.commits.len() <= max_commits {
+			return Ok(vec![(release.clone(), None)]);
+		}
+		let chunks = release.commits.chunks(max_commits).collect::<Vec<_>>();
+		// the version is a part of a file name (e.g. `api/v1.0.0` is `api-v1.0.0`)
+		let version = release
+			.version
+			.as_deref()
+			.unwrap_or("unreleased")
+			.chars()
+			.map(|c| {
+				if c.is_alphanumeric() || "._+-".contains(c) {
+					c
+				} else {
+					'-'
+				}
+			})
+			.collect::<String>();
+		let version = version.trim_start_matches('.');
+		chunks
+			.iter()
+			.enumerate()
+			.map(|(i, commits)| {
+				let path = if i == 0 {
+					None
+				} else {
+					Some(
+						path_template
+							.render(
+								&serde_json::json!({
+									"version": version,
+									"page": i + 1,
+									"pages": chunks.len(),
+								}),
+								Option::<HashMap<&str, String>>::None.as_ref(),
+								&[],
+							)?
+							.trim()
+							.to_string(),
+					)
+				};
+				Ok((
+					Release {
+						commits: commits.to_vec(),
+						..release.clone()
+					},
+					path,
+				))
+			})
+			.collect()
+	}
+
+	/// Renders the link to the given page of the release.
+	fn continuation(
+		&self,
+		release: &Release,
+		page: usize,
+		pages: usize,
+		path: &str,
+	) -> Result<String> {
+		let Some((_, continuation_template)) = &self.page_templates else {
+			return Ok(String::new());
+		};
+		continuation_template.render(
+			&serde_json::json!({
+				"version": release.version,
+				"page": page,
+				"pages": pages,
+				"path": path,
+			}),
+			Option::<HashMap<&str, String>>::None.as_ref(),
+			&[],
+		)
+	}
+
+	/// Renders the continuation pages of the long releases and returns them
+	/// along with their paths.
+	///
+	/// See [`pagination`] for the configuration.
+	///
+	/// [`pagination`]: crate::config::ChangelogConfig::pagination
+	pub fn pages(&self) -> Result<Vec<(String, String)>> {
+		if self.page_templates.is_none() {
+			return Ok(Vec::new());
+		}
+		let releases = self.releases_with_remote_data()?;
+		let additional_context = self.additional_context()?;
+		let postprocessors = self
+			.config
+			.changelog
+			.postprocessors
+			.clone()
+			.unwrap_or_default();
+		let mut output = Vec::new();
+		for release in releases {
+			let pages = self.paginate(&release)?;
+			for (i, (page, path)) in pages.iter().enumerate() {
+				let Some(path) = path else {
+					continue;
+				};
+				let mut content = self.body_template(&release).render(
+					page,
+					Some(&additional_context),
+					&postprocessors,
+				)?;
+				if let Some((_, Some(next_path))) = pages.get(i + 1) {
+					// link relative to the directory of the page
+					let next_path = Path::new(path)
+						.parent()
+						.and_then(|dir| Path::new(next_path).strip_prefix(dir).ok())
+						.map(|v| v.to_string_lossy().to_string())
+						.unwrap_or(next_path.to_string());
+					content += &self.continuation(
+						&release,
+						i + 2,
+						pages.len(),
+						&next_path,
+					)?;
+				}
+				output.push((path.to_string(), self.shorten_urls(content)?));
+			}
+		}
+		Ok(output)
+	}
+
+	/// Shortens the long URLs in the rendered output via the configured
+	/// [`url_shortener`].
+	///
+	/// [`url_shortener`]: crate::config::ChangelogConfig::url_shortener
+	fn shorten_urls(&self, output: String) -> Result<String> {
+		match &self.config.changelog.url_shortener {
+			Some(url_shortener) => {
+				url_shortener.shorten(&output, &mut self.short_urls.borrow_mut())
+			}
+			None => Ok(output),
+		}
+	}
+
 	/// Generates a changelog and prepends it to the given changelog.
-	pub fn prepend<W: Write>(
+	///
+	/// The new releases are inserted before the first release section of the
+	/// changelog, which is the first heading at the level of the generated
+	/// release headings that contains a version. Everything above it (e.g.
+	/// header, badges and manually written notes) is kept as is and replaces
+	/// the generated header.
+	pub fn prepend<W: Write>(&self, changelog: String, out: &mut W) -> Result<()> {
+		debug!("Generating changelog and prepending...");
+		let mut output = Vec::new();
+		self.generate(&mut output)?;
+		let output = str::from_utf8(&output)?;
+		let (header, body) = match &self.config.changelog.header {
+			Some(header) if output.starts_with(header.as_str()) => {
+				output.split_at(header.len())
+			}
+			_ => ("", output),
+		};
+		let release_depth = markdown::headings(body).first().map(|v| v.depth);
+		let position = markdown::headings(&changelog)
+			.into_iter()
+			.find(|v| {
+				release_depth.map_or(true, |depth| v.depth == depth) &&
+					VERSION_REGEX.is_match(v.text)
+			})
+			.map(|v| v.offset)
+			.unwrap_or(changelog.len());
+		let (preamble, releases) = changelog.split_at(position);
+		if preamble.trim().is_empty() {
+			write!(out, "{header}")?;
+		} else {
+			trace!("Keeping the top of the changelog: {preamble:?}");
+			write!(out, "{preamble}")?;
+		}
+		write!(out, "{body}{releases}")?;
+		Ok(())
+	}
+
+	/// Prints the releases as a graph to the given output.
+	pub fn write_graph<W: Write>(
 		&self,
-		mut changelog: String,
 		out: &mut W,
+		format: GraphFormat,
 	) -> Result<()> {
-		debug!("Generating changelog and prepending...");
-		if let Some(header) = &self.config.changelog.header {
-			changelog = changelog.replacen(header, "", 1);
+		let output = Releases {
+			releases: &self.releases,
 		}
-		self.generate(out)?;
-		write!(out, "{changelog}")?;
+		.as_graph(format);
+		writeln!(out, "{output}")?;
 		Ok(())
 	}
 
@@ -300,28 +1457,81 @@ impl<'a> Changelog<'a> {
 		writeln!(out, "{output}")?;
 		Ok(())
 	}
+
+	/// Prints the releases as a compact JSON timeline to the given output.
+	pub fn write_timeline<W: Write>(&self, out: &mut W) -> Result<()> {
+		let output = Releases {
+			releases: &self.releases,
+		}
+		.as_timeline(&self.config.git)?;
+		writeln!(out, "{output}")?;
+		Ok(())
+	}
+
+	/// Prints the badge data of the latest release to the given output.
+	///
+	/// See [`Releases::as_badge`].
+	pub fn write_badge<W: Write>(&self, out: &mut W) -> Result<()> {
+		let output = Releases {
+			releases: &self.releases,
+		}
+		.as_badge()?;
+		writeln!(out, "{output}")?;
+		Ok(())
+	}
+
+	/// Prints the releases as an SQL script to the given output.
+	///
+	/// See [`Releases::as_sql`].
+	pub fn write_sql<W: Write>(&self, out: &mut W, timestamp: i64) -> Result<()> {
+		let output = Releases {
+			releases: &self.releases,
+		}
+		.as_sql(timestamp);
+		write!(out, "{output}")?;
+		Ok(())
+	}
+
+	/// Prints the results of the query on the changelog context to the given
+	/// output.
+	///
+	/// Strings are printed as is and other values are printed as JSON.
+	pub fn write_query<W: Write>(&self, out: &mut W, path: &str) -> Result<()> {
+		let context = serde_json::to_value(&self.releases)?;
+		for value in query::query(&context, path)? {
+			match value {
+				serde_json::Value::String(v) => writeln!(out, "{v}")?,
+				v => writeln!(out, "{v}")?,
+			}
+		}
+		Ok(())
+	}
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
 	use crate::config::{
+		BumpConfig,
+		BumpLevel,
 		ChangelogConfig,
 		CommitParser,
+		CommitType,
+		EraConfig,
 		GitConfig,
+		LintConfig,
 		Remote,
 		RemoteConfig,
 		TextProcessor,
 	};
 	use pretty_assertions::assert_eq;
 	use regex::Regex;
-	use std::str;
 
 	fn get_test_data() -> (Config, Vec<Release<'static>>) {
 		let config = Config {
 			changelog: ChangelogConfig {
-				header:         Some(String::from("# Changelog")),
-				body:           Some(String::from(
+				header:            Some(String::from("# Changelog")),
+				body:              Some(String::from(
 					r#"{% if version %}
 				## Release [{{ version }}] - {{ timestamp | date(format="%Y-%m-%d") }}
 				{% if commit_id %}({{ commit_id }}){% endif %}{% else %}
@@ -332,21 +1542,38 @@ mod test {
 				- {{ commit.message }}{% endfor %}
 				{% endfor %}{% endfor %}"#,
 				)),
-				footer:         Some(String::from(
+				footer:            Some(String::from(
 					r#"-- total releases: {{ releases | length }} --"#,
 				)),
-				trim:           Some(true),
-				postprocessors: Some(vec![TextProcessor {
+				trim:              Some(true),
+				postprocessors:    Some(vec![TextProcessor {
 					pattern:         Regex::new("boring")
 						.expect("failed to compile regex"),
 					replace:         Some(String::from("exciting")),
 					replace_command: None,
 				}]),
+				commit_template:   None,
+				tag_message:       None,
+				url_shortener:     None,
+				max_body_size:     None,
+				elision_note:      None,
+				git_read_path:     None,
+				heading_level:     None,
+				heading_numbering: None,
+				commit_groups:     None,
+				collation:         None,
+				output_flavor:     None,
+				internal_domains:  None,
+				normalize:         None,
+				pagination:        None,
+				template_limits:   None,
 			},
 			git:       GitConfig {
 				conventional_commits:     Some(true),
 				filter_unconventional:    Some(false),
 				split_commits:            Some(false),
+				pull_request_merges:      None,
+				raw_entries:              None,
 				commit_preprocessors:     Some(vec![TextProcessor {
 					pattern:         Regex::new("<preprocess>")
 						.expect("failed to compile regex"),
@@ -355,6 +1582,7 @@ mod test {
 					)),
 					replace_command: None,
 				}]),
+				commit_types:             None,
 				commit_parsers:           Some(vec![
 					CommitParser {
 						sha:           Some(String::from("tea")),
@@ -366,6 +1594,10 @@ mod test {
 						skip:          None,
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           Some(String::from("coffee")),
@@ -377,6 +1609,10 @@ mod test {
 						skip:          Some(true),
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           Some(String::from("coffee2")),
@@ -388,6 +1624,10 @@ mod test {
 						skip:          Some(true),
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           None,
@@ -399,6 +1639,10 @@ mod test {
 						skip:          Some(true),
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           None,
@@ -410,6 +1654,10 @@ mod test {
 						skip:          None,
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           None,
@@ -421,6 +1669,10 @@ mod test {
 						skip:          None,
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           None,
@@ -432,6 +1684,10 @@ mod test {
 						skip:          None,
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           None,
@@ -443,6 +1699,10 @@ mod test {
 						skip:          None,
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           None,
@@ -454,6 +1714,10 @@ mod test {
 						skip:          None,
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 					CommitParser {
 						sha:           None,
@@ -465,6 +1729,10 @@ mod test {
 						skip:          None,
 						field:         None,
 						pattern:       None,
+						pattern_not:   None,
+						and:           None,
+						or:            None,
+						continue_:     None,
 					},
 				]),
 				protect_breaking_commits: None,
@@ -474,16 +1742,41 @@ mod test {
 				ignore_tags:              None,
 				topo_order:               Some(false),
 				sort_commits:             Some(String::from("oldest")),
+				sort_group_commits:       None,
 				link_parsers:             None,
 				limit_commits:            None,
+				release_period:           None,
+				commit_date:              None,
+				signing_keys:             None,
+				require_signed_commits:   None,
+				path_renames:             None,
+				embargoes:                None,
+				lift_embargoes:           false,
+				default_scope:            None,
+				scope_aliases:            None,
+				commit_filter:            None,
+				min_scope_commits:        None,
+				other_scope:              None,
+				hide_small_scopes:        None,
 			},
 			remote:    RemoteConfig {
-				github: Remote {
+				github:           Remote {
 					owner: String::from("coolguy"),
 					repo:  String::from("awesome"),
 					token: None,
 				},
+				required:         None,
+				skip_labels:      None,
+				group_labels:     None,
+				search_users:     None,
+				merge_pr_commits: None,
+				close_milestone:  None,
+				offline:          false,
 			},
+			lint:      LintConfig::default(),
+			bump:      BumpConfig::default(),
+			eras:      None,
+			workspace: None,
 		};
 		let test_release = Release {
 			version: Some(String::from("v1.0.0")),
@@ -548,9 +1841,14 @@ mod test {
 			commit_id: Some(String::from("0bc123")),
 			timestamp: 50000000,
 			previous: None,
+			moved_from: None,
+			authors: None,
+			assets: Vec::new(),
+			stats: Default::default(),
 			#[cfg(feature = "github")]
 			github: crate::github::GitHubReleaseMetadata {
 				contributors: vec![],
+				milestone:    None,
 			},
 		};
 		let releases = vec![
@@ -595,9 +1893,14 @@ mod test {
 				commit_id: None,
 				timestamp: 1000,
 				previous: Some(Box::new(test_release)),
+				moved_from: None,
+				authors: None,
+				assets: Vec::new(),
+				stats: Default::default(),
 				#[cfg(feature = "github")]
 				github: crate::github::GitHubReleaseMetadata {
 					contributors: vec![],
+					milestone:    None,
 				},
 			},
 		];
@@ -680,6 +1983,489 @@ mod test {
 		Ok(())
 	}
 
+	#[test]
+	fn changelog_generator_commit_template() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.commit_template = Some(String::from(
+			"{% if scope %}*({{ scope }})* {% endif %}{{ message }}",
+		));
+		config.changelog.body = Some(String::from(
+			r#"{% for commit in commits %}
+			- {{ commit.formatted }}{% endfor %}"#,
+		));
+		config.changelog.header = None;
+		config.changelog.footer = None;
+		let changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(
+			String::from(
+				r#"
+			- *(app)* add xyz
+			- *(app)* document zyx
+			- *(app)* fix abc
+			- *(ui)* do exciting stuff"#
+			)
+			.replace("			", ""),
+			str::from_utf8(&out).unwrap_or_default()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_tag_message() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.tag_message = Some(String::from(
+			r#"Release {{ version }}
+			{% for commit in commits %}
+			- {{ commit.message }}{% endfor %}"#,
+		));
+		let changelog = Changelog::new(
+			vec![Release {
+				version: Some(String::from("v1.1.0")),
+				..releases[2].clone()
+			}],
+			&config,
+		)?;
+		assert_eq!(
+			Some((
+				String::from("v1.1.0"),
+				String::from(
+					"Release v1.1.0\n\n- add xyz\n- document zyx\n- fix abc\n- do \
+					 exciting stuff"
+				)
+			)),
+			changelog
+				.tag_message()?
+				.map(|(version, message)| (version, message.replace("			", "")))
+		);
+		let changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		assert_eq!(None, changelog.tag_message()?);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_commit_types() -> Result<()> {
+		let (mut config, _) = get_test_data();
+		config.git.commit_parsers = None;
+		config.git.commit_types = Some(vec![CommitType {
+			name:      String::from("deps"),
+			group:     None,
+			bump:      Some(BumpLevel::Minor),
+			highlight: None,
+		}]);
+		let release = Release {
+			commits: vec![Commit::new(
+				String::from("1a2b3c4d"),
+				String::from("deps: bump xyz"),
+			)],
+			previous: Some(Box::new(Release {
+				version: Some(String::from("v1.0.0")),
+				..Default::default()
+			})),
+			..Default::default()
+		};
+		let mut changelog = Changelog::new(vec![release.clone()], &config)?;
+		assert_eq!(Some(String::from("v1.1.0")), changelog.bump_version()?);
+		config
+			.bump
+			.types
+			.insert(String::from("deps"), BumpLevel::Patch);
+		let mut changelog = Changelog::new(vec![release], &config)?;
+		assert_eq!(Some(String::from("v1.0.1")), changelog.bump_version()?);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_release_notes() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			r#"## {{ version | default(value="unreleased") }}
+			{% for commit in commits %}
+			- {{ commit.message }}{% endfor %}"#,
+		));
+		let changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		let notes = changelog.release_notes()?.map(|v| v.replace("			", ""));
+		assert_eq!(
+			Some(String::from(
+				"## unreleased\n\n- add xyz\n- document zyx\n- fix abc\n- do \
+				 exciting stuff"
+			)),
+			notes
+		);
+		assert!(!notes.unwrap_or_default().contains("this is a changelog"));
+		assert_eq!(None, Changelog::new(vec![], &config)?.release_notes()?);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_repository() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			"{{ repository.name }} ({{ repository.default_branch }}, {{ \
+			 repository.url | default(value=\"-\") }})",
+		));
+		let mut changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		changelog.set_repository(RepositoryMetadata {
+			name: Some(String::from("orhun/git-cliff")),
+			default_branch: Some(String::from("main")),
+			..Default::default()
+		});
+		assert_eq!(
+			Some(String::from("orhun/git-cliff (main, -)")),
+			changelog.release_notes()?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_prepend() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.header = Some(String::from("# Changelog\n\n"));
+		config.changelog.body = Some(String::from("## [1.2.0]\n\n- new\n\n"));
+		config.changelog.footer = None;
+		config.changelog.trim = Some(false);
+		let changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		let prepend = |previous: &str| -> Result<String> {
+			let mut out = Vec::new();
+			changelog.prepend(previous.to_string(), &mut out)?;
+			Ok(String::from_utf8_lossy(&out).to_string())
+		};
+		let previous = [
+			"# My Changelog",
+			"",
+			"[![badge](https://x.y/badge.svg)](https://x.y)",
+			"",
+			"```md",
+			"## [0.0.1]",
+			"```",
+			"",
+			"## Unreleased notes",
+			"",
+			"- write by hand",
+			"",
+			"## [1.1.0]",
+			"",
+			"- old",
+			"",
+		]
+		.join("\n");
+		let (preamble, releases) =
+			previous.split_at(previous.find("## [1.1.0]").unwrap_or_default());
+		assert_eq!(
+			format!("{preamble}## [1.2.0]\n\n- new\n\n{releases}"),
+			prepend(&previous)?
+		);
+		assert_eq!(
+			"# Changelog\n\n## [1.2.0]\n\n- new\n\n## [1.1.0]\n\n- old\n",
+			prepend("# Changelog\n\n## [1.1.0]\n\n- old\n")?
+		);
+		assert_eq!(
+			"# Changelog\n\n## [1.2.0]\n\n- new\n\n## [1.1.0]\n",
+			prepend("## [1.1.0]\n")?
+		);
+		assert_eq!("# Changelog\n\n## [1.2.0]\n\n- new\n\n", prepend("")?);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_pagination() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			r#"{% for commit in commits %}- {{ commit.message }}
+			{% endfor %}"#,
+		));
+		config.changelog.header = None;
+		config.changelog.footer = None;
+		config.changelog.pagination = Some(PaginationConfig {
+			max_commits:  2,
+			path:         Some(String::from("pages/{{ version }}-{{ page }}.md")),
+			continuation: Some(String::from("[next]({{ path }})")),
+		});
+		let changelog = Changelog::new(
+			vec![Release {
+				version: Some(String::from("v1.1.0")),
+				..releases[2].clone()
+			}],
+			&config,
+		)?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(
+			"- add xyz\n- document zyx\n[next](pages/v1.1.0-2.md)",
+			str::from_utf8(&out).unwrap_or_default()
+		);
+		assert_eq!(
+			vec![(
+				String::from("pages/v1.1.0-2.md"),
+				String::from("- fix abc\n- do exciting stuff\n")
+			)],
+			changelog.pages()?
+		);
+		let changelog = Changelog::new(
+			vec![Release {
+				version: Some(String::from("../api/v1.1.0")),
+				..releases[2].clone()
+			}],
+			&config,
+		)?;
+		assert_eq!("pages/-api-v1.1.0-2.md", changelog.pages()?[0].0.as_str());
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_max_body_size() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			r#"{% for commit in commits %}
+			- {{ commit.message }} ({{ commit.group }}){% endfor %}"#,
+		));
+		config.changelog.header = None;
+		config.changelog.footer = None;
+		config.changelog.max_body_size = Some(90);
+		config.remote.github = Remote::default();
+		let changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(
+			String::from(
+				r#"
+			- add xyz (New features)
+			- fix abc (Bug Fixes)
+			… Other omitted, see full changelog
+			"#
+			)
+			.replace("			", ""),
+			str::from_utf8(&out).unwrap_or_default()
+		);
+		config.changelog.elision_note = Some(String::from(
+			"\n{{ groups | length }} more in [CHANGES.md](/CHANGES.md)",
+		));
+		let changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(
+			String::from(
+				r#"
+			- add xyz (New features)
+			- fix abc (Bug Fixes)
+			1 more in [CHANGES.md](/CHANGES.md)
+			"#
+			)
+			.replace("			", ""),
+			str::from_utf8(&out).unwrap_or_default()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_heading_level() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			r#"## {{ version }}
+			{% for group, commits in commits | group_by(attribute="group") %}
+			### {{ group }}
+			```
+			# {{ commits | length }}
+			```
+			{% endfor %}"#,
+		));
+		config.changelog.header = None;
+		config.changelog.footer = None;
+		config.changelog.heading_level = Some(3);
+		config.changelog.heading_numbering = Some(true);
+		let mut release = releases[2].clone();
+		release.version = Some(String::from("v1.0.0"));
+		let mut previous_release = release.clone();
+		previous_release.version = Some(String::from("v0.9.0"));
+		previous_release.commits.truncate(1);
+		let changelog = Changelog::new(vec![previous_release, release], &config)?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(
+			String::from(
+				r#"### 1. v1.0.0
+
+			#### 1.1. Bug Fixes
+			```
+			# 1
+			```
+
+			#### 1.2. New features
+			```
+			# 1
+			```
+
+			#### 1.3. Other
+			```
+			# 2
+			```
+			### 2. v0.9.0
+
+			#### 2.1. New features
+			```
+			# 1
+			```
+			"#
+			)
+			.replace("			", ""),
+			str::from_utf8(&out).unwrap_or_default()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_sort_commits() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			r#"{% for group, commits in commits | group_by(attribute="group") %}
+			{{ group }}:{% for commit in commits %} {{ commit.scope }}/{{ commit.message }}{% endfor %}{% endfor %}"#,
+		)
+		.replace("			", ""));
+		config.changelog.header = None;
+		config.changelog.footer = None;
+		config.changelog.trim = Some(false);
+		for (sort_key, expected) in [
+			(
+				CommitSortKey::Scope,
+				"Other: app/do nothing other/support unconventional commits \
+				 other/this commit is preprocessed ui/make good stuff",
+			),
+			(
+				CommitSortKey::Message,
+				"Other: app/do nothing ui/make good stuff other/support \
+				 unconventional commits other/this commit is preprocessed",
+			),
+		] {
+			config.git.sort_group_commits = Some(sort_key);
+			let changelog = Changelog::new(vec![releases[0].clone()], &config)?;
+			let mut out = Vec::new();
+			changelog.generate(&mut out)?;
+			assert_eq!(
+				Some(expected),
+				str::from_utf8(&out).unwrap_or_default().lines().last()
+			);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_eras() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body =
+			Some(String::from("unreleased: {{ commits | length }}\n"));
+		config.changelog.header = None;
+		config.changelog.footer = None;
+		config.changelog.trim = Some(false);
+		config.eras = Some(vec![EraConfig {
+			until: Some(String::from("v1.0.0")),
+			body: Some(String::from("{{ version }}: {{ commits | length }}\n")),
+			git: Some(GitConfig {
+				conventional_commits: Some(false),
+				..Default::default()
+			}),
+			..Default::default()
+		}]);
+		let changelog =
+			Changelog::new(vec![releases[0].clone(), releases[2].clone()], &config)?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(
+			"unreleased: 4\nv1.0.0: 14\n",
+			str::from_utf8(&out).unwrap_or_default()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn changelog_generator_min_scope_commits() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			r#"{% for group, commits in commits | group_by(attribute="group") %}
+			{{ group }}:{% for commit in commits %} {{ commit.scope }}/{{ commit.message }}{% endfor %}{% endfor %}"#,
+		)
+		.replace("			", ""));
+		config.changelog.header = None;
+		config.changelog.footer = None;
+		config.changelog.trim = Some(false);
+		config.git.min_scope_commits = Some(2);
+		config.git.other_scope = Some(String::from("misc"));
+		let changelog = Changelog::new(vec![releases[0].clone()], &config)?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(
+			Some(
+				"Other: other/support unconventional commits misc/make good stuff \
+				 misc/do nothing other/this commit is preprocessed"
+			),
+			str::from_utf8(&out).unwrap_or_default().lines().last()
+		);
+
+		config.git.hide_small_scopes = Some(true);
+		let changelog = Changelog::new(vec![releases[0].clone()], &config)?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(
+			Some(
+				"Other: other/support unconventional commits other/this commit is \
+				 preprocessed"
+			),
+			str::from_utf8(&out).unwrap_or_default().lines().last()
+		);
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "github")]
+	fn changelog_generator_remote_not_required() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			r#"{% for commit in commits %}{{ commit.github.username }}{% endfor %}"#,
+		));
+		config.remote.github = Remote::default();
+		let changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		assert!(changelog.generate(&mut Vec::new()).is_err());
+		config.remote.required = Some(false);
+		let changelog = Changelog::new(vec![releases[2].clone()], &config)?;
+		changelog.generate(&mut Vec::new())?;
+		assert!(changelog.is_remote_degraded());
+		config.remote.offline = true;
+		assert!(matches!(
+			Changelog::new(vec![releases[2].clone()], &config),
+			Err(Error::OfflineError(_))
+		));
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "github")]
+	fn changelog_generator_github_metadata_options() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		for (body, close_milestone, milestones, repository) in [
+			("{{ version }}", None, false, false),
+			("{{ version }}", Some(true), true, false),
+			("{{ github.milestone.html_url }}", None, true, false),
+			(
+				"{{ repository.topics | join(sep=\", \") }}",
+				None,
+				false,
+				true,
+			),
+			("{{ repository.name }}", None, false, false),
+		] {
+			config.changelog.body = Some(String::from(body));
+			config.remote.close_milestone = close_milestone;
+			let changelog = Changelog::new(vec![releases[0].clone()], &config)?;
+			let options = changelog.github_metadata_options();
+			assert_eq!(
+				(milestones, repository),
+				(options.milestones, options.repository),
+				"{body}"
+			);
+		}
+		Ok(())
+	}
+
 	#[test]
 	fn changelog_generator_split_commits() -> Result<()> {
 		let (mut config, mut releases) = get_test_data();
@@ -0,0 +1,215 @@
+use std::cmp::Ordering;
+use unicode_normalization::char::{
+	decompose_canonical,
+	is_combining_mark,
+};
+
+/// Gap between the weights of the letters for placing the tailored letters.
+const WEIGHT_GAP: u32 = 16;
+
+/// Letters that are sorted right after another letter, e.g. `('ñ', 'n')`.
+type Tailoring = &'static [(char, char)];
+
+/// Letters that are sorted right after another letter in the alphabets of
+/// the languages (instead of being sorted as accented letters).
+const TAILORINGS: &[(&[&str], Tailoring)] = &[
+	(&["da", "nb", "nn", "no"], &[
+		('æ', 'z'),
+		('ø', 'æ'),
+		('å', 'ø'),
+	]),
+	(&["sv", "fi"], &[('å', 'z'), ('ä', 'å'), ('ö', 'ä')]),
+	(&["es"], &[('ñ', 'n')]),
+	(&["tr", "az"], &[
+		('ç', 'c'),
+		('ğ', 'g'),
+		('ı', 'h'),
+		('ö', 'o'),
+		('ş', 's'),
+		('ü', 'u'),
+	]),
+	(&["pl"], &[
+		('ą', 'a'),
+		('ć', 'c'),
+		('ę', 'e'),
+		('ł', 'l'),
+		('ń', 'n'),
+		('ó', 'o'),
+		('ś', 's'),
+		('ź', 'z'),
+		('ż', 'ź'),
+	]),
+	(&["cs", "sk"], &[
+		('č', 'c'),
+		('ř', 'r'),
+		('š', 's'),
+		('ž', 'z'),
+	]),
+];
+
+/// Languages that are sorted in the default order without any tailoring.
+const UNTAILORED_LANGUAGES: &[&str] = &[
+	"af", "ca", "de", "en", "eu", "fr", "ga", "gl", "id", "it", "ms", "nl", "pt",
+	"sw", "zu",
+];
+
+/// Letters that are sorted as the given letters if they are not tailored.
+const EXPANSIONS: &[(char, &str)] = &[
+	('ß', "ss"),
+	('æ', "ae"),
+	('œ', "oe"),
+	('ø', "o"),
+	('ł', "l"),
+	('đ', "d"),
+	('ð', "d"),
+	('þ', "th"),
+];
+
+/// Key for sorting a string with a [`Collator`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SortKey {
+	/// Weights of the base letters.
+	primary:   Vec<u32>,
+	/// Lowercase string for ordering the accents.
+	secondary: String,
+	/// Whether if the characters are uppercase for ordering the case.
+	///
+	/// The lowercase letters are sorted first.
+	tertiary:  Vec<bool>,
+	/// Original string for ordering the otherwise equal strings.
+	identical: String,
+}
+
+/// Collator for sorting the strings according to the alphabet of a locale.
+///
+/// The accents and the case are ignored unless the strings are otherwise
+/// equal, and the letters that are separate letters in the alphabet of the
+/// locale (e.g. `å`, `ä` and `ö` in Swedish) are sorted accordingly.
+///
+/// This is a small subset of the Unicode Collation Algorithm, so only the
+/// languages in [`TAILORINGS`] and [`UNTAILORED_LANGUAGES`] are supported.
+#[derive(Debug, Clone, Default)]
+pub struct Collator {
+	/// Letters that are sorted right after another letter.
+	tailoring: Tailoring,
+}
+
+impl Collator {
+	/// Constructs a new instance for the given locale (e.g. `sv` or `sv-SE`).
+	///
+	/// The strings are sorted in the default order if the locale is not
+	/// supported.
+	pub fn new(locale: &str) -> Self {
+		let language = locale
+			.split(['-', '_'])
+			.next()
+			.unwrap_or_default()
+			.to_lowercase();
+		let tailoring = TAILORINGS
+			.iter()
+			.find(|(languages, _)| languages.contains(&language.as_str()))
+			.map(|(_, tailoring)| *tailoring);
+		if tailoring.is_none() && !UNTAILORED_LANGUAGES.contains(&language.as_str())
+		{
+			warn!(
+				"The collation of `{locale}` is not supported, the strings are \
+				 sorted in the default order."
+			);
+		}
+		Self {
+			tailoring: tailoring.unwrap_or_default(),
+		}
+	}
+
+	/// Returns the weight of the tailored letter.
+	fn tailored_weight(&self, c: char) -> Option<u32> {
+		self.tailoring
+			.iter()
+			.find(|(letter, _)| *letter == c)
+			.map(|(_, after)| {
+				self.tailored_weight(*after)
+					.unwrap_or(*after as u32 * WEIGHT_GAP) +
+					1
+			})
+	}
+
+	/// Pushes the primary weights of the character.
+	fn push_weights(&self, c: char, weights: &mut Vec<u32>) {
+		if let Some(weight) = self.tailored_weight(c) {
+			weights.push(weight);
+		} else if let Some((_, expansion)) =
+			EXPANSIONS.iter().find(|(letter, _)| *letter == c)
+		{
+			weights.extend(expansion.chars().map(|v| v as u32 * WEIGHT_GAP));
+		} else {
+			let mut decomposed = Vec::new();
+			decompose_canonical(c, |v| decomposed.push(v));
+			weights.extend(
+				decomposed
+					.into_iter()
+					.filter(|v| !is_combining_mark(*v))
+					.map(|v| v as u32 * WEIGHT_GAP),
+			);
+		}
+	}
+
+	/// Returns the key for sorting the given string.
+	pub fn sort_key(&self, value: &str) -> SortKey {
+		let secondary = value.to_lowercase();
+		let mut primary = Vec::new();
+		for c in secondary.chars() {
+			self.push_weights(c, &mut primary);
+		}
+		SortKey {
+			primary,
+			secondary,
+			tertiary: value.chars().map(char::is_uppercase).collect(),
+			identical: value.to_string(),
+		}
+	}
+
+	/// Compares the given strings.
+	pub fn compare(&self, a: &str, b: &str) -> Ordering {
+		self.sort_key(a).cmp(&self.sort_key(b))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	fn sort<'a>(locale: &str, values: &[&'a str]) -> Vec<&'a str> {
+		let collator = Collator::new(locale);
+		let mut values = values.to_vec();
+		values.sort_by(|a, b| collator.compare(a, b));
+		values
+	}
+
+	#[test]
+	fn collate() {
+		let words = ["zebra", "Öl", "apple", "Äpfel", "ålder", "Ostern", "Zürich"];
+		assert_eq!(
+			vec!["ålder", "Äpfel", "apple", "Öl", "Ostern", "zebra", "Zürich"],
+			sort("de-DE", &words)
+		);
+		assert_eq!(
+			vec!["apple", "Ostern", "zebra", "Zürich", "ålder", "Äpfel", "Öl"],
+			sort("sv", &words)
+		);
+		assert_eq!(
+			vec!["nube", "ñandú", "oso"],
+			sort("es", &["oso", "ñandú", "nube"])
+		);
+		assert_eq!(
+			vec!["ısı", "iğne", "şeker", "top"],
+			sort("tr", &["top", "şeker", "iğne", "ısı"])
+		);
+		assert_eq!(
+			vec!["Strasse", "Straße"],
+			sort("de", &["Straße", "Strasse"])
+		);
+		assert_eq!(vec!["ab", "Ab", "b"], sort("en", &["b", "Ab", "ab"]));
+		assert_eq!(vec!["ab", "Ab", "b"], sort("xx", &["b", "Ab", "ab"]));
+	}
+}
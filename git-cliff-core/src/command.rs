@@ -4,6 +4,7 @@ use std::io::{
 	ErrorKind as IoErrorKind,
 	Write,
 };
+use std::path::Path;
 use std::process::{
 	Command,
 	Stdio,
@@ -67,6 +68,120 @@ pub fn run(
 	}
 }
 
+/// Commands for copying the text to the clipboard in the order of preference.
+const CLIPBOARD_COMMANDS: &[&[&str]] = if cfg!(target_os = "windows") {
+	&[&["clip"]]
+} else if cfg!(target_os = "macos") {
+	&[&["pbcopy"]]
+} else {
+	&[&["wl-copy"], &["xclip", "-selection", "clipboard"], &[
+		"xsel",
+		"--clipboard",
+		"--input",
+	]]
+};
+
+/// Copies the given text to the clipboard.
+///
+/// The first available clipboard command of the platform is used (e.g.
+/// `pbcopy` on macOS or `wl-copy`/`xclip`/`xsel` on Linux).
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+	for command in CLIPBOARD_COMMANDS {
+		let mut child = match Command::new(command[0])
+			.args(&command[1..])
+			.stdin(Stdio::piped())
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.spawn()
+		{
+			Ok(child) => child,
+			Err(e) if e.kind() == IoErrorKind::NotFound => {
+				log::debug!("Clipboard command is not found: {}", command[0]);
+				continue;
+			}
+			Err(e) => return Err(e.into()),
+		};
+		child
+			.stdin
+			.take()
+			.ok_or_else(|| {
+				IoError::new(IoErrorKind::Other, "stdin is not captured")
+			})?
+			.write_all(text.as_bytes())?;
+		let status = child.wait()?;
+		if status.success() {
+			return Ok(());
+		}
+		log::debug!("{} exited with {status}", command[0]);
+	}
+	Err(
+		IoError::new(IoErrorKind::NotFound, "no clipboard command is available")
+			.into(),
+	)
+}
+
+/// Error message for the missing `sqlite3` command.
+const SQLITE_NOT_FOUND: &str = "the sqlite3 command-line tool is required for \
+                                exporting to SQLite databases (use the `sql:` \
+                                export for writing the SQL script instead)";
+
+/// Checks if the `sqlite3` command-line tool is available for
+/// [`run_sqlite`].
+pub fn check_sqlite() -> Result<()> {
+	match Command::new("sqlite3")
+		.arg("-version")
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status()
+	{
+		Ok(status) if status.success() => Ok(()),
+		Ok(status) => Err(IoError::new(
+			IoErrorKind::Other,
+			format!("sqlite3 -version exited with {status}"),
+		)
+		.into()),
+		Err(e) if e.kind() == IoErrorKind::NotFound => {
+			Err(IoError::new(IoErrorKind::NotFound, SQLITE_NOT_FOUND).into())
+		}
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Runs the given SQL script on the SQLite database at the given path.
+///
+/// The database is created if it does not exist. The `sqlite3` command-line
+/// tool is used for this and the script is aborted at the first error.
+pub fn run_sqlite(path: &Path, script: &str) -> Result<()> {
+	let mut child = Command::new("sqlite3")
+		.arg("-bail")
+		.arg(path)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::null())
+		.spawn()
+		.map_err(|e| {
+			if e.kind() == IoErrorKind::NotFound {
+				IoError::new(IoErrorKind::NotFound, SQLITE_NOT_FOUND)
+			} else {
+				e
+			}
+		})?;
+	child
+		.stdin
+		.take()
+		.ok_or_else(|| IoError::new(IoErrorKind::Other, "stdin is not captured"))?
+		.write_all(script.as_bytes())?;
+	let status = child.wait()?;
+	if status.success() {
+		Ok(())
+	} else {
+		Err(IoError::new(
+			IoErrorKind::Other,
+			format!("sqlite3 exited with {status}"),
+		)
+		.into())
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
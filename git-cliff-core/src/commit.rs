@@ -1,5 +1,8 @@
 use crate::config::{
+	CommitCondition,
+	CommitDate,
 	CommitParser,
+	EntryNormalization,
 	GitConfig,
 	LinkParser,
 	TextProcessor,
@@ -32,11 +35,54 @@ use serde::{
 	Deserialize,
 	Serialize,
 };
+use serde_json::{
+	Map,
+	Value,
+};
+use std::fmt;
 
 /// Regular expression for matching SHA1 and a following commit message
 /// separated by a whitespace.
 static SHA1_REGEX: Lazy<Regex> = lazy_regex!(r#"^\b([a-f0-9]{40})\b (.*)$"#);
 
+/// Regular expression for matching a list item in the commit body.
+///
+/// Supports `-`, `*`, `+` and numbered (`1.` or `1)`) markers.
+static LIST_ITEM_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"^\s*(?:[-*+]|\d+[.)])\s+(.*)$"#);
+
+/// Regular expression for matching a breaking change note in the commit
+/// message.
+///
+/// The note can be qualified with a scope, e.g. `BREAKING CHANGE(api): x`.
+static BREAKING_CHANGE_REGEX: Lazy<Regex> = lazy_regex!(
+	r#"^BREAKING[ -]CHANGE(?:\((?P<scope>[^)]+)\))?:\s*(?P<description>.*)$"#
+);
+
+/// Regular expression for matching a footer in the commit message.
+static FOOTER_REGEX: Lazy<Regex> = lazy_regex!(r#"^[\w-]+(?:: | #)"#);
+
+/// Regular expression for matching the subject of a merge commit that is
+/// created by a forge for a pull request.
+///
+/// Supports GitHub (`Merge pull request #123 from x/y`), Bitbucket (`Merged in
+/// x (pull request #123)`), Azure DevOps (`Merged PR 123: x`) and GitLab
+/// (`Merge branch 'x' into 'y'`, see [`MERGE_REQUEST_REGEX`]).
+static PR_MERGE_REGEX: Lazy<Regex> = lazy_regex!(
+	r#"^(?:Merge pull request #(\d+) from \S+|Merged in \S+ \(pull request #(\d+)\)|Merged PR (\d+): (.+)|Merge branch '[^']+' into '[^']+')$"#
+);
+
+/// Regular expression for matching the reference to the merge request in the
+/// body of a GitLab merge commit (e.g. `See merge request x/y!123`).
+static MERGE_REQUEST_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"(?m)^See merge request \S+!(\d+)$"#);
+
+/// Regular expression for matching the raw entry blocks in the commit message
+/// (i.e. the lines between `Changelog-Begin` and `Changelog-End`).
+static RAW_ENTRY_REGEX: Lazy<Regex> = lazy_regex!(
+	r#"(?ims)^[^\S\n]*changelog-begin[^\S\n]*\r?\n(.*?)^[^\S\n]*changelog-end[^\S\n]*$"#
+);
+
 /// Object representing a link
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -47,6 +93,15 @@ pub struct Link {
 	pub href: String,
 }
 
+/// Breaking change that is described in a commit.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BreakingChange {
+	/// Scope of the breaking change.
+	pub scope:       Option<String>,
+	/// Description of the breaking change.
+	pub description: String,
+}
+
 /// A conventional commit footer.
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 struct Footer<'a> {
@@ -76,6 +131,57 @@ impl<'a> From<&'a ConventionalFooter<'a>> for Footer<'a> {
 	}
 }
 
+/// Paragraphs and list items of a commit body.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BodyStructure {
+	/// Paragraphs that are not lists, unwrapped into a single line.
+	pub paragraphs: Vec<String>,
+	/// Items of the lists, with the continuation lines unwrapped.
+	pub items:      Vec<String>,
+}
+
+impl BodyStructure {
+	/// Parses the given commit body.
+	///
+	/// Paragraphs are separated by blank lines. A line that starts with a list
+	/// marker begins a new item and the following indented lines are treated
+	/// as its continuation.
+	pub fn parse(body: &str) -> Self {
+		let mut structure = Self::default();
+		let mut paragraph: Vec<&str> = Vec::new();
+		let mut in_list = false;
+		let flush_paragraph =
+			|paragraph: &mut Vec<&str>, paragraphs: &mut Vec<String>| {
+				if !paragraph.is_empty() {
+					paragraphs.push(paragraph.join(" "));
+					paragraph.clear();
+				}
+			};
+		for line in body.lines() {
+			if line.trim().is_empty() {
+				flush_paragraph(&mut paragraph, &mut structure.paragraphs);
+				in_list = false;
+			} else if let Some(item) =
+				LIST_ITEM_REGEX.captures(line).and_then(|v| v.get(1))
+			{
+				flush_paragraph(&mut paragraph, &mut structure.paragraphs);
+				structure.items.push(item.as_str().trim().to_string());
+				in_list = true;
+			} else if in_list && line.starts_with(char::is_whitespace) {
+				if let Some(item) = structure.items.last_mut() {
+					item.push(' ');
+					item.push_str(line.trim());
+				}
+			} else {
+				in_list = false;
+				paragraph.push(line.trim());
+			}
+		}
+		flush_paragraph(&mut paragraph, &mut structure.paragraphs);
+		structure
+	}
+}
+
 /// Commit signature that indicates authorship.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Signature {
@@ -98,35 +204,121 @@ impl<'a> From<CommitSignature<'a>> for Signature {
 	}
 }
 
+/// Status of the cryptographic signature of a commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+	/// Good signature.
+	Good,
+	/// Good signature with unknown validity (e.g. the key is not trusted).
+	Untrusted,
+	/// Bad signature.
+	Bad,
+	/// Good signature that has expired.
+	Expired,
+	/// Good signature made by an expired key.
+	ExpiredKey,
+	/// Good signature made by a revoked key.
+	Revoked,
+	/// Signature cannot be checked (e.g. the key is missing).
+	Unverified,
+	/// Commit is not signed.
+	Unsigned,
+}
+
+impl SignatureStatus {
+	/// Parses the status from the `%G?` placeholder of `git log`.
+	pub fn from_code(code: &str) -> Self {
+		match code {
+			"G" => Self::Good,
+			"U" => Self::Untrusted,
+			"B" => Self::Bad,
+			"X" => Self::Expired,
+			"Y" => Self::ExpiredKey,
+			"R" => Self::Revoked,
+			"E" => Self::Unverified,
+			_ => Self::Unsigned,
+		}
+	}
+
+	/// Returns `true` if the signature is valid.
+	pub fn is_valid(&self) -> bool {
+		matches!(self, Self::Good | Self::Untrusted)
+	}
+}
+
+impl fmt::Display for SignatureStatus {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Good => write!(f, "good"),
+			Self::Untrusted => write!(f, "untrusted"),
+			Self::Bad => write!(f, "bad"),
+			Self::Expired => write!(f, "expired"),
+			Self::ExpiredKey => write!(f, "expired_key"),
+			Self::Revoked => write!(f, "revoked"),
+			Self::Unverified => write!(f, "unverified"),
+			Self::Unsigned => write!(f, "unsigned"),
+		}
+	}
+}
+
 /// Common commit object that is parsed from a repository.
 #[derive(Debug, Default, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Commit<'a> {
 	/// Commit ID.
-	pub id:            String,
+	pub id:               String,
 	/// Commit message including title, description and summary.
-	pub message:       String,
+	pub message:          String,
 	/// Conventional commit.
 	#[serde(skip_deserializing)]
-	pub conv:          Option<ConventionalCommit<'a>>,
+	pub conv:             Option<ConventionalCommit<'a>>,
 	/// Commit group based on a commit parser or its conventional type.
-	pub group:         Option<String>,
+	pub group:            Option<String>,
 	/// Default commit scope based on (inherited from) conventional type or a
 	/// commit parser.
-	pub default_scope: Option<String>,
+	pub default_scope:    Option<String>,
 	/// Commit scope for overriding the default one.
-	pub scope:         Option<String>,
+	pub scope:            Option<String>,
 	/// A list of links found in the commit
-	pub links:         Vec<Link>,
+	pub links:            Vec<Link>,
 	/// Commit author.
-	pub author:        Signature,
+	pub author:           Signature,
 	/// Committer.
-	pub committer:     Signature,
+	pub committer:        Signature,
+	/// Date of the commit (Unix timestamp).
+	///
+	/// See [`GitConfig::commit_date`].
+	pub date:             i64,
 	/// Whether if the commit has two or more parents.
-	pub merge_commit:  bool,
+	pub merge_commit:     bool,
+	/// Whether if the commit is marked as breaking outside of its message
+	/// (e.g. an entry of a parsed changelog).
+	pub breaking:         bool,
+	/// Commit rendered with the commit template.
+	pub formatted:        Option<String>,
+	/// Whether if the commit is already included in the deployed reference.
+	pub deployed:         bool,
+	/// Status of the signature if the signatures are verified.
+	pub signature_status: Option<SignatureStatus>,
+	/// Fingerprint (or ID) of the key that signed the commit.
+	pub signing_key:      Option<String>,
+	/// External metadata of the commit (e.g. deployment records).
+	pub metadata:         Option<Map<String, Value>>,
+	/// Ignored tag (e.g. a release candidate) that the commit first appeared
+	/// in before it is folded into the release.
+	pub first_seen_in:    Option<String>,
+	/// Number of the pull request if the commit is its merge commit.
+	///
+	/// See [`GitConfig::pull_request_merges`].
+	pub pr_number:        Option<u64>,
+	/// Entry that is written verbatim in the commit message.
+	///
+	/// See [`GitConfig::raw_entries`].
+	pub raw_entry:        Option<String>,
 	/// GitHub metadata of the commit.
 	#[cfg(feature = "github")]
-	pub github:        GitHubContributor,
+	pub github:           GitHubContributor,
 }
 
 impl<'a> From<String> for Commit<'a> {
@@ -151,6 +343,125 @@ impl<'a> From<String> for Commit<'a> {
 	}
 }
 
+/// Commit parser that matches a commit.
+#[derive(Debug, Clone)]
+pub struct ParserMatch<'p> {
+	/// Index of the parser.
+	pub index: usize,
+	/// Matched regex and the text that it is matched against.
+	///
+	/// It is `None` if the parser is matched by the commit SHA or only by its
+	/// conditions (e.g. `pattern_not`).
+	pub regex: Option<(&'p Regex, String)>,
+}
+
+/// Step of processing a commit.
+///
+/// The steps are passed to the trace of [`Commit::process_with_trace`] in
+/// the order that they are taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessStep {
+	/// Raw entry block is extracted.
+	RawEntry(Option<String>),
+	/// Message of the pull request is taken from the merge commit.
+	PullRequestMerge {
+		/// Number of the pull request.
+		number:  u64,
+		/// Message of the pull request.
+		message: String,
+	},
+	/// Commit preprocessor is applied.
+	Preprocessor {
+		/// Index of the preprocessor.
+		index:   usize,
+		/// Whether the pattern matches the message.
+		matched: bool,
+		/// Message after the preprocessing if it is changed.
+		message: Option<String>,
+	},
+	/// Commit is parsed as a conventional commit.
+	Conventional {
+		/// Type of the commit.
+		type_:    String,
+		/// Scope of the commit.
+		scope:    Option<String>,
+		/// Whether the commit is a breaking change.
+		breaking: bool,
+	},
+	/// Commit is not a conventional commit.
+	Unconventional(String),
+	/// Scope of the commit is replaced.
+	ScopeAlias {
+		/// Alias in the commit.
+		alias: String,
+		/// Scope that replaces the alias.
+		scope: String,
+	},
+	/// Commit parser matches the commit.
+	Parser {
+		/// Index of the parser.
+		index: usize,
+		/// Matched regex and the text that it is matched against.
+		regex: Option<(String, String)>,
+	},
+	/// None of the commit parsers matches the commit.
+	NoParser,
+	/// Group is set by the configured commit type.
+	TypeGroup(String),
+	/// Link parser finds links in the commit message.
+	LinkParser {
+		/// Index of the link parser.
+		index: usize,
+		/// Number of the links.
+		links: usize,
+	},
+	/// Date of the commit is set.
+	CommitDate(i64),
+}
+
+/// Applies the preprocessors to the commit message.
+///
+/// The `COMMIT_SHA` environment variable of the commands is set to the given
+/// commit ID. This is the same preprocessing that is done while generating
+/// the changelog (see [`Commit::preprocess`]).
+pub fn preprocess_message(
+	message: &str,
+	commit_id: &str,
+	preprocessors: &[TextProcessor],
+) -> Result<String> {
+	let mut message = message.to_string();
+	for preprocessor in preprocessors {
+		preprocessor.replace(&mut message, vec![("COMMIT_SHA", commit_id)])?;
+	}
+	Ok(message)
+}
+
+/// Parses the commit message as a [conventional commit].
+///
+/// This is the same parsing that is done while generating the changelog
+/// (see [`Commit::into_conventional`]).
+///
+/// [conventional commit]: https://www.conventionalcommits.org
+pub fn parse_conventional(message: &str) -> Result<ConventionalCommit<'_>> {
+	Ok(ConventionalCommit::parse(message)?)
+}
+
+/// Resolves the group of the commit message with the given configuration.
+///
+/// The message is processed the same way as it would be while generating the
+/// changelog (see [`Commit::process`]) and the group is returned along with
+/// the fallback to the conventional type (see [`Commit::group_name`]).
+///
+/// Returns `None` if the commit would be skipped (e.g. by a commit parser or
+/// for not being conventional).
+pub fn resolve_group(message: &str, config: &GitConfig) -> Result<Option<String>> {
+	match Commit::new(String::new(), message.to_string()).process(config) {
+		Ok(commit) => Ok(commit.group_name()),
+		Err(AppError::GroupError(_) | AppError::ParseError(_)) => Ok(None),
+		Err(e) => Err(e),
+	}
+}
+
 #[cfg(feature = "repo")]
 impl<'a> From<&GitCommit<'a>> for Commit<'a> {
 	fn from(commit: &GitCommit<'a>) -> Self {
@@ -181,54 +492,352 @@ impl Commit<'_> {
 	/// * sets the group for the commit
 	/// * extacts links and generates URLs
 	pub fn process(&self, config: &GitConfig) -> Result<Self> {
+		self.process_with_trace(config, |_| {})
+	}
+
+	/// Processes the commit and passes each step to the given trace.
+	///
+	/// See [`Commit::process`].
+	pub fn process_with_trace(
+		&self,
+		config: &GitConfig,
+		mut trace: impl FnMut(ProcessStep),
+	) -> Result<Self> {
 		let mut commit = self.clone();
-		if let Some(preprocessors) = &config.commit_preprocessors {
-			commit = commit.preprocess(preprocessors)?;
+		if config.raw_entries.unwrap_or(false) {
+			commit.raw_entry = self.raw_entry_block();
+			trace(ProcessStep::RawEntry(commit.raw_entry.clone()));
+		}
+		if config.pull_request_merges.unwrap_or(false) {
+			if let Some((number, message)) = commit.pull_request_merge() {
+				trace(ProcessStep::PullRequestMerge {
+					number,
+					message: message.clone(),
+				});
+				commit.pr_number = Some(number);
+				commit.message = message;
+			}
+		}
+		for (index, preprocessor) in
+			config.commit_preprocessors.iter().flatten().enumerate()
+		{
+			let message = commit.message.clone();
+			let matched = preprocessor.pattern.is_match(&message);
+			commit = commit.preprocess(std::slice::from_ref(preprocessor))?;
+			trace(ProcessStep::Preprocessor {
+				index,
+				matched,
+				message: Some(commit.message.clone()).filter(|v| *v != message),
+			});
 		}
 		if config.conventional_commits.unwrap_or(true) {
-			if config.filter_unconventional.unwrap_or(true) {
-				commit = commit.into_conventional()?;
-			} else if let Ok(conv_commit) = commit.clone().into_conventional() {
-				commit = conv_commit;
+			match commit.clone().into_conventional() {
+				Ok(conv_commit) => {
+					commit = conv_commit;
+					if let Some(conv) = &commit.conv {
+						trace(ProcessStep::Conventional {
+							type_:    conv.type_().to_string(),
+							scope:    conv.scope().map(|v| v.to_string()),
+							breaking: conv.breaking(),
+						});
+					}
+				}
+				Err(e) => {
+					trace(ProcessStep::Unconventional(e.to_string()));
+					if config.filter_unconventional.unwrap_or(true) {
+						return Err(e);
+					}
+				}
 			}
 		}
+		let mut scope_alias = None;
+		if let Some((alias, scope)) = commit
+			.scope
+			.clone()
+			.or_else(|| {
+				commit
+					.conv
+					.as_ref()
+					.and_then(|conv| conv.scope())
+					.map(|scope| scope.to_string())
+			})
+			.and_then(|alias| config.scope_aliases.as_ref()?.get_key_value(&alias))
+		{
+			trace(ProcessStep::ScopeAlias {
+				alias: alias.clone(),
+				scope: scope.clone(),
+			});
+			commit.scope = Some(scope.clone());
+			scope_alias = commit.scope.clone();
+		}
+		let type_group = commit.type_group(config);
 		if let Some(parsers) = &config.commit_parsers {
-			commit = commit.parse(
+			let matches = commit.find_parsers(parsers)?;
+			if matches.is_empty() {
+				trace(ProcessStep::NoParser);
+			}
+			for parser_match in &matches {
+				trace(ProcessStep::Parser {
+					index: parser_match.index,
+					regex: parser_match
+						.regex
+						.as_ref()
+						.map(|(regex, text)| (regex.to_string(), text.to_string())),
+				});
+			}
+			commit = commit.apply_parsers(
 				parsers,
+				matches,
 				config.protect_breaking_commits.unwrap_or(false),
-				config.filter_commits.unwrap_or(false),
+				config.filter_commits.unwrap_or(false) && type_group.is_none(),
 			)?;
+			// keep the alias unless the parsers override the scope
+			commit.scope = commit.scope.or(scope_alias);
+		}
+		if let Some(group) = type_group {
+			trace(ProcessStep::TypeGroup(group.clone()));
+			commit.group = Some(group);
+		}
+		for (index, parser) in config.link_parsers.iter().flatten().enumerate() {
+			let count = commit.links.len();
+			commit = commit.parse_links(std::slice::from_ref(parser))?;
+			if commit.links.len() > count {
+				trace(ProcessStep::LinkParser {
+					index,
+					links: commit.links.len() - count,
+				});
+			}
 		}
-		if let Some(parsers) = &config.link_parsers {
-			commit = commit.parse_links(parsers)?;
+		if commit.default_scope.is_none() {
+			commit.default_scope = config.default_scope.clone();
+		}
+		commit.date = commit.timestamp(config.commit_date.unwrap_or_default());
+		if config.commit_date.is_some() {
+			trace(ProcessStep::CommitDate(commit.date));
 		}
 		Ok(commit)
 	}
 
+	/// Returns the timestamp of the commit for the given [`CommitDate`].
+	///
+	/// The merge time falls back to the committer time if the commit is not
+	/// merged via a pull request (or the remote data is not fetched).
+	pub fn timestamp(&self, commit_date: CommitDate) -> i64 {
+		match commit_date {
+			CommitDate::Author => self.author.timestamp,
+			#[cfg(feature = "github")]
+			CommitDate::Merge => self.github.pr_merged_at.unwrap_or(self.committer.timestamp),
+			_ => self.committer.timestamp,
+		}
+	}
+
+	/// Splits the commit into a commit for each line of the message if
+	/// [`split_commits`](GitConfig::split_commits) is enabled.
+	pub fn split(&self, config: &GitConfig) -> Vec<Self> {
+		if config.split_commits.unwrap_or(false) {
+			self.message
+				.lines()
+				.map(|line| {
+					let mut commit = self.clone();
+					commit.message = line.to_string();
+					commit
+				})
+				.collect()
+		} else {
+			vec![self.clone()]
+		}
+	}
+
+	/// Returns the raw entry block of the commit message.
+	///
+	/// The block is the lines between the `Changelog-Begin` and `Changelog-End`
+	/// lines (case-insensitive) and it is returned without any processing
+	/// except for trimming the surrounding empty lines. Multiple blocks are
+	/// joined with a newline.
+	pub fn raw_entry_block(&self) -> Option<String> {
+		let blocks = RAW_ENTRY_REGEX
+			.captures_iter(&self.message)
+			.filter_map(|v| {
+				let block = v.get(1)?.as_str().trim_matches(['\r', '\n']);
+				(!block.trim().is_empty()).then(|| block.trim_end().to_string())
+			})
+			.collect::<Vec<String>>();
+		(!blocks.is_empty()).then(|| blocks.join("\n"))
+	}
+
+	/// Returns the number of the pull request and the entry message if the
+	/// commit is a merge commit of a pull request.
+	///
+	/// The message is the first paragraph of the body (e.g. the title of the
+	/// pull request) unless the title is in the subject (e.g. Azure DevOps).
+	/// The subject is used if there is no body.
+	pub fn pull_request_merge(&self) -> Option<(u64, String)> {
+		let (subject, body) =
+			self.message.split_once('\n').unwrap_or((&self.message, ""));
+		let captures = PR_MERGE_REGEX.captures(subject.trim())?;
+		let number = match captures.iter().skip(1).take(3).flatten().next() {
+			Some(number) => number.as_str(),
+			None => MERGE_REQUEST_REGEX.captures(body)?.get(1)?.as_str(),
+		};
+		let paragraph = body
+			.trim()
+			.split("\n\n")
+			.next()
+			.map(|v| v.trim())
+			.filter(|v| !v.is_empty() && !MERGE_REQUEST_REGEX.is_match(v));
+		let message = captures
+			.get(4)
+			.map(|v| v.as_str())
+			.or(paragraph)
+			.unwrap_or(subject.trim())
+			.to_string();
+		Some((number.parse().ok()?, message))
+	}
+
 	/// Returns the commit with its conventional type set.
+	///
+	/// See [`parse_conventional`].
 	pub fn into_conventional(mut self) -> Result<Self> {
-		match ConventionalCommit::parse(Box::leak(
+		self.conv = Some(parse_conventional(Box::leak(
 			self.message.to_string().into_boxed_str(),
-		)) {
-			Ok(conv) => {
-				self.conv = Some(conv);
-				Ok(self)
+		))?);
+		Ok(self)
+	}
+
+	/// Returns the group of the configured [`commit_types`] entry that
+	/// matches the conventional type of the commit.
+	///
+	/// [`commit_types`]: GitConfig::commit_types
+	pub fn type_group(&self, config: &GitConfig) -> Option<String> {
+		self.conv
+			.as_ref()
+			.and_then(|conv| config.commit_type(conv.type_().as_str()))
+			.and_then(|v| v.group.clone())
+	}
+
+	/// Returns whether the commit is a highlight of its release.
+	///
+	/// The breaking changes and the commits whose types are configured as
+	/// [`highlight`] are highlighted, along with the commits in the groups of
+	/// those types.
+	///
+	/// [`highlight`]: crate::config::CommitType::highlight
+	pub fn is_highlight(&self, config: &GitConfig) -> bool {
+		let is_highlight_type = |name: &str| {
+			config
+				.commit_type(name)
+				.and_then(|v| v.highlight)
+				.unwrap_or_else(|| name.eq_ignore_ascii_case("feat"))
+		};
+		self.is_breaking() ||
+			self.conv
+				.as_ref()
+				.is_some_and(|conv| is_highlight_type(conv.type_().as_str())) ||
+			self.group.as_ref().is_some_and(|group| {
+				config.commit_types.iter().flatten().any(|v| {
+					v.group.as_ref() == Some(group) && is_highlight_type(&v.name)
+				})
+			})
+	}
+
+	/// Returns the breaking changes that are described in the commit.
+	///
+	/// Each `BREAKING CHANGE` (or `BREAKING-CHANGE`) note in the message is a
+	/// separate breaking change and it can be qualified with a scope, e.g.
+	/// `BREAKING CHANGE(api): remove x`. The notes without a scope inherit the
+	/// scope of the commit.
+	///
+	/// If there are no notes but the conventional commit is marked as breaking
+	/// (e.g. `feat!: x`), its description is returned as the breaking change.
+	pub fn breaking_changes(&self) -> Vec<BreakingChange> {
+		let mut breaking_changes: Vec<BreakingChange> = Vec::new();
+		let mut in_note = false;
+		for line in self.message.lines().skip(1) {
+			if let Some(captures) = BREAKING_CHANGE_REGEX.captures(line) {
+				breaking_changes.push(BreakingChange {
+					scope:       captures
+						.name("scope")
+						.map(|v| v.as_str().trim().to_string())
+						.or_else(|| self.scope_name()),
+					description: captures["description"].trim().to_string(),
+				});
+				in_note = true;
+			} else if line.trim().is_empty() || FOOTER_REGEX.is_match(line) {
+				in_note = false;
+			} else if let Some(breaking_change) =
+				breaking_changes.last_mut().filter(|_| in_note)
+			{
+				breaking_change.description += &format!("\n{}", line.trim());
+			}
+		}
+		if breaking_changes.is_empty() {
+			if let Some(conv) = self.conv.as_ref().filter(|v| v.breaking()) {
+				breaking_changes.push(BreakingChange {
+					scope:       self.scope_name(),
+					description: conv.description().to_string(),
+				});
+			} else if self.breaking {
+				breaking_changes.push(BreakingChange {
+					scope:       self.scope_name(),
+					description: self
+						.message
+						.lines()
+						.next()
+						.unwrap_or_default()
+						.to_string(),
+				});
 			}
-			Err(e) => Err(AppError::ParseError(e)),
 		}
+		breaking_changes
+	}
+
+	/// Returns `true` if the commit is marked as breaking or it describes a
+	/// breaking change (see [`Commit::breaking_changes`]).
+	pub fn is_breaking(&self) -> bool {
+		self.breaking ||
+			self.conv.as_ref().is_some_and(|v| v.breaking()) ||
+			self.message
+				.lines()
+				.skip(1)
+				.any(|line| BREAKING_CHANGE_REGEX.is_match(line))
+	}
+
+	/// Normalizes the description of the commit for the changelog entry.
+	///
+	/// The description of a conventional commit is normalized, while the
+	/// first line of the [`message`] is normalized for the other commits.
+	///
+	/// [`message`]: Commit::message
+	pub fn normalize(mut self, normalization: &EntryNormalization) -> Result<Self> {
+		let (subject, rest) = match self.message.split_once('\n') {
+			Some((subject, rest)) => (subject, Some(rest)),
+			None => (self.message.as_str(), None),
+		};
+		let (prefix, description) = match subject.split_once(": ") {
+			Some((prefix, description)) if self.conv.is_some() => {
+				(format!("{prefix}: "), description)
+			}
+			_ => (String::new(), subject),
+		};
+		let mut message = prefix + &normalization.apply(description);
+		if let Some(rest) = rest {
+			message = format!("{message}\n{rest}");
+		}
+		self.message = message;
+		if self.conv.is_some() {
+			self = self.into_conventional()?;
+		}
+		Ok(self)
 	}
 
 	/// Preprocesses the commit using [`TextProcessor`]s.
 	///
-	/// Modifies the commit [`message`] using regex or custom OS command.
+	/// Modifies the commit [`message`] using regex or custom OS command (see
+	/// [`preprocess_message`]).
 	///
 	/// [`message`]: Commit::message
 	pub fn preprocess(mut self, preprocessors: &[TextProcessor]) -> Result<Self> {
-		preprocessors.iter().try_for_each(|preprocessor| {
-			preprocessor
-				.replace(&mut self.message, vec![("COMMIT_SHA", &self.id)])?;
-			Ok::<(), AppError>(())
-		})?;
+		self.message = preprocess_message(&self.message, &self.id, preprocessors)?;
 		Ok(self)
 	}
 
@@ -238,9 +847,130 @@ impl Commit<'_> {
 	/// and the commit is breaking, or the parser's `skip` field is None or
 	/// `false`. Returns `true` otherwise.
 	fn skip_commit(&self, parser: &CommitParser, protect_breaking: bool) -> bool {
-		parser.skip.unwrap_or(false) &&
-			!(self.conv.as_ref().map(|c| c.breaking()).unwrap_or(false) &&
-				protect_breaking)
+		parser.skip.unwrap_or(false) && !(self.is_breaking() && protect_breaking)
+	}
+
+	/// Returns the value of the given field of the commit for the
+	/// [`CommitParser`]s.
+	fn field_value(&self, field: &str) -> Option<String> {
+		match field {
+			"id" => Some(self.id.clone()),
+			"message" => Some(self.message.clone()),
+			"body" => self
+				.conv
+				.as_ref()
+				.and_then(|v| v.body())
+				.map(|v| v.to_string()),
+			"type" => self.conv.as_ref().map(|v| v.type_().to_string()),
+			"scope" => self.scope_name(),
+			"author.name" => self.author.name.clone(),
+			"author.email" => self.author.email.clone(),
+			"committer.name" => self.committer.name.clone(),
+			"committer.email" => self.committer.email.clone(),
+			_ => None,
+		}
+	}
+
+	/// Checks if the given [`CommitParser`] matches the commit.
+	///
+	/// The parser matches if the SHA is the same or any of the regexes (i.e.
+	/// `message`, `body` and `pattern`) matches and the rest of the conditions
+	/// (i.e. `pattern_not`, `and` and `or`) are met. A parser that consists of
+	/// only the rest of the conditions matches if they are met.
+	fn match_parser<'p>(
+		&self,
+		index: usize,
+		parser: &'p CommitParser,
+	) -> Result<Option<ParserMatch<'p>>> {
+		let mut regex_checks = Vec::new();
+		if let Some(message_regex) = parser.message.as_ref() {
+			regex_checks.push((message_regex, self.message.to_string()))
+		}
+		if let (Some(body_regex), Some(body)) = (
+			parser.body.as_ref(),
+			self.conv.as_ref().and_then(|v| v.body()),
+		) {
+			regex_checks.push((body_regex, body.to_string()))
+		}
+		if let (Some(field_name), Some(pattern_regex)) =
+			(parser.field.as_ref(), parser.pattern.as_ref())
+		{
+			regex_checks.push((
+				pattern_regex,
+				self.field_value(field_name).ok_or_else(|| {
+					AppError::FieldError(format!(
+						"field {} does not have a value",
+						field_name
+					))
+				})?,
+			));
+		}
+		if parser.sha.clone().map(|v| v.to_lowercase()).as_deref() == Some(&self.id)
+		{
+			return Ok(Some(ParserMatch { index, regex: None }));
+		}
+		let has_regex = parser.message.is_some() ||
+			parser.body.is_some() ||
+			parser.pattern.is_some();
+		let has_conditions = parser.pattern_not.is_some() ||
+			parser.and.is_some() ||
+			parser.or.is_some();
+		let regex = regex_checks
+			.into_iter()
+			.find(|(regex, text)| regex.is_match(text));
+		if (has_regex && regex.is_none()) || !(has_regex || has_conditions) {
+			return Ok(None);
+		}
+		let is_met = |condition: &CommitCondition| {
+			condition.is_met(self.field_value(&condition.field).as_deref())
+		};
+		let pattern_not_met = match (&parser.field, &parser.pattern_not) {
+			(Some(field), Some(pattern_not)) => !self
+				.field_value(field)
+				.is_some_and(|value| pattern_not.is_match(&value)),
+			_ => true,
+		};
+		if pattern_not_met &&
+			parser.and.iter().flatten().all(is_met) &&
+			parser.or.as_ref().map_or(true, |v| v.iter().any(is_met))
+		{
+			Ok(Some(ParserMatch { index, regex }))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Returns the first [`CommitParser`] that matches the commit.
+	pub fn find_parser<'p>(
+		&self,
+		parsers: &'p [CommitParser],
+	) -> Result<Option<ParserMatch<'p>>> {
+		for (index, parser) in parsers.iter().enumerate() {
+			if let Some(parser_match) = self.match_parser(index, parser)? {
+				return Ok(Some(parser_match));
+			}
+		}
+		Ok(None)
+	}
+
+	/// Returns the [`CommitParser`]s that match the commit in order.
+	///
+	/// The matching stops at the first parser that does not have
+	/// [`continue`](CommitParser::continue_) enabled.
+	pub fn find_parsers<'p>(
+		&self,
+		parsers: &'p [CommitParser],
+	) -> Result<Vec<ParserMatch<'p>>> {
+		let mut matches = Vec::new();
+		for (index, parser) in parsers.iter().enumerate() {
+			if let Some(parser_match) = self.match_parser(index, parser)? {
+				matches.push(parser_match);
+				if !parser.continue_.unwrap_or(false) {
+					break;
+				}
+			}
+		}
+		Ok(matches)
 	}
 
 	/// Parses the commit using [`CommitParser`]s.
@@ -250,95 +980,76 @@ impl Commit<'_> {
 	/// [`group`]: Commit::group
 	/// [`scope`]: Commit::scope
 	pub fn parse(
+		self,
+		parsers: &[CommitParser],
+		protect_breaking: bool,
+		filter: bool,
+	) -> Result<Self> {
+		let matches = self.find_parsers(parsers)?;
+		self.apply_parsers(parsers, matches, protect_breaking, filter)
+	}
+
+	/// Applies the [`CommitParser`]s that match the commit.
+	///
+	/// See [`Commit::parse`].
+	fn apply_parsers(
 		mut self,
 		parsers: &[CommitParser],
+		matches: Vec<ParserMatch<'_>>,
 		protect_breaking: bool,
 		filter: bool,
 	) -> Result<Self> {
-		for parser in parsers {
-			let mut regex_checks = Vec::new();
-			if let Some(message_regex) = parser.message.as_ref() {
-				regex_checks.push((message_regex, self.message.to_string()))
-			}
-			if let (Some(body_regex), Some(body)) = (
-				parser.body.as_ref(),
-				self.conv.as_ref().and_then(|v| v.body()),
-			) {
-				regex_checks.push((body_regex, body.to_string()))
-			}
-			if let (Some(field_name), Some(pattern_regex)) =
-				(parser.field.as_ref(), parser.pattern.as_ref())
-			{
-				regex_checks.push((
-					pattern_regex,
-					match field_name.as_str() {
-						"id" => Some(self.id.clone()),
-						"message" => Some(self.message.clone()),
-						"body" => self
-							.conv
-							.as_ref()
-							.and_then(|v| v.body())
-							.map(|v| v.to_string()),
-						"author.name" => self.author.name.clone(),
-						"author.email" => self.author.email.clone(),
-						"committer.name" => self.committer.name.clone(),
-						"committer.email" => self.committer.email.clone(),
-						_ => None,
-					}
-					.ok_or_else(|| {
-						AppError::FieldError(format!(
-							"field {} does not have a value",
-							field_name
-						))
-					})?,
-				));
+		if matches.is_empty() {
+			return if !filter {
+				Ok(self)
+			} else {
+				Err(AppError::GroupError(String::from(
+					"Commit does not belong to any group",
+				)))
+			};
+		}
+		for (i, ParserMatch { index, regex }) in matches.into_iter().enumerate() {
+			let parser = &parsers[index];
+			if self.skip_commit(parser, protect_breaking) {
+				return Err(AppError::GroupError(String::from("Skipping commit")));
 			}
-			if parser.sha.clone().map(|v| v.to_lowercase()).as_deref() ==
-				Some(&self.id)
-			{
-				if self.skip_commit(parser, protect_breaking) {
-					return Err(AppError::GroupError(String::from(
-						"Skipping commit",
-					)));
-				} else {
+			match regex {
+				Some((regex, text)) => {
+					let regex_replace = |mut value: String| {
+						for mat in regex.find_iter(&text) {
+							value = regex.replace(mat.as_str(), value).to_string();
+						}
+						value
+					};
+					// keep the values of the previous parsers in the chain
+					let previous = |value: Option<String>| value.filter(|_| i > 0);
+					self.group = parser
+						.group
+						.as_ref()
+						.cloned()
+						.map(regex_replace)
+						.or(previous(self.group));
+					self.scope = parser
+						.scope
+						.as_ref()
+						.cloned()
+						.map(regex_replace)
+						.or(previous(self.scope));
+					self.default_scope = parser
+						.default_scope
+						.as_ref()
+						.cloned()
+						.or(previous(self.default_scope));
+				}
+				None => {
 					self.group = parser.group.clone().or(self.group);
 					self.scope = parser.scope.clone().or(self.scope);
 					self.default_scope =
 						parser.default_scope.clone().or(self.default_scope);
-					return Ok(self);
-				}
-			}
-			for (regex, text) in regex_checks {
-				if regex.is_match(&text) {
-					if self.skip_commit(parser, protect_breaking) {
-						return Err(AppError::GroupError(String::from(
-							"Skipping commit",
-						)));
-					} else {
-						let regex_replace = |mut value: String| {
-							for mat in regex.find_iter(&text) {
-								value =
-									regex.replace(mat.as_str(), value).to_string();
-							}
-							value
-						};
-						self.group =
-							parser.group.as_ref().cloned().map(regex_replace);
-						self.scope =
-							parser.scope.as_ref().cloned().map(regex_replace);
-						self.default_scope = parser.default_scope.as_ref().cloned();
-						return Ok(self);
-					}
 				}
 			}
 		}
-		if !filter {
-			Ok(self)
-		} else {
-			Err(AppError::GroupError(String::from(
-				"Commit does not belong to any group",
-			)))
-		}
+		Ok(self)
 	}
 
 	/// Parses the commit using [`LinkParser`]s.
@@ -367,6 +1078,49 @@ impl Commit<'_> {
 		Ok(self)
 	}
 
+	/// Returns the group of the commit.
+	///
+	/// Falls back to the conventional type if the group is not set.
+	pub fn group_name(&self) -> Option<String> {
+		self.group
+			.clone()
+			.or_else(|| self.conv.as_ref().map(|conv| conv.type_().to_string()))
+	}
+
+	/// Returns the scope of the commit.
+	///
+	/// Falls back to the conventional scope and then the default scope if the
+	/// scope is not set.
+	pub fn scope_name(&self) -> Option<String> {
+		self.scope
+			.clone()
+			.or_else(|| {
+				self.conv
+					.as_ref()
+					.and_then(|conv| conv.scope())
+					.map(|scope| scope.to_string())
+			})
+			.or_else(|| self.default_scope.clone())
+	}
+
+	/// Returns `true` if the commit has a valid signature that is made by one
+	/// of the given keys.
+	///
+	/// The keys are matched by the end of the fingerprint so that the key IDs
+	/// can be used as well. Any key is allowed if no keys are given.
+	pub fn is_signed_by(&self, keys: &[String]) -> bool {
+		self.signature_status.is_some_and(|v| v.is_valid()) &&
+			(keys.is_empty() ||
+				self.signing_key.as_ref().is_some_and(|signing_key| {
+					keys.iter().any(|key| {
+						!key.is_empty() &&
+							signing_key
+								.to_uppercase()
+								.ends_with(&key.to_uppercase())
+					})
+				}))
+	}
+
 	/// Returns an iterator over this commit's [`Footer`]s, if this is a
 	/// conventional commit.
 	///
@@ -403,8 +1157,14 @@ impl Serialize for Commit<'_> {
 		commit.serialize_field("id", &self.id)?;
 		match &self.conv {
 			Some(conv) => {
-				commit.serialize_field("message", conv.description())?;
+				commit.serialize_field(
+					"message",
+					self.raw_entry.as_deref().unwrap_or(conv.description()),
+				)?;
 				commit.serialize_field("body", &conv.body())?;
+				let body = conv.body().map(BodyStructure::parse).unwrap_or_default();
+				commit.serialize_field("body_paragraphs", &body.paragraphs)?;
+				commit.serialize_field("body_items", &body.items)?;
 				commit.serialize_field("footers", &SerializeFooters(self))?;
 				commit.serialize_field(
 					"group",
@@ -414,7 +1174,7 @@ impl Serialize for Commit<'_> {
 					"breaking_description",
 					&conv.breaking_description(),
 				)?;
-				commit.serialize_field("breaking", &conv.breaking())?;
+				commit.serialize_field("breaking", &self.is_breaking())?;
 				commit.serialize_field(
 					"scope",
 					&self
@@ -425,19 +1185,33 @@ impl Serialize for Commit<'_> {
 				)?;
 			}
 			None => {
-				commit.serialize_field("message", &self.message)?;
+				commit.serialize_field(
+					"message",
+					self.raw_entry.as_ref().unwrap_or(&self.message),
+				)?;
 				commit.serialize_field("group", &self.group)?;
+				commit.serialize_field("breaking", &self.is_breaking())?;
 				commit.serialize_field(
 					"scope",
 					&self.scope.as_deref().or(self.default_scope.as_deref()),
 				)?;
 			}
 		}
+		commit.serialize_field("breaking_changes", &self.breaking_changes())?;
 		commit.serialize_field("links", &self.links)?;
 		commit.serialize_field("author", &self.author)?;
 		commit.serialize_field("committer", &self.committer)?;
+		commit.serialize_field("date", &self.date)?;
 		commit.serialize_field("conventional", &self.conv.is_some())?;
 		commit.serialize_field("merge_commit", &self.merge_commit)?;
+		commit.serialize_field("formatted", &self.formatted)?;
+		commit.serialize_field("deployed", &self.deployed)?;
+		commit.serialize_field("signature_status", &self.signature_status)?;
+		commit.serialize_field("signing_key", &self.signing_key)?;
+		commit.serialize_field("metadata", &self.metadata)?;
+		commit.serialize_field("first_seen_in", &self.first_seen_in)?;
+		commit.serialize_field("pr_number", &self.pr_number)?;
+		commit.serialize_field("raw_entry", &self.raw_entry)?;
 		#[cfg(feature = "github")]
 		commit.serialize_field("github", &self.github)?;
 		commit.end()
@@ -447,6 +1221,10 @@ impl Serialize for Commit<'_> {
 #[cfg(test)]
 mod test {
 	use super::*;
+	use crate::config::{
+		ChangelogConfig,
+		CommitGroup,
+	};
 	#[test]
 	fn conventional_commit() -> Result<()> {
 		let test_cases = vec![
@@ -476,6 +1254,10 @@ mod test {
 				skip:          None,
 				field:         None,
 				pattern:       None,
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			}],
 			false,
 			false,
@@ -485,6 +1267,143 @@ mod test {
 		Ok(())
 	}
 
+	#[test]
+	fn breaking_changes() -> Result<()> {
+		let breaking_change =
+			|scope: Option<&str>, description: &str| BreakingChange {
+				scope:       scope.map(String::from),
+				description: description.to_string(),
+			};
+		let commit = Commit::from(String::from(
+			"refactor(core): split the modules\n\nBody.\n\nBREAKING CHANGE(api): \
+			 remove x\nBREAKING-CHANGE: rename y\n  to z\nRefs: #1\nBREAKING \
+			 CHANGE(cli): drop --foo",
+		))
+		.into_conventional()?;
+		assert!(commit.is_breaking());
+		assert_eq!(
+			vec![
+				breaking_change(Some("api"), "remove x"),
+				breaking_change(Some("core"), "rename y\nto z"),
+				breaking_change(Some("cli"), "drop --foo"),
+			],
+			commit.breaking_changes()
+		);
+
+		let commit = Commit::from(String::from("feat(ui)!: remove the old theme"))
+			.into_conventional()?;
+		assert_eq!(
+			vec![breaking_change(Some("ui"), "remove the old theme")],
+			commit.breaking_changes()
+		);
+		let commit =
+			Commit::from(String::from("feat: add x")).into_conventional()?;
+		assert!(!commit.is_breaking());
+		assert!(commit.breaking_changes().is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn custom_commit_types() -> Result<()> {
+		let mut config = crate::config::GitConfig {
+			commit_types: Some(vec![crate::config::CommitType {
+				name:      String::from("deps"),
+				group:     Some(String::from("Dependencies")),
+				bump:      None,
+				highlight: None,
+			}]),
+			commit_parsers: Some(vec![
+				CommitParser {
+					message: Regex::new("^chore: release").ok(),
+					skip: Some(true),
+					..Default::default()
+				},
+				CommitParser {
+					message: Regex::new(".*").ok(),
+					group: Some(String::from("Other")),
+					..Default::default()
+				},
+			]),
+			filter_commits: Some(true),
+			..Default::default()
+		};
+		let commit =
+			Commit::from(String::from("deps: bump xyz")).process(&config)?;
+		assert_eq!(Some(String::from("Dependencies")), commit.group);
+		let commit = Commit::from(String::from("fix: xyz")).process(&config)?;
+		assert_eq!(Some(String::from("Other")), commit.group);
+		for message in ["release: v1.0.0", "wip: xyz"] {
+			let commit = Commit::from(String::from(message)).process(&config)?;
+			assert!(commit.conv.is_some());
+			assert_eq!(Some(String::from("Other")), commit.group);
+		}
+		assert!(Commit::from(String::from("chore: release"))
+			.process(&config)
+			.is_err());
+
+		config.commit_types = None;
+		let commit =
+			Commit::from(String::from("deps: bump xyz")).process(&config)?;
+		assert_eq!(Some(String::from("Other")), commit.group);
+		Ok(())
+	}
+
+	#[test]
+	fn highlight_commits() -> Result<()> {
+		let mut config = crate::config::GitConfig::default();
+		let commit = |message: &str, group: Option<&str>| -> Result<Commit> {
+			Ok(Commit {
+				group: group.map(String::from),
+				..Commit::from(String::from(message)).into_conventional()?
+			})
+		};
+		assert!(commit("feat: add x", None)?.is_highlight(&config));
+		assert!(commit("fix!: fix x", None)?.is_highlight(&config));
+		assert!(!commit("ux: improve x", None)?.is_highlight(&config));
+
+		config.commit_types = Some(vec![
+			crate::config::CommitType {
+				name: String::from("feat"),
+				highlight: Some(false),
+				..Default::default()
+			},
+			crate::config::CommitType {
+				name: String::from("ux"),
+				group: Some(String::from("User Experience")),
+				highlight: Some(true),
+				..Default::default()
+			},
+		]);
+		assert!(!commit("feat: add x", None)?.is_highlight(&config));
+		assert!(commit("ux: improve x", None)?.is_highlight(&config));
+		assert!(commit("refactor: tweak x", Some("User Experience"))?
+			.is_highlight(&config));
+		assert!(!commit("refactor: tweak x", Some("Other"))?.is_highlight(&config));
+		Ok(())
+	}
+
+	#[test]
+	fn fallback_to_default_scope() -> Result<()> {
+		let config = crate::config::GitConfig {
+			conventional_commits: Some(true),
+			default_scope: Some(String::from("general")),
+			..Default::default()
+		};
+		let commit = Commit::from(String::from("feat: add xyz")).process(&config)?;
+		assert_eq!(Some(String::from("general")), commit.default_scope);
+		let commit =
+			Commit::from(String::from("feat(app): add xyz")).process(&config)?;
+		assert_eq!(
+			Some("app"),
+			commit
+				.conv
+				.as_ref()
+				.and_then(|v| v.scope())
+				.map(|v| v.as_str())
+		);
+		Ok(())
+	}
+
 	#[test]
 	fn conventional_footers() {
 		let cfg = crate::config::GitConfig {
@@ -537,6 +1456,27 @@ mod test {
 		}
 	}
 
+	#[test]
+	fn parse_body_structure() {
+		let body = "First paragraph\nwrapped here.\n\n- item one\n  continued\n* \
+		            item two\n1. item three\n\nLast paragraph.";
+		assert_eq!(
+			BodyStructure {
+				paragraphs: vec![
+					String::from("First paragraph wrapped here."),
+					String::from("Last paragraph."),
+				],
+				items:      vec![
+					String::from("item one continued"),
+					String::from("item two"),
+					String::from("item three"),
+				],
+			},
+			BodyStructure::parse(body)
+		);
+		assert_eq!(BodyStructure::default(), BodyStructure::parse(""));
+	}
+
 	#[test]
 	fn parse_link() -> Result<()> {
 		let test_cases = vec![
@@ -649,6 +1589,10 @@ mod test {
 				skip:          None,
 				field:         Some(String::from("author.name")),
 				pattern:       Regex::new("John Doe").ok(),
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			}],
 			false,
 			false,
@@ -677,6 +1621,10 @@ mod test {
 				skip:          Some(true),
 				field:         None,
 				pattern:       None,
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			}],
 			false,
 			false,
@@ -696,6 +1644,10 @@ mod test {
 				skip:          None,
 				field:         None,
 				pattern:       None,
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			}],
 			false,
 			false,
@@ -704,4 +1656,369 @@ mod test {
 
 		Ok(())
 	}
+
+	#[test]
+	fn commit_parser_conditions() -> Result<()> {
+		let mut commit =
+			Commit::new(String::from("123123"), String::from("feat(ci): add x"))
+				.into_conventional()?;
+		commit.author.name = Some(String::from("renovate[bot]"));
+		let parser =
+			|pattern_not: Option<&str>, continue_: Option<bool>| CommitParser {
+				message: Regex::new("^feat").ok(),
+				group: Some(String::from("Features")),
+				field: Some(String::from("scope")),
+				pattern_not: pattern_not.and_then(|v| Regex::new(v).ok()),
+				continue_,
+				..Default::default()
+			};
+		let condition = |field: &str, pattern: Option<&str>, not: Option<&str>| {
+			CommitCondition {
+				field:       String::from(field),
+				pattern:     pattern.and_then(|v| Regex::new(v).ok()),
+				pattern_not: not.and_then(|v| Regex::new(v).ok()),
+			}
+		};
+
+		assert!(commit.find_parser(&[parser(Some("^ci$"), None)])?.is_none());
+		assert!(commit.find_parser(&[parser(Some("^ui$"), None)])?.is_some());
+		assert!(commit
+			.find_parser(&[CommitParser {
+				and: Some(vec![
+					condition("type", Some("^feat$"), None),
+					condition("author.name", None, Some(r"\[bot\]$")),
+				]),
+				..Default::default()
+			}])?
+			.is_none());
+		assert!(commit
+			.find_parser(&[CommitParser {
+				or: Some(vec![
+					condition("body", Some("x"), None),
+					condition("author.email", None, Some("x")),
+				]),
+				..Default::default()
+			}])?
+			.is_some());
+		assert!(commit.find_parser(&[CommitParser::default()])?.is_none());
+
+		let parsers = [
+			parser(None, Some(true)),
+			CommitParser {
+				message: Regex::new("ci").ok(),
+				scope: Some(String::from("CI")),
+				..Default::default()
+			},
+			CommitParser {
+				message: Regex::new(".*").ok(),
+				group: Some(String::from("Other")),
+				..Default::default()
+			},
+		];
+		assert_eq!(
+			vec![0, 1],
+			commit
+				.find_parsers(&parsers)?
+				.into_iter()
+				.map(|v| v.index)
+				.collect::<Vec<usize>>()
+		);
+		let parsed_commit = commit.clone().parse(&parsers, false, false)?;
+		assert_eq!(Some(String::from("Features")), parsed_commit.group);
+		assert_eq!(Some(String::from("CI")), parsed_commit.scope);
+
+		let parsers = [parser(None, Some(true)), CommitParser {
+			field: Some(String::from("author.name")),
+			pattern: Regex::new(r"\[bot\]$").ok(),
+			skip: Some(true),
+			..Default::default()
+		}];
+		assert!(commit.parse(&parsers, false, false).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn normalize_commit() -> Result<()> {
+		let normalization = EntryNormalization {
+			capitalize:          Some(true),
+			strip_period:        Some(true),
+			collapse_whitespace: Some(true),
+		};
+		let commit = Commit::new(
+			String::from("123"),
+			String::from("feat(ui)!: add  the\tdark theme.\n\nbody  text."),
+		)
+		.into_conventional()?
+		.normalize(&normalization)?;
+		let conv = commit.conv.as_ref().expect("not conventional");
+		assert_eq!("Add the dark theme", conv.description());
+		assert_eq!(Some("body  text."), conv.body());
+		assert!(conv.breaking());
+
+		let commit =
+			Commit::new(String::from("456"), String::from("wait for it..."))
+				.normalize(&normalization)?;
+		assert_eq!("Wait for it...", commit.message);
+		let commit = Commit::new(String::from("789"), String::from("."))
+			.normalize(&normalization)?;
+		assert_eq!(".", commit.message);
+
+		let config = ChangelogConfig {
+			normalize: Some(normalization),
+			commit_groups: Some(vec![CommitGroup {
+				name: String::from("Dependencies"),
+				normalize: Some(EntryNormalization {
+					capitalize: Some(false),
+					..Default::default()
+				}),
+				..Default::default()
+			}]),
+			..Default::default()
+		};
+		let normalization = config
+			.entry_normalization(Some("Dependencies"))
+			.expect("not normalized");
+		assert_eq!(Some(false), normalization.capitalize);
+		assert_eq!(Some(true), normalization.strip_period);
+		assert_eq!(
+			Some(true),
+			config
+				.entry_normalization(Some("Features"))
+				.and_then(|v| v.capitalize)
+		);
+		assert!(ChangelogConfig::default()
+			.entry_normalization(None)
+			.is_none());
+		Ok(())
+	}
+
+	#[test]
+	fn commit_date() -> Result<()> {
+		let commit = Commit {
+			id: String::from("123"),
+			message: String::from("feat: add x"),
+			author: Signature {
+				timestamp: 1714953600,
+				..Default::default()
+			},
+			committer: Signature {
+				timestamp: 1715040000,
+				..Default::default()
+			},
+			..Default::default()
+		};
+		for (commit_date, timestamp) in [
+			(None, 1715040000),
+			(Some(CommitDate::Author), 1714953600),
+			(Some(CommitDate::Committer), 1715040000),
+			(Some(CommitDate::Merge), 1715040000),
+		] {
+			let config = GitConfig {
+				commit_date,
+				..Default::default()
+			};
+			assert_eq!(timestamp, commit.process(&config)?.date);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn scope_aliases() -> Result<()> {
+		let config = GitConfig {
+			scope_aliases: Some(std::collections::HashMap::from([(
+				String::from("gui"),
+				String::from("ui"),
+			)])),
+			commit_parsers: Some(vec![CommitParser {
+				field: Some(String::from("scope")),
+				pattern: Regex::new("^ui$").ok(),
+				group: Some(String::from("UI")),
+				..Default::default()
+			}]),
+			..Default::default()
+		};
+		let commit = Commit::new(String::from("123"), String::from("fix(gui): x"))
+			.process(&config)?;
+		assert_eq!(Some(String::from("ui")), commit.scope);
+		assert_eq!(Some(String::from("UI")), commit.group);
+		let commit = Commit::new(String::from("456"), String::from("fix(api): y"))
+			.process(&config)?;
+		assert_eq!(Some(String::from("api")), commit.scope_name());
+		Ok(())
+	}
+
+	#[test]
+	fn raw_entry() -> Result<()> {
+		let commit = Commit::new(
+			String::from("123"),
+			String::from(
+				"feat(ui): add the dark theme\n\nSome \
+				 context.\n\nChangelog-Begin\nAdded a **dark theme**:\n  - enable \
+				 it in the settings\nChangelog-End\n\nchangelog-begin\nAnd \
+				 `more`.\nchangelog-end\n",
+			),
+		);
+		assert_eq!(
+			Some(String::from(
+				"Added a **dark theme**:\n  - enable it in the settings\nAnd \
+				 `more`."
+			)),
+			commit.raw_entry_block()
+		);
+		assert_eq!(
+			None,
+			Commit::new(
+				String::from("456"),
+				String::from("fix: x\n\nChangelog-Begin\n\nChangelog-End")
+			)
+			.raw_entry_block()
+		);
+		assert_eq!(
+			None,
+			Commit::new(
+				String::from("789"),
+				String::from("fix: x\n\nChangelog-Begin\ny")
+			)
+			.raw_entry_block()
+		);
+
+		let config = GitConfig {
+			raw_entries: Some(true),
+			..Default::default()
+		};
+		let processed = commit.process(&config)?;
+		assert_eq!(commit.raw_entry_block(), processed.raw_entry);
+		assert_eq!(
+			Some("ui"),
+			processed
+				.conv
+				.as_ref()
+				.and_then(|v| v.scope())
+				.map(|v| v.as_str())
+		);
+		let value = serde_json::to_value(&processed)?;
+		assert_eq!(
+			Some(
+				"Added a **dark theme**:\n  - enable it in the settings\nAnd \
+				 `more`."
+			),
+			value["message"].as_str()
+		);
+		assert!(commit.process(&GitConfig::default())?.raw_entry.is_none());
+		Ok(())
+	}
+
+	#[test]
+	fn pull_request_merge_commit() -> Result<()> {
+		for (message, expected) in [
+			(
+				"Merge pull request #12 from x/feature\n\nAdd x\n\nCloses #1",
+				Some((12, "Add x")),
+			),
+			(
+				"Merge pull request #12 from x/feature",
+				Some((12, "Merge pull request #12 from x/feature")),
+			),
+			(
+				"Merged in feature (pull request #34)\n\nFix y",
+				Some((34, "Fix y")),
+			),
+			(
+				"Merged PR 56: Update z\n\nDescription",
+				Some((56, "Update z")),
+			),
+			(
+				"Merge branch 'feature' into 'main'\n\nAdd w\n\nSee merge request \
+				 x/y!78",
+				Some((78, "Add w")),
+			),
+			("Merge branch 'feature' into 'main'", None),
+			("Merge branch 'main' of x.git", None),
+			("feat: add x", None),
+		] {
+			let commit = Commit::new(String::from("123123"), String::from(message));
+			assert_eq!(
+				expected.map(|(number, message)| (number, String::from(message))),
+				commit.pull_request_merge(),
+				"{message}"
+			);
+		}
+
+		let commit = Commit::new(
+			String::from("123123"),
+			String::from("Merge pull request #12 from x/feature\n\nfeat: add x"),
+		)
+		.process(&GitConfig {
+			pull_request_merges: Some(true),
+			..Default::default()
+		})?;
+		assert_eq!(Some(12), commit.pr_number);
+		assert_eq!(Some(String::from("feat")), commit.group_name());
+		Ok(())
+	}
+
+	#[test]
+	fn commit_signature() {
+		let mut commit =
+			Commit::new(String::from("123123"), String::from("feat: add xyz"));
+		assert!(!commit.is_signed_by(&[]));
+		commit.signature_status = Some(SignatureStatus::from_code("U"));
+		commit.signing_key =
+			Some(String::from("4A1F0C6D1E2B3C4D5E6F7A8B9C0D1E2F3A4B5C6D"));
+		assert!(commit.is_signed_by(&[]));
+		assert!(commit.is_signed_by(&[String::from("3a4b5c6d")]));
+		assert!(!commit.is_signed_by(&[String::from("ABCDEF01")]));
+		commit.signature_status = Some(SignatureStatus::from_code("R"));
+		assert_eq!(Some(SignatureStatus::Revoked), commit.signature_status);
+		assert!(!commit.is_signed_by(&[String::from("3A4B5C6D")]));
+		assert_eq!(SignatureStatus::Unsigned, SignatureStatus::from_code("N"));
+	}
+
+	#[test]
+	fn standalone_parsing() -> Result<()> {
+		let config = GitConfig {
+			commit_preprocessors: Some(vec![TextProcessor {
+				pattern:         Regex::new(r"\s*\(#[0-9]+\)$")?,
+				replace:         Some(String::new()),
+				replace_command: None,
+			}]),
+			commit_parsers: Some(vec![
+				CommitParser {
+					message: Regex::new("^feat").ok(),
+					group: Some(String::from("Features")),
+					..Default::default()
+				},
+				CommitParser {
+					message: Regex::new("^chore").ok(),
+					skip: Some(true),
+					..Default::default()
+				},
+			]),
+			..Default::default()
+		};
+		assert_eq!(
+			"feat(ui): add x",
+			preprocess_message(
+				"feat(ui): add x (#42)",
+				"123",
+				config.commit_preprocessors.as_deref().unwrap_or_default()
+			)?
+		);
+		let conv = parse_conventional("feat(ui)!: add x")?;
+		assert_eq!("feat", conv.type_().as_str());
+		assert_eq!(Some("ui"), conv.scope().map(|v| v.as_str()));
+		assert!(conv.breaking());
+		assert!(parse_conventional("add x").is_err());
+		assert_eq!(
+			Some(String::from("Features")),
+			resolve_group("feat(ui): add x (#42)", &config)?
+		);
+		assert_eq!(
+			Some(String::from("fix")),
+			resolve_group("fix: fix y", &config)?
+		);
+		assert_eq!(None, resolve_group("chore: release", &config)?);
+		assert_eq!(None, resolve_group("add x", &config)?);
+		Ok(())
+	}
 }
@@ -1,5 +1,14 @@
 use crate::command;
-use crate::error::Result;
+use crate::error::{
+	Error,
+	Result,
+};
+use crate::release::tag_version;
+use git_cliff_derive::ConfigReference;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+};
 use regex::{
 	Regex,
 	RegexBuilder,
@@ -9,10 +18,15 @@ use serde::{
 	Deserialize,
 	Serialize,
 };
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::path::{
+	Path,
+	PathBuf,
+};
 
 /// Regex for matching the metadata in Cargo.toml
 const CARGO_METADATA_REGEX: &str =
@@ -21,8 +35,17 @@ const CARGO_METADATA_REGEX: &str =
 /// Regex for matching the metadata in pyproject.toml
 const PYPROJECT_METADATA_REGEX: &str = r"^\[(?:tool)\.git\-cliff\.";
 
+/// Keys of the configuration values that are templates themselves.
+///
+/// They are rendered while generating the changelog, not while parsing the
+/// configuration. The whole `changelog` table is skipped since most of its
+/// values (e.g. `body`, `footer`, `commit_template` and `tag_message`) are
+/// templates of the changelog and the rest are not rendered either.
+const TEMPLATE_KEYS: &[&str] =
+	&["changelog", "eras[].body", "workspace.umbrella_template"];
+
 /// Configuration values.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigReference)]
 pub struct Config {
 	/// Configuration values about changelog generation.
 	#[serde(default)]
@@ -33,25 +56,212 @@ pub struct Config {
 	/// Configuration values about remote.
 	#[serde(default)]
 	pub remote:    RemoteConfig,
+	/// Configuration values about linting the commits.
+	#[serde(default)]
+	pub lint:      LintConfig,
+	/// Configuration values about bumping the version.
+	#[serde(default)]
+	pub bump:      BumpConfig,
+	/// Configuration values for the ranges of releases.
+	pub eras:      Option<Vec<EraConfig>>,
+	/// Configuration values about the packages of a monorepo.
+	pub workspace: Option<WorkspaceConfig>,
 }
 
+/// Regular expression for matching the URLs in the changelog.
+static URL_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"https?://[^\s<>()\[\]"'`]*[^\s<>()\[\]"'`.,;:!?]"#);
+
+/// Minimum number of the characters of the abbreviated commit SHA1s.
+const MIN_SHA_LENGTH: usize = 7;
+
+/// Regular expression for matching the dates (e.g. `2024-06-01`).
+static DATE_REGEX: Lazy<Regex> = lazy_regex!(r"^(\d{4})-(\d{2})-(\d{2})$");
+
 /// Changelog configuration.
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
 pub struct ChangelogConfig {
 	/// Changelog header.
-	pub header:         Option<String>,
+	pub header:            Option<String>,
 	/// Changelog body, template.
-	pub body:           Option<String>,
+	pub body:              Option<String>,
 	/// Changelog footer.
-	pub footer:         Option<String>,
+	pub footer:            Option<String>,
 	/// Trim the template.
-	pub trim:           Option<bool>,
+	pub trim:              Option<bool>,
 	/// Changelog postprocessors.
-	pub postprocessors: Option<Vec<TextProcessor>>,
+	pub postprocessors:    Option<Vec<TextProcessor>>,
+	/// Template for formatting a single commit.
+	pub commit_template:   Option<String>,
+	/// Template for the message of the annotated release tag.
+	pub tag_message:       Option<String>,
+	/// Hook for shortening the long URLs in the changelog.
+	pub url_shortener:     Option<UrlShortener>,
+	/// Maximum size of the changelog in bytes.
+	pub max_body_size:     Option<usize>,
+	/// Template of the note for the groups that are elided for fitting
+	/// [`max_body_size`].
+	///
+	/// [`max_body_size`]: ChangelogConfig::max_body_size
+	pub elision_note:      Option<String>,
+	/// Markdown heading level of the releases.
+	pub heading_level:     Option<usize>,
+	/// Whether to number the headings of the releases and groups.
+	pub heading_numbering: Option<bool>,
+	/// Metadata of the commit groups.
+	pub commit_groups:     Option<Vec<CommitGroup>>,
+	/// Locale for sorting the groups, scopes and names (e.g. `sv-SE`).
+	pub collation:         Option<String>,
+	/// Flavor of the output for adapting the templates.
+	pub output_flavor:     Option<OutputFlavor>,
+	/// Pagination of the long releases.
+	pub pagination:        Option<PaginationConfig>,
+	/// Limits of rendering the templates.
+	pub template_limits:   Option<TemplateLimits>,
+	/// E-mail domains of the internal authors (e.g. `example.com`).
+	///
+	/// The subdomains are also matched. If it is set, the authors of the
+	/// releases are grouped into the internal and external ones.
+	pub internal_domains:  Option<Vec<String>>,
+	/// Normalization of the changelog entries.
+	///
+	/// It can be overridden for a group via [`commit_groups`].
+	///
+	/// [`commit_groups`]: ChangelogConfig::commit_groups
+	pub normalize:         Option<EntryNormalization>,
+	/// Path of the repository to read the git objects from in templates.
+	///
+	/// This is not read from the configuration file and needs to be enabled
+	/// explicitly (e.g. via `--allow-git-read`).
+	#[serde(skip)]
+	pub git_read_path:     Option<PathBuf>,
+}
+
+impl ChangelogConfig {
+	/// Returns the normalization of the entries in the given group.
+	///
+	/// The options of the group take precedence over the global ones.
+	pub fn entry_normalization(
+		&self,
+		group: Option<&str>,
+	) -> Option<EntryNormalization> {
+		let group_normalization = group
+			.and_then(|group| {
+				self.commit_groups
+					.iter()
+					.flatten()
+					.find(|v| v.name == group)
+			})
+			.and_then(|v| v.normalize);
+		match (group_normalization, self.normalize) {
+			(Some(group), Some(global)) => Some(group.or(global)),
+			(group, global) => group.or(global),
+		}
+	}
+}
+
+/// Limits of rendering the templates.
+///
+/// This is useful for rendering the untrusted or buggy templates without
+/// hanging the process.
+#[derive(
+	Debug,
+	Default,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Serialize,
+	Deserialize,
+	ConfigReference,
+)]
+pub struct TemplateLimits {
+	/// Maximum time of rendering a template in milliseconds.
+	///
+	/// It is checked whenever the template writes to the output, so the parts
+	/// that do not produce any output are not interrupted.
+	pub timeout:         Option<u64>,
+	/// Maximum size of the rendered output in bytes.
+	///
+	/// It is checked while rendering.
+	pub max_output_size: Option<usize>,
+	/// Maximum depth of the nested blocks, macro calls and includes.
+	///
+	/// The recursive macros are not allowed if it is set since their depth
+	/// cannot be determined before rendering.
+	pub max_depth:       Option<usize>,
+}
+
+/// Normalization of the changelog entries.
+///
+/// It is applied to the descriptions of the conventional commits and the
+/// first line of the other commit messages before rendering.
+#[derive(
+	Debug,
+	Default,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Serialize,
+	Deserialize,
+	ConfigReference,
+)]
+pub struct EntryNormalization {
+	/// Whether to capitalize the first letter.
+	pub capitalize:          Option<bool>,
+	/// Whether to strip the trailing period.
+	///
+	/// Ellipses (e.g. `...`) are kept.
+	pub strip_period:        Option<bool>,
+	/// Whether to collapse the consecutive whitespace into a single space.
+	pub collapse_whitespace: Option<bool>,
+}
+
+impl EntryNormalization {
+	/// Returns the normalization with the unset options taken from the given
+	/// one.
+	pub fn or(self, other: Self) -> Self {
+		Self {
+			capitalize:          self.capitalize.or(other.capitalize),
+			strip_period:        self.strip_period.or(other.strip_period),
+			collapse_whitespace: self
+				.collapse_whitespace
+				.or(other.collapse_whitespace),
+		}
+	}
+
+	/// Normalizes the given text.
+	///
+	/// The text is returned as is if it would be empty after normalizing.
+	pub fn apply(&self, text: &str) -> String {
+		let mut normalized = if self.collapse_whitespace.unwrap_or(false) {
+			text.split_whitespace().collect::<Vec<&str>>().join(" ")
+		} else {
+			text.to_string()
+		};
+		if self.strip_period.unwrap_or(false) &&
+			normalized.ends_with('.') &&
+			!normalized.ends_with("..")
+		{
+			normalized.pop();
+		}
+		if self.capitalize.unwrap_or(false) {
+			let mut chars = normalized.chars();
+			if let Some(first) = chars.next() {
+				normalized = first.to_uppercase().chain(chars).collect();
+			}
+		}
+		if normalized.trim().is_empty() {
+			text.to_string()
+		} else {
+			normalized
+		}
+	}
 }
 
 /// Git configuration
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
 pub struct GitConfig {
 	/// Whether to enable parsing conventional commits.
 	pub conventional_commits:  Option<bool>,
@@ -60,11 +270,45 @@ pub struct GitConfig {
 	/// Whether to split commits by line, processing each line as an individual
 	/// commit.
 	pub split_commits:         Option<bool>,
+	/// Whether to use the merge commits of the pull requests as the entries.
+	///
+	/// The message of a merge commit that is created by a forge (e.g. `Merge
+	/// pull request #123 from x/y`) is replaced with the first paragraph of its
+	/// body and the commits that are merged by the pull request are skipped.
+	pub pull_request_merges:   Option<bool>,
+	/// Whether to use the raw entry blocks of the commit messages as the
+	/// entries.
+	///
+	/// The lines between the `Changelog-Begin` and `Changelog-End` lines of a
+	/// commit message are passed through to the changelog verbatim.
+	pub raw_entries:           Option<bool>,
 
 	/// Git commit preprocessors.
 	pub commit_preprocessors:     Option<Vec<TextProcessor>>,
+	/// Additional conventional commit types (e.g. `deps`).
+	///
+	/// The commits with the types that are not configured are still accepted.
+	pub commit_types:             Option<Vec<CommitType>>,
 	/// Git commit parsers.
 	pub commit_parsers:           Option<Vec<CommitParser>>,
+	/// Scope of the commits that do not have a scope.
+	pub default_scope:            Option<String>,
+	/// Scopes that replace their aliases (e.g. `gui = "ui"`).
+	///
+	/// The scopes are replaced before the commits are parsed.
+	pub scope_aliases:            Option<HashMap<String, String>>,
+	/// Expression for filtering the commits.
+	pub commit_filter:            Option<String>,
+	/// Minimum number of commits for a scope to be listed separately in its
+	/// group.
+	pub min_scope_commits:        Option<usize>,
+	/// Scope of the commits whose scope have less than
+	/// [`min_scope_commits`](GitConfig::min_scope_commits) commits.
+	pub other_scope:              Option<String>,
+	/// Whether to hide the commits whose scope have less than
+	/// [`min_scope_commits`](GitConfig::min_scope_commits) commits instead of
+	/// merging them into the [`other_scope`](GitConfig::other_scope).
+	pub hide_small_scopes:        Option<bool>,
 	/// Whether to protect all breaking changes from being skipped by a commit
 	/// parser.
 	pub protect_breaking_commits: Option<bool>,
@@ -85,19 +329,687 @@ pub struct GitConfig {
 	pub topo_order:               Option<bool>,
 	/// Sorting of the commits inside sections.
 	pub sort_commits:             Option<String>,
+	/// Key for sorting the commits within the groups.
+	pub sort_group_commits:       Option<CommitSortKey>,
 	/// Limit the number of commits included in the changelog.
 	pub limit_commits:            Option<usize>,
+	/// Calendar period for grouping the untagged commits into releases.
+	pub release_period:           Option<ReleasePeriod>,
+	/// Timestamp that is used as the date of the commits.
+	///
+	/// The merge time of the pull requests is only available with the remote
+	/// integration. The committer time is used for the commits that are not
+	/// merged via a pull request.
+	pub commit_date:              Option<CommitDate>,
+	/// Fingerprints (or IDs) of the keys that are allowed to sign the
+	/// commits.
+	///
+	/// The signatures of the commits are verified if this is set.
+	pub signing_keys:             Option<Vec<String>>,
+	/// Whether to fail if a commit is not signed by one of the
+	/// [`signing_keys`](GitConfig::signing_keys).
+	pub require_signed_commits:   Option<bool>,
+	/// Historical moves of the paths for filtering the commits by path.
+	pub path_renames:             Option<Vec<PathRename>>,
+	/// Commits that are kept out of the changelog until they are disclosed.
+	pub embargoes:                Option<Vec<Embargo>>,
+	/// Whether to include the commits under
+	/// [`embargoes`](GitConfig::embargoes).
+	///
+	/// This is not read from the configuration file and needs to be enabled
+	/// explicitly (e.g. via `--lift-embargoes`).
+	#[serde(skip)]
+	pub lift_embargoes:           bool,
+}
+
+/// Historical move of a path in the repository.
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct PathRename {
+	/// Path before the move.
+	pub old:   String,
+	/// Path after the move.
+	pub new:   String,
+	/// SHA1 of the commit that moved the path.
+	///
+	/// The paths are mapped only for the commits before it (or for all the
+	/// commits if it is not set).
+	pub since: Option<String>,
+}
+
+impl PathRename {
+	/// Returns the new path if the given path is under the old path.
+	pub fn map(&self, path: &Path) -> Option<PathBuf> {
+		path.strip_prefix(&self.old)
+			.ok()
+			.map(|rest| Path::new(&self.new).join(rest))
+	}
+}
+
+/// Embargo for keeping the commits out of the changelog (e.g. security fixes
+/// before the disclosure).
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct Embargo {
+	/// Regex for matching the commit message.
+	#[serde(with = "serde_regex", default)]
+	pub message: Option<Regex>,
+	/// SHA1 of the commit.
+	///
+	/// It can be abbreviated (at least 7 characters).
+	#[serde(default, deserialize_with = "deserialize_sha")]
+	pub sha:     Option<String>,
+	/// Date of the disclosure (e.g. `2024-06-01`).
+	///
+	/// The commits are included from this date on (in UTC). If it is not set,
+	/// the commits are excluded until the embargoes are lifted explicitly.
+	pub until:   Option<String>,
+}
+
+impl Embargo {
+	/// Returns `true` if the embargo is in effect at the given time (Unix
+	/// timestamp).
+	pub fn is_active(&self, timestamp: i64) -> Result<bool> {
+		let Some(until) = &self.until else {
+			return Ok(true);
+		};
+		let until = parse_date(until).ok_or_else(|| {
+			Error::ArgumentError(format!(
+				"invalid embargo date: {until} (expected YYYY-MM-DD)"
+			))
+		})?;
+		Ok(timestamp < until)
+	}
+
+	/// Returns `true` if the commit with the given SHA1 and message is under
+	/// the embargo.
+	pub fn matches(&self, id: &str, message: &str) -> bool {
+		self.sha
+			.as_ref()
+			.is_some_and(|sha| id.starts_with(&sha.to_lowercase())) ||
+			self.message.as_ref().is_some_and(|v| v.is_match(message))
+	}
+}
+
+impl GitConfig {
+	/// Returns the [`embargoes`](GitConfig::embargoes) that are in effect at
+	/// the given time (Unix timestamp).
+	///
+	/// Returns nothing if the embargoes are lifted.
+	pub fn active_embargoes(&self, timestamp: i64) -> Result<Vec<&Embargo>> {
+		if self.lift_embargoes {
+			return Ok(Vec::new());
+		}
+		let mut embargoes = Vec::new();
+		for embargo in self.embargoes.iter().flatten() {
+			if embargo.is_active(timestamp)? {
+				embargoes.push(embargo);
+			}
+		}
+		Ok(embargoes)
+	}
+
+	/// Returns `true` if the signatures of the commits need to be verified.
+	pub fn verify_signatures(&self) -> bool {
+		self.signing_keys.is_some() || self.require_signed_commits.unwrap_or(false)
+	}
+
+	/// Returns the [`commit_types`](GitConfig::commit_types) entry with the
+	/// given name.
+	pub fn commit_type(&self, name: &str) -> Option<&CommitType> {
+		self.commit_types
+			.iter()
+			.flatten()
+			.find(|v| v.name.eq_ignore_ascii_case(name))
+	}
+}
+
+/// Key for sorting the commits within the groups.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ConfigReference,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitSortKey {
+	/// Commit message (description for conventional commits).
+	Message,
+	/// Commit scope, the commits without a scope come first.
+	Scope,
+	/// Commit date (see [`GitConfig::commit_date`]).
+	Date,
+	/// Author name.
+	Author,
+}
+
+/// Flavor of the changelog output.
+#[derive(
+	Debug,
+	Default,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Serialize,
+	Deserialize,
+	ConfigReference,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFlavor {
+	/// Markdown with inline HTML (e.g. GitHub releases).
+	#[default]
+	Markdown,
+	/// MDX that does not allow raw HTML (e.g. documentation sites).
+	Mdx,
+	/// HTML.
+	Html,
+}
+
+impl OutputFlavor {
+	/// Returns `true` if raw HTML is allowed in the output.
+	pub fn allows_html(&self) -> bool {
+		*self != Self::Mdx
+	}
+}
+
+/// Calendar period for grouping the commits into releases.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ConfigReference,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleasePeriod {
+	/// ISO 8601 week, e.g. `2024-W09`.
+	Week,
+	/// Calendar month, e.g. `2024-03`.
+	Month,
+	/// Quarter of the year, e.g. `2024-Q1`.
+	Quarter,
+	/// Calendar year, e.g. `2024`.
+	Year,
+}
+
+/// Timestamp that is used as the date of the commits.
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	Serialize,
+	Deserialize,
+	ConfigReference,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitDate {
+	/// Time when the commit is authored.
+	Author,
+	/// Time when the commit is committed (e.g. rebased or cherry-picked).
+	#[default]
+	Committer,
+	/// Time when the pull request of the commit is merged.
+	Merge,
+}
+
+/// Returns the year, month and day of the given timestamp (in UTC).
+pub(crate) fn civil_date(timestamp: i64) -> (i64, i64, i64) {
+	// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+	let days = timestamp.div_euclid(86400) + 719468;
+	let era = days.div_euclid(146097);
+	let day_of_era = days.rem_euclid(146097);
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 -
+		day_of_era / 146096) /
+		365;
+	let day_of_year =
+		day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 {
+		month_index + 3
+	} else {
+		month_index - 9
+	};
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+	(year, month, day)
+}
+
+/// Returns the timestamp of the start of the given date (e.g. `2024-06-01`).
+///
+/// Returns `None` if the date is not valid.
+pub(crate) fn parse_date(value: &str) -> Option<i64> {
+	let captures = DATE_REGEX.captures(value)?;
+	let [year, month, day] =
+		[1, 2, 3].map(|i| captures[i].parse::<i64>().unwrap_or_default());
+	let timestamp = civil_timestamp(year, month, day);
+	((1..=12).contains(&month) && civil_date(timestamp) == (year, month, day))
+		.then_some(timestamp)
+}
+
+/// Returns the timestamp of the start of the given day (in UTC).
+///
+/// This is the inverse of [`civil_date`].
+pub(crate) fn civil_timestamp(year: i64, month: i64, day: i64) -> i64 {
+	// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+	let year = year - i64::from(month <= 2);
+	let era = year.div_euclid(400);
+	let year_of_era = year.rem_euclid(400);
+	let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let day_of_era =
+		year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	(era * 146097 + day_of_era - 719468) * 86400
+}
+
+impl ReleasePeriod {
+	/// Returns the name of the period that the given timestamp (in UTC)
+	/// belongs to.
+	pub fn name(&self, timestamp: i64) -> String {
+		let (year, month, _) = civil_date(timestamp);
+		match self {
+			Self::Week => {
+				// The week belongs to the year that its Thursday is in.
+				let days = timestamp.div_euclid(86400);
+				let thursday = days - (days + 3).rem_euclid(7) + 3;
+				let (year, month, day) = civil_date(thursday * 86400);
+				let leap_day = i64::from(
+					month > 2 &&
+						year % 4 == 0 && (year % 100 != 0 || year % 400 == 0),
+				);
+				let day_of_year =
+					[0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334]
+						[(month - 1) as usize] +
+						day + leap_day;
+				format!("{year}-W{:02}", (day_of_year - 1) / 7 + 1)
+			}
+			Self::Month => format!("{year}-{month:02}"),
+			Self::Quarter => format!("{year}-Q{}", (month - 1) / 3 + 1),
+			Self::Year => year.to_string(),
+		}
+	}
 }
 
 /// Remote configuration.
-#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, ConfigReference)]
 pub struct RemoteConfig {
 	/// GitHub remote.
-	pub github: Remote,
+	#[serde(default)]
+	pub github:           Remote,
+	/// Whether to fail if the data cannot be fetched from the remote.
+	pub required:         Option<bool>,
+	/// Labels of the pull requests whose commits are skipped.
+	pub skip_labels:      Option<Vec<String>>,
+	/// Regex for the labels of the pull requests that are used as the groups
+	/// of the commits (e.g. `^area/(.+)$`).
+	#[serde(with = "serde_regex", default)]
+	pub group_labels:     Option<Regex>,
+	/// Whether to search the users by email for the commit authors that are
+	/// not linked to a GitHub account.
+	pub search_users:     Option<bool>,
+	/// Whether to merge the commits of the same pull request into a single
+	/// entry that is titled by the pull request.
+	pub merge_pr_commits: Option<bool>,
+	/// Whether to close the milestone that is titled after the version of the
+	/// latest release (e.g. `v1.0.0`) after generating the changelog.
+	pub close_milestone:  Option<bool>,
+	/// Whether the network access is disabled.
+	///
+	/// This is not read from the configuration file and needs to be enabled
+	/// explicitly (e.g. via `--offline`).
+	#[serde(skip)]
+	pub offline:          bool,
+}
+
+/// Configuration for a range of releases.
+///
+/// This is useful for generating the changelog of the releases that are
+/// made before the conventions of the project are changed.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct EraConfig {
+	/// First tag of the era (inclusive).
+	pub from:  Option<String>,
+	/// Last tag of the era (inclusive).
+	pub until: Option<String>,
+	/// Changelog body for the releases of the era.
+	pub body:  Option<String>,
+	/// Git configuration for processing the commits of the era.
+	///
+	/// It replaces the [`git`](Config::git) section for the commits of the
+	/// era, the options related to the tags are not used.
+	pub git:   Option<GitConfig>,
+}
+
+impl EraConfig {
+	/// Returns `true` if the given version is in the era.
+	///
+	/// The versions are compared semantically and the unreleased changes
+	/// (i.e. `None`) are considered to be the newest.
+	pub fn contains(&self, version: Option<&str>) -> bool {
+		let compare = |tag: &str| match (version, tag_version(tag)) {
+			(Some(version), Some(tag)) => tag_version(version).map(|v| v.cmp(&tag)),
+			(None, Some(_)) => Some(Ordering::Greater),
+			_ => None,
+		};
+		self.from.as_deref().map_or(true, |tag| {
+			compare(tag).is_some_and(|v| v != Ordering::Less)
+		}) && self.until.as_deref().map_or(true, |tag| {
+			compare(tag).is_some_and(|v| v != Ordering::Greater)
+		})
+	}
+}
+
+/// Workspace configuration.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct WorkspaceConfig {
+	/// Packages of the workspace.
+	#[serde(default)]
+	pub packages:          Vec<PackageConfig>,
+	/// Template of the umbrella changelog.
+	pub umbrella_template: Option<String>,
+}
+
+/// Package of a workspace.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct PackageConfig {
+	/// Name of the package.
+	pub name:        String,
+	/// Directory of the package.
+	pub path:        String,
+	/// Regex for matching the release tags of the package.
+	///
+	/// Defaults to the tags that start with the name of the package (e.g.
+	/// `core-v1.0.0`).
+	#[serde(with = "serde_regex", default)]
+	pub tag_pattern: Option<Regex>,
+	/// Path or URL of the changelog of the package.
+	pub changelog:   Option<String>,
+}
+
+impl PackageConfig {
+	/// Returns the regex for matching the release tags of the package.
+	pub fn tag_pattern(&self) -> Result<Regex> {
+		match &self.tag_pattern {
+			Some(pattern) => Ok(pattern.clone()),
+			None => Ok(Regex::new(&format!(
+				"^{}-v?[0-9]",
+				regex::escape(&self.name)
+			))?),
+		}
+	}
+}
+
+/// Increment of the version.
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Serialize,
+	Deserialize,
+	ConfigReference,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+	/// Version is not incremented.
+	#[serde(alias = "skip")]
+	None,
+	/// Pre-release identifier is incremented.
+	///
+	/// This is only applied when the previous version is a pre-release.
+	Prerelease,
+	/// Patch version is incremented.
+	Patch,
+	/// Minor version is incremented.
+	Minor,
+	/// Major version is incremented.
+	Major,
+}
+
+impl BumpLevel {
+	/// Parses the increment, e.g. from a commit trailer.
+	///
+	/// `skip` is accepted as an alias of `none`.
+	pub fn parse(value: &str) -> Option<Self> {
+		match value.trim().to_lowercase().as_str() {
+			"none" | "skip" => Some(Self::None),
+			"prerelease" => Some(Self::Prerelease),
+			"patch" => Some(Self::Patch),
+			"minor" => Some(Self::Minor),
+			"major" => Some(Self::Major),
+			_ => None,
+		}
+	}
+}
+
+impl fmt::Display for BumpLevel {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::None => write!(f, "none"),
+			Self::Prerelease => write!(f, "prerelease"),
+			Self::Patch => write!(f, "patch"),
+			Self::Minor => write!(f, "minor"),
+			Self::Major => write!(f, "major"),
+		}
+	}
+}
+
+/// Format of the build number that is appended to the version.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ConfigReference,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildNumber {
+	/// Build metadata, e.g. `1.2.3+build.45`.
+	Metadata,
+	/// Fourth numeric component, e.g. `1.2.3.45`.
+	Component,
+}
+
+/// Version bump configuration.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct BumpConfig {
+	/// Rules for overriding the increments that the commits contribute.
+	pub overrides:    Option<Vec<BumpOverride>>,
+	/// Build number that is incremented along with the version.
+	pub build_number: Option<BuildNumber>,
+	/// Increments of the conventional commit types (e.g. `perf = "patch"`).
+	///
+	/// The types that are not set increment the patch version, except `feat`
+	/// which increments the minor version. Breaking changes always increment
+	/// the major version.
+	#[serde(default)]
+	pub types:        HashMap<String, BumpLevel>,
+}
+
+/// Deserializes the (abbreviated) SHA1 of a commit.
+///
+/// The SHA1s that are shorter than [`MIN_SHA_LENGTH`] are rejected since
+/// they would match too many commits.
+fn deserialize_sha<'de, D>(
+	deserializer: D,
+) -> std::result::Result<Option<String>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	let sha = Option::<String>::deserialize(deserializer)?;
+	if let Some(sha) = &sha {
+		if sha.len() < MIN_SHA_LENGTH || !sha.chars().all(|c| c.is_ascii_hexdigit())
+		{
+			return Err(serde::de::Error::custom(format!(
+				"invalid commit SHA1: `{sha}` (expected at least {MIN_SHA_LENGTH} \
+				 hexadecimal characters)"
+			)));
+		}
+	}
+	Ok(sha.map(|v| v.to_lowercase()))
+}
+
+/// Rule for overriding the increment that a commit contributes.
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct BumpOverride {
+	/// SHA1 of the commit.
+	///
+	/// It can be abbreviated (at least 7 characters).
+	#[serde(default, deserialize_with = "deserialize_sha")]
+	pub sha:     Option<String>,
+	/// Regex for matching the commit message.
+	#[serde(with = "serde_regex", default)]
+	pub message: Option<Regex>,
+	/// Increment of the matched commits.
+	pub bump:    BumpLevel,
+}
+
+impl BumpOverride {
+	/// Returns `true` if the rule matches the given commit ID and message.
+	///
+	/// The rules without any condition do not match.
+	pub fn matches(&self, id: &str, message: &str) -> bool {
+		(self.sha.is_some() || self.message.is_some()) &&
+			self.sha
+				.as_ref()
+				.map_or(true, |sha| id.starts_with(&sha.to_lowercase())) &&
+			self.message
+				.as_ref()
+				.map_or(true, |regex| regex.is_match(message))
+	}
+}
+
+/// Lint configuration.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct LintConfig {
+	/// Maximum number of characters in the commit subject.
+	pub max_subject_length: Option<usize>,
+	/// Words that are not allowed in the commit messages.
+	pub disallowed_words:   Option<Vec<String>>,
+	/// Misspelled words along with their corrections (e.g. `teh = "the"`).
+	///
+	/// They are checked in addition to the common misspellings.
+	pub misspellings:       Option<HashMap<String, String>>,
+	/// Severities of the lint rules.
+	#[serde(default)]
+	pub rules:              LintRules,
+	/// Checks of the rendered changelog.
+	pub output:             Option<OutputLintConfig>,
+}
+
+/// Configuration of the checks of the rendered changelog.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct OutputLintConfig {
+	/// Maximum number of characters in a line.
+	pub max_line_length:       Option<usize>,
+	/// Whether to check if the absolute URLs respond successfully.
+	pub check_urls:            Option<bool>,
+	/// Maximum number of the URLs that are checked at the same time.
+	pub url_check_concurrency: Option<usize>,
+	/// File for caching the URLs that are alive between the runs.
+	pub url_check_cache:       Option<PathBuf>,
+	/// Severities of the rules.
+	#[serde(default)]
+	pub rules:                 OutputLintRules,
+}
+
+/// Severities of the rules for checking the rendered changelog.
+///
+/// The rules that are not set use their default severity.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct OutputLintRules {
+	/// Line is longer than
+	/// [`max_line_length`](OutputLintConfig::max_line_length).
+	///
+	/// Defaults to `warning`.
+	pub line_length:          Option<LintSeverity>,
+	/// Relative link points to a file that does not exist.
+	///
+	/// Defaults to `error`.
+	pub broken_links:         Option<LintSeverity>,
+	/// Heading has the same text as a sibling heading.
+	///
+	/// Defaults to `warning`.
+	pub duplicate_headings:   Option<LintSeverity>,
+	/// Heading level is incremented by more than one.
+	///
+	/// Defaults to `warning`.
+	pub heading_increment:    Option<LintSeverity>,
+	/// Line ends with whitespace (except for a hard line break).
+	///
+	/// Defaults to `off`.
+	pub trailing_spaces:      Option<LintSeverity>,
+	/// Multiple consecutive blank lines.
+	///
+	/// Defaults to `off`.
+	pub multiple_blank_lines: Option<LintSeverity>,
+	/// Absolute URL does not respond successfully (if
+	/// [`check_urls`](OutputLintConfig::check_urls) is enabled).
+	///
+	/// Defaults to `error`.
+	pub dead_links:           Option<LintSeverity>,
+}
+
+/// Severities of the lint rules.
+///
+/// The rules that are not set use their default severity.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct LintRules {
+	/// Commit is not a conventional commit.
+	///
+	/// Defaults to `error` if conventional commits are enabled.
+	pub conventional:     Option<LintSeverity>,
+	/// Subject is longer than
+	/// [`max_subject_length`](LintConfig::max_subject_length).
+	///
+	/// Defaults to `warning`.
+	pub subject_length:   Option<LintSeverity>,
+	/// Subject does not start with a verb in the imperative mood.
+	///
+	/// Defaults to `off`.
+	pub imperative_mood:  Option<LintSeverity>,
+	/// Subject ends with a period.
+	///
+	/// Defaults to `off`.
+	pub trailing_period:  Option<LintSeverity>,
+	/// Commit message contains one of the
+	/// [`disallowed_words`](LintConfig::disallowed_words).
+	///
+	/// Defaults to `error`.
+	pub disallowed_words: Option<LintSeverity>,
+	/// Commit message contains a misspelled word.
+	///
+	/// Defaults to `warning`.
+	pub spelling:         Option<LintSeverity>,
+}
+
+/// Severity of a lint rule.
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Serialize,
+	Deserialize,
+	ConfigReference,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+	/// Rule is disabled.
+	Off,
+	/// Violations are reported.
+	Warning,
+	/// Violations are reported and the lint fails.
+	Error,
+}
+
+impl fmt::Display for LintSeverity {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Off => write!(f, "off"),
+			Self::Warning => write!(f, "warning"),
+			Self::Error => write!(f, "error"),
+		}
+	}
 }
 
 /// A single remote.
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
 pub struct Remote {
 	/// Owner of the remote.
 	pub owner: String,
@@ -136,8 +1048,46 @@ impl Remote {
 	}
 }
 
+/// Metadata of a commit group.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct CommitGroup {
+	/// Name of the group that is set by the commit parsers.
+	pub name:      String,
+	/// Title of the group.
+	pub title:     Option<String>,
+	/// Icon of the group.
+	pub icon:      Option<String>,
+	/// Whether to collapse the group (e.g. into a `<details>` block).
+	pub collapse:  Option<bool>,
+	/// Normalization of the entries of the group.
+	///
+	/// The unset options are taken from [`ChangelogConfig::normalize`].
+	pub normalize: Option<EntryNormalization>,
+}
+
+/// Additional conventional commit type.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct CommitType {
+	/// Name of the type.
+	#[serde(rename = "type")]
+	pub name:      String,
+	/// Group of the commits with this type.
+	///
+	/// It takes precedence over the group that is set by the commit parsers.
+	pub group:     Option<String>,
+	/// Increment of the version for the commits with this type.
+	///
+	/// The increment that is set via [`bump`](Config::bump) takes precedence.
+	pub bump:      Option<BumpLevel>,
+	/// Whether the commits with this type (or in its group) are the highlights
+	/// of the releases.
+	///
+	/// Defaults to `true` for `feat` and `false` for the other types.
+	pub highlight: Option<bool>,
+}
+
 /// Parser for grouping commits.
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
 pub struct CommitParser {
 	/// SHA1 of the commit.
 	pub sha:           Option<String>,
@@ -160,10 +1110,52 @@ pub struct CommitParser {
 	/// Regex for matching the field value.
 	#[serde(with = "serde_regex", default)]
 	pub pattern:       Option<Regex>,
+	/// Regex that the field value must not match.
+	#[serde(with = "serde_regex", default)]
+	pub pattern_not:   Option<Regex>,
+	/// Conditions that all need to be met for matching the commit.
+	pub and:           Option<Vec<CommitCondition>>,
+	/// Conditions that at least one of them needs to be met for matching the
+	/// commit.
+	pub or:            Option<Vec<CommitCondition>>,
+	/// Whether to keep matching the subsequent parsers after this one.
+	///
+	/// The subsequent parsers override the group and scope that are set by
+	/// this parser if they set them as well.
+	#[serde(rename = "continue")]
+	pub continue_:     Option<bool>,
+}
+
+/// Condition for matching the commits in [`CommitParser`]s.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct CommitCondition {
+	/// Field name of the commit to match the regex against.
+	pub field:       String,
+	/// Regex that the field value needs to match.
+	#[serde(with = "serde_regex", default)]
+	pub pattern:     Option<Regex>,
+	/// Regex that the field value must not match.
+	#[serde(with = "serde_regex", default)]
+	pub pattern_not: Option<Regex>,
+}
+
+impl CommitCondition {
+	/// Returns `true` if the given field value meets the condition.
+	///
+	/// A missing value does not match the `pattern` and it is not matched by
+	/// the `pattern_not` either.
+	pub fn is_met(&self, value: Option<&str>) -> bool {
+		self.pattern
+			.as_ref()
+			.map_or(true, |regex| value.is_some_and(|v| regex.is_match(v))) &&
+			self.pattern_not
+				.as_ref()
+				.map_or(true, |regex| !value.is_some_and(|v| regex.is_match(v)))
+	}
 }
 
 /// TextProcessor, e.g. for modifying commit messages.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigReference)]
 pub struct TextProcessor {
 	/// Regex for matching a text to replace.
 	#[serde(with = "serde_regex")]
@@ -193,8 +1185,85 @@ impl TextProcessor {
 	}
 }
 
+/// Pagination of the long releases.
+///
+/// The releases that have more commits than the limit are split into pages
+/// and the pages after the first one are written to separate files.
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct PaginationConfig {
+	/// Maximum number of commits on a page.
+	pub max_commits:  usize,
+	/// Template for the path of the continuation pages.
+	pub path:         Option<String>,
+	/// Template for the link to the next page.
+	pub continuation: Option<String>,
+}
+
+impl PaginationConfig {
+	/// Default template for the path of the continuation pages.
+	pub const DEFAULT_PATH: &'static str = "CHANGELOG-{{ version }}-{{ page }}.md";
+	/// Default template for the link to the next page.
+	pub const DEFAULT_CONTINUATION: &'static str =
+		"\n_Continued on [page {{ page }} of {{ pages }}]({{ path }})._\n";
+}
+
+/// Hook for shortening the long URLs, e.g. the compare and issue links.
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigReference)]
+pub struct UrlShortener {
+	/// Command that is run for each long URL.
+	///
+	/// The URL is passed via stdin and the `URL` environment variable and the
+	/// output of the command is used as the shortened URL.
+	pub command:    String,
+	/// Minimum length of the URLs to shorten.
+	pub min_length: Option<usize>,
+}
+
+impl UrlShortener {
+	/// Default minimum length of the URLs to shorten.
+	const DEFAULT_MIN_LENGTH: usize = 60;
+
+	/// Replaces the long URLs in the text with the shortened ones.
+	///
+	/// The shortened URLs are stored in the given cache so that the command is
+	/// run only once for each URL.
+	pub fn shorten(
+		&self,
+		text: &str,
+		cache: &mut HashMap<String, String>,
+	) -> Result<String> {
+		let min_length = self.min_length.unwrap_or(Self::DEFAULT_MIN_LENGTH);
+		let mut output = String::with_capacity(text.len());
+		let mut last_index = 0;
+		for url in URL_REGEX.find_iter(text) {
+			if url.as_str().len() < min_length {
+				continue;
+			}
+			let short_url = match cache.get(url.as_str()) {
+				Some(short_url) => short_url.to_string(),
+				None => {
+					let short_url = command::run(
+						&self.command,
+						Some(url.as_str().to_string()),
+						vec![("URL", url.as_str())],
+					)?
+					.trim()
+					.to_string();
+					cache.insert(url.as_str().to_string(), short_url.clone());
+					short_url
+				}
+			};
+			output.push_str(&text[last_index..url.start()]);
+			output.push_str(&short_url);
+			last_index = url.end();
+		}
+		output.push_str(&text[last_index..]);
+		Ok(output)
+	}
+}
+
 /// Parser for extracting links in commits.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigReference)]
 pub struct LinkParser {
 	/// Regex for finding links in the commit message.
 	#[serde(with = "serde_regex")]
@@ -205,9 +1274,99 @@ pub struct LinkParser {
 	pub text:    Option<String>,
 }
 
+/// Context for rendering the templates in the configuration values.
+///
+/// The values are rendered before the changelog is generated, e.g.
+/// `tag_pattern = "{{ package }}-v[0-9].*"`.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigContext {
+	/// Name of the package (e.g. in a workspace).
+	pub package: Option<String>,
+	/// Version of the release (e.g. from `--tag`).
+	pub version: Option<String>,
+}
+
+impl ConfigContext {
+	/// Returns `true` if the given value contains a template.
+	fn is_template(value: &str) -> bool {
+		value.contains("{{") || value.contains("{%")
+	}
+
+	/// Renders the template in the given value.
+	///
+	/// The variables that are not set (e.g. `package`) cannot be used unless
+	/// they are given a default value with the `default` filter.
+	pub fn render(&self, value: &str) -> Result<String> {
+		if !Self::is_template(value) {
+			return Ok(value.to_string());
+		}
+		let mut context = tera::Context::new();
+		if let Some(package) = &self.package {
+			context.insert("package", package);
+		}
+		if let Some(version) = &self.version {
+			context.insert("version", version);
+		}
+		tera::Tera::one_off(value, &context, false).map_err(|e| {
+			match std::error::Error::source(&e) {
+				Some(source) => Error::TemplateRenderError(source.to_string()),
+				None => Error::TemplateError(e),
+			}
+		})
+	}
+
+	/// Renders the templates in the string values recursively.
+	///
+	/// The [`TEMPLATE_KEYS`] are skipped.
+	fn render_values(&self, value: &mut config::Value, key: &str) -> Result<()> {
+		match &mut value.kind {
+			config::ValueKind::String(text) if Self::is_template(text) => {
+				*text = self.render(text).map_err(|e| match e {
+					Error::TemplateRenderError(e) => {
+						Error::TemplateRenderError(format!("{key}: {e}"))
+					}
+					e => e,
+				})?;
+			}
+			config::ValueKind::Array(values) => {
+				for value in values {
+					self.render_values(value, &format!("{key}[]"))?;
+				}
+			}
+			config::ValueKind::Table(table) => {
+				for (name, value) in table {
+					let key = if key.is_empty() {
+						name.to_string()
+					} else {
+						format!("{key}.{name}")
+					};
+					if !TEMPLATE_KEYS.contains(&key.as_str()) {
+						self.render_values(value, &key)?;
+					}
+				}
+			}
+			_ => {}
+		}
+		Ok(())
+	}
+}
+
 impl Config {
 	/// Parses the config file and returns the values.
+	///
+	/// See [`Config::parse_with_context`].
 	pub fn parse(path: &Path) -> Result<Config> {
+		Self::parse_with_context(path, &ConfigContext::default())
+	}
+
+	/// Parses the config file and returns the values.
+	///
+	/// The templates in the values (except for the changelog templates) are
+	/// rendered with the given context.
+	pub fn parse_with_context(
+		path: &Path,
+		context: &ConfigContext,
+	) -> Result<Config> {
 		let config_builder = if path.file_name() == Some(OsStr::new("Cargo.toml")) ||
 			path.file_name() == Some(OsStr::new("pyproject.toml"))
 		{
@@ -229,12 +1388,37 @@ impl Config {
 		} else {
 			config::Config::builder().add_source(config::File::from(path))
 		};
-		Ok(config_builder
-			.add_source(
+		Self::build(
+			config_builder.add_source(
 				config::Environment::with_prefix("GIT_CLIFF").separator("__"),
-			)
-			.build()?
-			.try_deserialize()?)
+			),
+			context,
+		)
+	}
+
+	/// Builds the configuration from the sources and renders the templates in
+	/// the values.
+	///
+	/// The values are deserialized after rendering so that the values of the
+	/// environment variables are still converted to the types of the fields
+	/// (e.g. `GIT_CLIFF__GIT__LIMIT_COMMITS=1`).
+	fn build(
+		builder: config::ConfigBuilder<config::builder::DefaultState>,
+		context: &ConfigContext,
+	) -> Result<Config> {
+		let mut value = builder.build()?.try_deserialize::<config::Value>()?;
+		context.render_values(&mut value, "")?;
+		Ok(Config::deserialize(value)?)
+	}
+
+	/// Returns the index and the configuration of the first era that
+	/// contains the given version.
+	pub fn era(&self, version: Option<&str>) -> Option<(usize, &EraConfig)> {
+		self.eras
+			.iter()
+			.flatten()
+			.enumerate()
+			.find(|(_, era)| era.contains(version))
 	}
 }
 
@@ -281,6 +1465,205 @@ mod test {
 		Ok(())
 	}
 
+	#[test]
+	fn render_config_values() -> Result<()> {
+		let context = ConfigContext {
+			package: Some(String::from("core")),
+			version: None,
+		};
+		let config = Config::build(
+			config::Config::builder()
+				.add_source(config::File::from_str(
+					r#"
+					[changelog]
+					body = "{{ version }}"
+					[git]
+					tag_pattern = "{{ package }}-v[0-9]"
+					skip_tags = "{{ version | default(value='x') }}"
+					"#,
+					config::FileFormat::Toml,
+				))
+				.add_source(
+					config::Environment::with_prefix("GIT_CLIFF")
+						.separator("__")
+						.source(Some(
+							[
+								("GIT_CLIFF__GIT__LIMIT_COMMITS", "1"),
+								("GIT_CLIFF__GIT__FILTER_COMMITS", "true"),
+								("GIT_CLIFF__GIT__SORT_COMMITS", "{{ package }}"),
+							]
+							.into_iter()
+							.map(|(k, v)| (k.to_string(), v.to_string()))
+							.collect(),
+						)),
+				),
+			&context,
+		)?;
+		assert_eq!(Some(String::from("{{ version }}")), config.changelog.body);
+		assert_eq!(Some(1), config.git.limit_commits);
+		assert_eq!(Some(true), config.git.filter_commits);
+		assert_eq!(Some(String::from("core")), config.git.sort_commits);
+		assert_eq!(
+			Some(String::from("core-v[0-9]")),
+			config.git.tag_pattern.map(|v| v.to_string())
+		);
+		assert_eq!(
+			Some(String::from("x")),
+			config.git.skip_tags.map(|v| v.to_string())
+		);
+		assert_eq!("v1.0.0", context.render("v1.0.0")?);
+		assert!(context.render("{{ version }}").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn era_config() {
+		let era = EraConfig {
+			from: Some(String::from("v0.2.0")),
+			until: Some(String::from("v1.0.0")),
+			..Default::default()
+		};
+		assert!(!era.contains(Some("v0.1.0")));
+		assert!(era.contains(Some("v0.2.0")));
+		assert!(era.contains(Some("0.9.1")));
+		assert!(era.contains(Some("v1.0.0")));
+		assert!(!era.contains(Some("v1.0.1")));
+		assert!(!era.contains(Some("latest")));
+		assert!(!era.contains(None));
+		let era = EraConfig {
+			from: Some(String::from("v1.0.1")),
+			..Default::default()
+		};
+		assert!(era.contains(Some("v2.0.0")));
+		assert!(era.contains(None));
+	}
+
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn url_shortener() -> Result<()> {
+		let url_shortener = UrlShortener {
+			command:    String::from(r#"echo "https://s.io/${URL##*/}""#),
+			min_length: Some(30),
+		};
+		let mut cache = HashMap::new();
+		assert_eq!(
+			"[1.0.0]: https://s.io/v0.9.0..v1.0.0.\n\
+			 See https://git.io/x and (https://s.io/v0.9.0..v1.0.0)",
+			url_shortener.shorten(
+				"[1.0.0]: https://github.com/orhun/git-cliff/compare/v0.9.0..v1.0.0.\n\
+				 See https://git.io/x and (https://github.com/orhun/git-cliff/compare/v0.9.0..v1.0.0)",
+				&mut cache
+			)?
+		);
+		assert_eq!(1, cache.len());
+		Ok(())
+	}
+
+	#[test]
+	fn path_rename() {
+		let rename = PathRename {
+			old:   String::from("lib/core"),
+			new:   String::from("crates/core"),
+			since: None,
+		};
+		assert_eq!(
+			Some(PathBuf::from("crates/core/src/lib.rs")),
+			rename.map(Path::new("lib/core/src/lib.rs"))
+		);
+		assert_eq!(None, rename.map(Path::new("lib/core-utils/src/lib.rs")));
+		assert_eq!(None, rename.map(Path::new("crates/core/src/lib.rs")));
+	}
+
+	#[test]
+	fn release_period() {
+		for (period, timestamp, name) in [
+			(ReleasePeriod::Week, 1709251200, "2024-W09"),
+			(ReleasePeriod::Week, 1704067200, "2024-W01"),
+			(ReleasePeriod::Week, 1735603200, "2025-W01"),
+			(ReleasePeriod::Week, 1609459200, "2020-W53"),
+			(ReleasePeriod::Month, 1709251200, "2024-03"),
+			(ReleasePeriod::Quarter, 1709251200, "2024-Q1"),
+			(ReleasePeriod::Year, 1709251200, "2024"),
+		] {
+			assert_eq!(name, period.name(timestamp));
+		}
+	}
+
+	#[test]
+	fn bump_types() -> Result<()> {
+		let config = toml::from_str::<BumpConfig>(
+			r#"
+			[types]
+			perf = "minor"
+			"#,
+		)?;
+		assert_eq!(
+			HashMap::from([(String::from("perf"), BumpLevel::Minor)]),
+			config.types
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn bump_override() -> Result<()> {
+		let rule = toml::from_str::<BumpOverride>(
+			r#"
+			sha = "A1B2C3D"
+			bump = "prerelease"
+			"#,
+		)?;
+		assert!(rule.matches("a1b2c3d4e5f6", "fix: typo"));
+		assert!(!rule.matches("b1b2c3d4e5f6", "fix: typo"));
+		assert_eq!(BumpLevel::Prerelease, rule.bump);
+		assert_eq!(Some(BumpLevel::Prerelease), BumpLevel::parse("Prerelease"));
+		for sha in ["", "a1b2", "xyzxyzxyz"] {
+			assert!(toml::from_str::<BumpOverride>(&format!(
+				"sha = \"{sha}\"\nbump = \"major\""
+			))
+			.is_err());
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn embargo() -> Result<()> {
+		let embargo = Embargo {
+			message: Regex::new(r"^fix\(security\)").ok(),
+			sha:     Some(String::from("1A2B3C4")),
+			until:   Some(String::from("2024-05-07")),
+		};
+		assert!(embargo.matches("1111111", "fix(security): escape input"));
+		assert!(embargo.matches("1a2b3c4d", "fix: typo"));
+		assert!(!embargo.matches("1111111", "fix: typo"));
+
+		// 2024-05-06 and 2024-05-07
+		assert!(embargo.is_active(1714953600)?);
+		assert!(!embargo.is_active(1715040000)?);
+		assert!(Embargo {
+			until: None,
+			..embargo.clone()
+		}
+		.is_active(1715040000)?);
+		for until in ["May 7th", "2024-13-45", "2023-02-29"] {
+			assert!(Embargo {
+				until: Some(String::from(until)),
+				..embargo.clone()
+			}
+			.is_active(1714953600)
+			.is_err());
+		}
+		assert!(toml::from_str::<Embargo>("sha = \"\"").is_err());
+
+		let mut config = GitConfig {
+			embargoes: Some(vec![embargo]),
+			..Default::default()
+		};
+		assert_eq!(1, config.active_embargoes(1714953600)?.len());
+		config.lift_embargoes = true;
+		assert!(config.active_embargoes(1714953600)?.is_empty());
+		Ok(())
+	}
+
 	#[test]
 	fn remote_config() {
 		let remote1 = Remote::new("abc", "xyz1");
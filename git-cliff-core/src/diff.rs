@@ -0,0 +1,407 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Number of the unchanged lines that are shown around the changes.
+const CONTEXT_LINES: usize = 3;
+
+/// Furthest reaching paths of the diagonals, indexed by the diagonal.
+struct Diagonals {
+	/// Offset of the zero diagonal.
+	offset: isize,
+	/// Furthest reaching positions in the old lines.
+	values: Vec<usize>,
+}
+
+impl Diagonals {
+	/// Constructs a new instance for the given maximum number of edits.
+	fn new(max_edits: usize) -> Self {
+		Self {
+			offset: max_edits as isize,
+			values: vec![0; 2 * max_edits + 1],
+		}
+	}
+
+	/// Returns the position of the given diagonal.
+	fn get(&self, k: isize) -> usize {
+		self.values[(k + self.offset) as usize]
+	}
+
+	/// Sets the position of the given diagonal.
+	fn set(&mut self, k: isize, value: usize) {
+		self.values[(k + self.offset) as usize] = value;
+	}
+}
+
+/// Returns the number of the equal lines at the start of the ranges.
+fn common_prefix(
+	old: &[&str],
+	old_range: Range<usize>,
+	new: &[&str],
+	new_range: Range<usize>,
+) -> usize {
+	old[old_range]
+		.iter()
+		.zip(&new[new_range])
+		.take_while(|(a, b)| a == b)
+		.count()
+}
+
+/// Returns the number of the equal lines at the end of the ranges.
+fn common_suffix(
+	old: &[&str],
+	old_range: Range<usize>,
+	new: &[&str],
+	new_range: Range<usize>,
+) -> usize {
+	old[old_range]
+		.iter()
+		.rev()
+		.zip(new[new_range].iter().rev())
+		.take_while(|(a, b)| a == b)
+		.count()
+}
+
+/// Returns the start of the middle snake of the shortest edit script.
+///
+/// See "An O(ND) Difference Algorithm and Its Variations" (Myers, 1986).
+fn middle_snake(
+	old: &[&str],
+	old_range: Range<usize>,
+	new: &[&str],
+	new_range: Range<usize>,
+	forward: &mut Diagonals,
+	backward: &mut Diagonals,
+) -> Option<(usize, usize)> {
+	let (n, m) = (old_range.len(), new_range.len());
+	let delta = n as isize - m as isize;
+	let odd = delta & 1 == 1;
+	forward.set(1, 0);
+	backward.set(1, 0);
+	for d in 0..((n + m).div_ceil(2) + 1) as isize {
+		for k in (-d..=d).rev().step_by(2) {
+			let mut x =
+				if k == -d || (k != d && forward.get(k - 1) < forward.get(k + 1)) {
+					forward.get(k + 1)
+				} else {
+					forward.get(k - 1) + 1
+				};
+			let y = (x as isize - k) as usize;
+			let start = (x, y);
+			if x < n && y < m {
+				x += common_prefix(
+					old,
+					old_range.start + x..old_range.end,
+					new,
+					new_range.start + y..new_range.end,
+				);
+			}
+			forward.set(k, x);
+			if odd &&
+				(k - delta).abs() < d &&
+				forward.get(k) + backward.get(delta - k) >= n
+			{
+				return Some((old_range.start + start.0, new_range.start + start.1));
+			}
+		}
+		for k in (-d..=d).rev().step_by(2) {
+			let mut x = if k == -d ||
+				(k != d && backward.get(k - 1) < backward.get(k + 1))
+			{
+				backward.get(k + 1)
+			} else {
+				backward.get(k - 1) + 1
+			};
+			let mut y = (x as isize - k) as usize;
+			if x < n && y < m {
+				let length = common_suffix(
+					old,
+					old_range.start..old_range.start + n - x,
+					new,
+					new_range.start..new_range.start + m - y,
+				);
+				x += length;
+				y += length;
+			}
+			backward.set(k, x);
+			if !odd &&
+				(k - delta).abs() <= d &&
+				backward.get(k) + forward.get(delta - k) >= n
+			{
+				return Some((old_range.start + n - x, new_range.start + m - y));
+			}
+		}
+	}
+	None
+}
+
+/// Collects the index pairs of the equal lines in the given ranges.
+fn collect_common_lines(
+	old: &[&str],
+	mut old_range: Range<usize>,
+	new: &[&str],
+	mut new_range: Range<usize>,
+	diagonals: &mut (Diagonals, Diagonals),
+	pairs: &mut Vec<(usize, usize)>,
+) {
+	let prefix = common_prefix(old, old_range.clone(), new, new_range.clone());
+	pairs.extend((0..prefix).map(|i| (old_range.start + i, new_range.start + i)));
+	old_range.start += prefix;
+	new_range.start += prefix;
+	let suffix = common_suffix(old, old_range.clone(), new, new_range.clone());
+	old_range.end -= suffix;
+	new_range.end -= suffix;
+	if !old_range.is_empty() && !new_range.is_empty() {
+		if let Some((x, y)) = middle_snake(
+			old,
+			old_range.clone(),
+			new,
+			new_range.clone(),
+			&mut diagonals.0,
+			&mut diagonals.1,
+		) {
+			collect_common_lines(
+				old,
+				old_range.start..x,
+				new,
+				new_range.start..y,
+				diagonals,
+				pairs,
+			);
+			collect_common_lines(
+				old,
+				x..old_range.end,
+				new,
+				y..new_range.end,
+				diagonals,
+				pairs,
+			);
+		}
+	}
+	pairs.extend((0..suffix).map(|i| (old_range.end + i, new_range.end + i)));
+}
+
+/// Returns the index pairs of the longest common subsequence of the lines.
+///
+/// The lines that only exist in one of the versions are left out since they
+/// can not be a part of the subsequence. The shortest edit script of the rest
+/// is found in linear space after stripping the common prefix and suffix.
+pub(crate) fn common_lines(old: &[&str], new: &[&str]) -> Vec<(usize, usize)> {
+	let (old_set, new_set) = (
+		old.iter().collect::<HashSet<_>>(),
+		new.iter().collect::<HashSet<_>>(),
+	);
+	let old_indices = (0..old.len())
+		.filter(|i| new_set.contains(&old[*i]))
+		.collect::<Vec<usize>>();
+	let new_indices = (0..new.len())
+		.filter(|i| old_set.contains(&new[*i]))
+		.collect::<Vec<usize>>();
+	let old = old_indices.iter().map(|i| old[*i]).collect::<Vec<&str>>();
+	let new = new_indices.iter().map(|i| new[*i]).collect::<Vec<&str>>();
+	let max_edits = (old.len() + new.len()).div_ceil(2) + 1;
+	let mut diagonals = (Diagonals::new(max_edits), Diagonals::new(max_edits));
+	let mut pairs = Vec::new();
+	collect_common_lines(
+		&old,
+		0..old.len(),
+		&new,
+		0..new.len(),
+		&mut diagonals,
+		&mut pairs,
+	);
+	pairs
+		.into_iter()
+		.map(|(i, j)| (old_indices[i], new_indices[j]))
+		.collect()
+}
+
+/// Line of the diff along with its positions in the old and new versions.
+struct DiffLine<'a> {
+	/// Marker of the line (` `, `-` or `+`).
+	marker:   char,
+	/// Index of the line in the old version (or the next line if added).
+	old_line: usize,
+	/// Index of the line in the new version (or the next line if removed).
+	new_line: usize,
+	/// Content of the line.
+	text:     &'a str,
+}
+
+/// Returns the lines of the diff between the old and new lines.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+	let mut lines = Vec::new();
+	let (mut i, mut j) = (0, 0);
+	for (old_index, new_index) in common_lines(old, new)
+		.into_iter()
+		.chain([(old.len(), new.len())])
+	{
+		for (i, text) in old.iter().enumerate().take(old_index).skip(i) {
+			lines.push(DiffLine {
+				marker: '-',
+				old_line: i,
+				new_line: j,
+				text,
+			});
+		}
+		for (j, text) in new.iter().enumerate().take(new_index).skip(j) {
+			lines.push(DiffLine {
+				marker: '+',
+				old_line: old_index,
+				new_line: j,
+				text,
+			});
+		}
+		if old_index < old.len() {
+			lines.push(DiffLine {
+				marker:   ' ',
+				old_line: old_index,
+				new_line: new_index,
+				text:     old[old_index],
+			});
+		}
+		(i, j) = (old_index + 1, new_index + 1);
+	}
+	lines
+}
+
+/// Returns the range of a hunk in the format of the hunk headers.
+///
+/// The start is the line before the hunk if the hunk is empty.
+fn hunk_range(start: usize, count: usize) -> String {
+	if count == 0 {
+		format!("{start},0")
+	} else {
+		format!("{},{count}", start + 1)
+	}
+}
+
+/// Returns the unified diff of the changes to the file at the given path.
+///
+/// The file is treated as a new file if the old content is `None`. An empty
+/// string is returned if there are no changes.
+pub fn unified_diff(path: &str, old: Option<&str>, new: &str) -> String {
+	let old_lines = old.unwrap_or_default().lines().collect::<Vec<&str>>();
+	let new_lines = new.lines().collect::<Vec<&str>>();
+	let lines = diff_lines(&old_lines, &new_lines);
+	let changes = lines
+		.iter()
+		.enumerate()
+		.filter(|(_, line)| line.marker != ' ')
+		.map(|(i, _)| i)
+		.collect::<Vec<usize>>();
+	if changes.is_empty() && old.is_some() {
+		return String::new();
+	}
+	let mut hunks: Vec<(usize, usize)> = Vec::new();
+	for i in changes {
+		let start = i.saturating_sub(CONTEXT_LINES);
+		let end = (i + CONTEXT_LINES + 1).min(lines.len());
+		match hunks.last_mut() {
+			Some((_, last_end)) if start <= *last_end => *last_end = end,
+			_ => hunks.push((start, end)),
+		}
+	}
+	let path = path.trim_start_matches('/');
+	let mut diff = match old {
+		Some(_) => format!("--- a/{path}\n"),
+		None => String::from("--- /dev/null\n"),
+	};
+	diff += &format!("+++ b/{path}\n");
+	for (start, end) in hunks {
+		let hunk = &lines[start..end];
+		let old_count = hunk.iter().filter(|v| v.marker != '+').count();
+		let new_count = hunk.iter().filter(|v| v.marker != '-').count();
+		diff += &format!(
+			"@@ -{} +{} @@\n",
+			hunk_range(hunk[0].old_line, old_count),
+			hunk_range(hunk[0].new_line, new_count)
+		);
+		for line in hunk {
+			diff += &format!("{}{}\n", line.marker, line.text);
+		}
+	}
+	diff
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn generate_unified_diff() {
+		let old = "# Changelog\n\n## [1.0.0]\n\n- a\n- b\n- c\n- d\n- e\n- f\n- g\n";
+		let new = "# Changelog\n\n## [1.1.0]\n\n- x\n\n## [1.0.0]\n\n- a\n- b\n- \
+		           c\n- d\n- e\n- f\n";
+		assert_eq!(
+			[
+				"--- a/CHANGELOG.md",
+				"+++ b/CHANGELOG.md",
+				"@@ -1,5 +1,9 @@",
+				" # Changelog",
+				" ",
+				"+## [1.1.0]",
+				"+",
+				"+- x",
+				"+",
+				" ## [1.0.0]",
+				" ",
+				" - a",
+				"@@ -8,4 +12,3 @@",
+				" - d",
+				" - e",
+				" - f",
+				"-- g",
+				"",
+			]
+			.join("\n"),
+			unified_diff("CHANGELOG.md", Some(old), new)
+		);
+		assert_eq!(
+			"--- /dev/null\n+++ b/badge.json\n@@ -0,0 +1,1 @@\n+{}\n",
+			unified_diff("badge.json", None, "{}\n")
+		);
+		assert_eq!(
+			"--- a/x\n+++ b/x\n@@ -1,1 +0,0 @@\n-y\n",
+			unified_diff("x", Some("y\n"), "")
+		);
+		assert!(unified_diff("CHANGELOG.md", Some(old), old).is_empty());
+		assert_eq!(
+			"--- a/tmp/x.md\n+++ b/tmp/x.md\n@@ -1,1 +1,1 @@\n-a\n+b\n",
+			unified_diff("/tmp/x.md", Some("a\n"), "b\n")
+		);
+	}
+
+	#[test]
+	fn find_common_lines() {
+		let lcs_length = |a: &[&str], b: &[&str]| {
+			let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+			for i in (0..a.len()).rev() {
+				for j in (0..b.len()).rev() {
+					lengths[i][j] = if a[i] == b[j] {
+						lengths[i + 1][j + 1] + 1
+					} else {
+						lengths[i + 1][j].max(lengths[i][j + 1])
+					};
+				}
+			}
+			lengths[0][0]
+		};
+		let mut seed = 42_u32;
+		let mut lines = |count: usize| {
+			(0..count)
+				.map(|_| {
+					seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+					["a", "b", "c", "d"][(seed >> 16) as usize % 4]
+				})
+				.collect::<Vec<&str>>()
+		};
+		for count in 0..60 {
+			let (old, new) = (lines(count), lines(60 - count));
+			let pairs = common_lines(&old, &new);
+			assert_eq!(lcs_length(&old, &new), pairs.len());
+			assert!(pairs.iter().all(|(i, j)| old[*i] == new[*j]));
+			assert!(pairs.windows(2).all(|v| v[0].0 < v[1].0 && v[0].1 < v[1].1));
+		}
+	}
+}
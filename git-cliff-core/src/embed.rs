@@ -3,9 +3,29 @@ use crate::error::{
 	Error,
 	Result,
 };
+use lazy_regex::Lazy;
 use rust_embed::RustEmbed;
 use std::str;
 
+/// Built-in templates along with their names.
+///
+/// They are extracted once since the templates are parsed along with each
+/// [`Template`](crate::template::Template).
+static BUILTIN_TEMPLATES: Lazy<std::result::Result<Vec<(String, String)>, String>> =
+	Lazy::new(|| {
+		BuiltinTemplates::iter()
+			.filter_map(|name| BuiltinTemplates::get(&name).map(|file| (name, file)))
+			.map(|(name, file)| {
+				Ok((
+					name.trim_end_matches(".tera").to_string(),
+					str::from_utf8(&file.data)
+						.map_err(|e| format!("template {name} is not valid: {e}"))?
+						.to_string(),
+				))
+			})
+			.collect()
+	});
+
 /// Default configuration file embedder/extractor.
 ///
 /// Embeds `config/`[`DEFAULT_CONFIG`] into the binary.
@@ -61,3 +81,22 @@ impl BuiltinConfig {
 		Ok((toml::from_str(&Self::get_config(name.to_string())?)?, name))
 	}
 }
+
+/// Built-in template embedder/extractor.
+///
+/// Embeds the files under `/templates/` into the binary so that they can be
+/// imported in the templates by their name without the extension.
+#[derive(RustEmbed)]
+#[folder = "../templates/"]
+pub struct BuiltinTemplates;
+
+impl BuiltinTemplates {
+	/// Returns the embedded templates along with their names.
+	///
+	/// The templates are extracted on the first call.
+	pub fn get_templates() -> Result<&'static [(String, String)]> {
+		BUILTIN_TEMPLATES
+			.as_deref()
+			.map_err(|e| Error::EmbeddedError(e.to_string()))
+	}
+}
@@ -18,6 +18,9 @@ pub enum Error {
 	#[cfg(feature = "repo")]
 	#[error("Git repository error: `{0}`")]
 	RepoError(String),
+	/// Error that may occur when the required tags are not found.
+	#[error("Tag error: `{0}`")]
+	TagError(String),
 	/// Error that may occur while parsing the config file.
 	#[error("Cannot parse config: `{0}`")]
 	ConfigError(#[from] config::ConfigError),
@@ -40,6 +43,9 @@ pub enum Error {
 	/// Error that may occur while rendering the template.
 	#[error("Template render error:\n{0}")]
 	TemplateRenderError(String),
+	/// Error that may occur when a template exceeds the configured limits.
+	#[error("Template limit exceeded: {0}")]
+	TemplateLimitError(String),
 	/// Error that may occur during more general template operations.
 	#[error("Template error: `{0}`")]
 	TemplateError(#[from] tera::Error),
@@ -68,13 +74,35 @@ pub enum Error {
 	/// value matchers
 	#[error("Field error: `{0}`")]
 	FieldError(String),
+	/// Error that may occur while parsing a commit filter expression.
+	#[error("Filter error: `{0}`")]
+	FilterError(String),
+	/// Error that may occur while parsing a query.
+	#[error("Query error: `{0}`")]
+	QueryError(String),
+	/// Error that may occur when the commits do not pass the lint.
+	#[error("Lint error: `{0}`")]
+	LintError(String),
+	/// Error that may occur when a scope is not in the allowed scopes.
+	#[error("Scope error: `{0}`")]
+	ScopeError(String),
+	/// Error that may occur when issues are found in the tag history.
+	#[error("Audit error: `{0}`")]
+	AuditError(String),
+	/// Error that may occur when the commits are not signed by the allowed
+	/// keys.
+	#[error("Signature error: `{0}`")]
+	SignatureError(String),
+	/// Error that may occur while parsing the external commit metadata.
+	#[error("Metadata error: `{0}`")]
+	MetadataError(String),
 	/// Error that may occur while parsing a SemVer version or version
 	/// requirement.
 	#[error("Semver error: `{0}`")]
 	SemverError(#[from] semver::Error),
 	/// The errors that may occur when processing a HTTP request.
 	#[error("HTTP client error: `{0}`")]
-	#[cfg(feature = "github")]
+	#[cfg(any(feature = "github", feature = "link-check"))]
 	HttpClientError(#[from] reqwest::Error),
 	/// The errors that may occur while constructing the HTTP client with
 	/// middleware.
@@ -92,6 +120,15 @@ pub enum Error {
 	/// The errors that may occur while parsing URLs.
 	#[error("URL parse error: `{0}`")]
 	UrlParseError(#[from] url::ParseError),
+	/// Error that may occur when the remote rejects the credentials.
+	#[error("Remote authentication error: `{0}`")]
+	RemoteAuthError(String),
+	/// Error that may occur when the rate limit of the remote is exceeded.
+	#[error("Remote rate limit error: `{0}`")]
+	RemoteRateLimitError(String),
+	/// Error that may occur when network access is needed in offline mode.
+	#[error("Offline error: `{0}`")]
+	OfflineError(String),
 	/// Error that may occur when a remote is not set.
 	#[error("Repository remote is not set.")]
 	RemoteNotSetError,
@@ -100,6 +137,117 @@ pub enum Error {
 	DirsError(String),
 }
 
+impl Error {
+	/// Returns the stable code of the error.
+	///
+	/// The codes are meant to be used by the tools that wrap git-cliff and
+	/// they are not changed between the releases.
+	pub fn code(&self) -> &'static str {
+		match self {
+			Self::IoError(_) => "io",
+			Self::Utf8Error(_) => "utf8",
+			#[cfg(feature = "repo")]
+			Self::GitError(_) => "git",
+			#[cfg(feature = "repo")]
+			Self::RepoError(_) => "repo",
+			Self::TagError(_) => "tag_not_found",
+			Self::ConfigError(_) => "config",
+			Self::LoggerError(_) => "logger",
+			Self::ParseError(_) => "commit_parse",
+			Self::GroupError(_) => "commit_group",
+			Self::ChangelogError(_) => "changelog",
+			Self::TemplateParseError(_) => "template_parse",
+			Self::TemplateRenderError(_) => "template_render",
+			Self::TemplateLimitError(_) => "template_limit",
+			Self::TemplateError(_) => "template",
+			Self::ArgumentError(_) => "argument",
+			Self::EmbeddedError(_) => "embedded",
+			Self::DeserializeError(_) => "config_toml",
+			Self::JsonError(_) => "json",
+			Self::RegexError(_) => "regex",
+			Self::SystemTimeError(_) => "system_time",
+			Self::IntParseError(_) => "int_parse",
+			Self::FieldError(_) => "commit_field",
+			Self::FilterError(_) => "filter",
+			Self::QueryError(_) => "query",
+			Self::LintError(_) => "lint",
+			Self::ScopeError(_) => "scope_not_allowed",
+			Self::AuditError(_) => "audit",
+			Self::SignatureError(_) => "unsigned_commit",
+			Self::MetadataError(_) => "metadata",
+			Self::SemverError(_) => "semver",
+			#[cfg(any(feature = "github", feature = "link-check"))]
+			Self::HttpClientError(_) => "http_client",
+			#[cfg(feature = "github")]
+			Self::HttpClientMiddlewareError(_) => "http_client",
+			#[cfg(feature = "github")]
+			Self::HttpHeaderError(_) => "http_header",
+			Self::PaginationError(_) => "remote_pagination",
+			Self::UrlParseError(_) => "url_parse",
+			Self::RemoteAuthError(_) => "remote_auth",
+			Self::RemoteRateLimitError(_) => "remote_rate_limit",
+			Self::OfflineError(_) => "offline",
+			Self::RemoteNotSetError => "remote_not_set",
+			Self::DirsError(_) => "dirs",
+		}
+	}
+
+	/// Returns the category of the error.
+	pub fn category(&self) -> &'static str {
+		match self {
+			Self::IoError(_) | Self::Utf8Error(_) | Self::DirsError(_) => "io",
+			#[cfg(feature = "repo")]
+			Self::GitError(_) | Self::RepoError(_) => "repository",
+			Self::TagError(_) | Self::AuditError(_) => "repository",
+			Self::ConfigError(_) |
+			Self::DeserializeError(_) |
+			Self::EmbeddedError(_) |
+			Self::RegexError(_) |
+			Self::FilterError(_) => "config",
+			Self::ParseError(_) |
+			Self::GroupError(_) |
+			Self::FieldError(_) |
+			Self::LintError(_) |
+			Self::ScopeError(_) |
+			Self::SignatureError(_) => "commit",
+			Self::TemplateParseError(_) |
+			Self::TemplateRenderError(_) |
+			Self::TemplateLimitError(_) |
+			Self::TemplateError(_) => "template",
+			Self::ArgumentError(_) |
+			Self::QueryError(_) |
+			Self::MetadataError(_) => "argument",
+			#[cfg(any(feature = "github", feature = "link-check"))]
+			Self::HttpClientError(_) => "remote",
+			#[cfg(feature = "github")]
+			Self::HttpClientMiddlewareError(_) | Self::HttpHeaderError(_) => "remote",
+			Self::PaginationError(_) |
+			Self::UrlParseError(_) |
+			Self::RemoteAuthError(_) |
+			Self::RemoteRateLimitError(_) |
+			Self::OfflineError(_) |
+			Self::RemoteNotSetError => "remote",
+			Self::LoggerError(_) |
+			Self::ChangelogError(_) |
+			Self::JsonError(_) |
+			Self::SystemTimeError(_) |
+			Self::IntParseError(_) |
+			Self::SemverError(_) => "changelog",
+		}
+	}
+
+	/// Returns the error as a JSON object that contains the code, category
+	/// and message of the error.
+	pub fn to_json(&self) -> String {
+		serde_json::json!({
+			"code": self.code(),
+			"category": self.category(),
+			"message": self.to_string(),
+		})
+		.to_string()
+	}
+}
+
 /// Result type of the core library.
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -114,6 +262,15 @@ mod test {
 		Ok(Commit::parse("test")?)
 	}
 
+	#[test]
+	fn error_json() {
+		let error = Error::TagError(String::from("no tags"));
+		assert_eq!(
+			r#"{"category":"repository","code":"tag_not_found","message":"Tag error: `no tags`"}"#,
+			error.to_json()
+		);
+	}
+
 	#[test]
 	fn throw_parse_error() {
 		let actual_error = mock_function().expect_err("expected error");
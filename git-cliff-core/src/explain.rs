@@ -0,0 +1,300 @@
+use crate::commit::{
+	Commit,
+	ProcessStep,
+};
+use crate::config::GitConfig;
+use crate::error::{
+	Error,
+	Result,
+};
+use crate::filter::CommitFilter;
+use std::fmt;
+use std::time::{
+	SystemTime,
+	UNIX_EPOCH,
+};
+
+/// Step of processing a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+	/// Name of the step (e.g. `commit_parsers[0]`).
+	pub name:    String,
+	/// Outcome of the step.
+	pub outcome: String,
+}
+
+impl Step {
+	/// Constructs a new instance.
+	fn new(name: &str, outcome: String) -> Self {
+		Self {
+			name: name.to_string(),
+			outcome,
+		}
+	}
+
+	/// Describes the step of [`Commit::process_with_trace`].
+	fn from_process_step(step: ProcessStep, config: &GitConfig) -> Self {
+		match step {
+			ProcessStep::RawEntry(entry) => Self::new("raw_entries", match entry {
+				Some(entry) => format!("entry is set to {entry:?}"),
+				None => String::from("no raw entry block"),
+			}),
+			ProcessStep::PullRequestMerge { number, message } => Self::new(
+				"pull_request_merges",
+				format!("message of #{number} is {message:?}"),
+			),
+			ProcessStep::Preprocessor {
+				index,
+				matched,
+				message,
+			} => Self::new(
+				&format!("commit_preprocessors[{index}]"),
+				match (matched, message) {
+					(_, Some(message)) => {
+						format!("message is changed to {message:?}")
+					}
+					(true, None) => String::from("matches without any change"),
+					(false, None) => String::from("no match"),
+				},
+			),
+			ProcessStep::Conventional {
+				type_,
+				scope,
+				breaking,
+			} => Self::new(
+				"conventional_commits",
+				format!(
+					"type: {type_}, scope: {}, breaking: {breaking}",
+					scope.as_deref().unwrap_or("-")
+				),
+			),
+			ProcessStep::Unconventional(reason) => {
+				Self::new("conventional_commits", reason)
+			}
+			ProcessStep::ScopeAlias { alias, scope } => Self::new(
+				"scope_aliases",
+				format!("{alias:?} is replaced with {scope:?}"),
+			),
+			ProcessStep::Parser { index, regex } => {
+				Self::new(&format!("commit_parsers[{index}]"), match regex {
+					Some((regex, text)) => format!("`{regex}` matches {text:?}"),
+					None if config
+						.commit_parsers
+						.as_ref()
+						.and_then(|v| v.get(index))
+						.is_some_and(|v| v.sha.is_some()) =>
+					{
+						String::from("SHA matches")
+					}
+					None => String::from("conditions are met"),
+				})
+			}
+			ProcessStep::NoParser => {
+				Self::new("commit_parsers", String::from("no parser matches"))
+			}
+			ProcessStep::TypeGroup(group) => {
+				Self::new("commit_types", format!("group is set to {group:?}"))
+			}
+			ProcessStep::LinkParser { index, links } => Self::new(
+				&format!("link_parsers[{index}]"),
+				format!("{links} link(s) found"),
+			),
+			ProcessStep::CommitDate(timestamp) => {
+				Self::new("commit_date", format!("date is set to {timestamp}"))
+			}
+		}
+	}
+}
+
+/// Explanation of how a commit is processed.
+///
+/// The commit is processed the same way as it would be while generating the
+/// changelog and each step is recorded along the way.
+#[derive(Debug, Clone)]
+pub struct Explanation<'a> {
+	/// Steps of the processing.
+	pub steps:       Vec<Step>,
+	/// Processed commit if it is included in the changelog.
+	pub commit:      Option<Commit<'a>>,
+	/// Reason of skipping the commit.
+	pub skip_reason: Option<String>,
+}
+
+impl<'a> Explanation<'a> {
+	/// Processes the commit with the given configuration and explains it.
+	pub fn new(commit: Commit<'a>, config: &GitConfig) -> Result<Self> {
+		let filter = config
+			.commit_filter
+			.as_deref()
+			.map(CommitFilter::parse)
+			.transpose()?;
+		let embargoes = config.active_embargoes(
+			SystemTime::now()
+				.duration_since(UNIX_EPOCH)?
+				.as_secs()
+				.try_into()?,
+		)?;
+		let mut steps = Vec::new();
+		let processed = commit.process_with_trace(config, |step| {
+			steps.push(Step::from_process_step(step, config))
+		});
+		let (commit, skip_reason) = match processed {
+			Ok(commit) => match filter {
+				Some(filter) if !filter.matches(&commit) => (
+					None,
+					Some(String::from("Commit does not match the commit filter")),
+				),
+				_ => match embargoes
+					.iter()
+					.find(|v| v.matches(&commit.id, &commit.message))
+				{
+					Some(embargo) => (
+						None,
+						Some(format!(
+							"Commit is under embargo until {}",
+							embargo.until.as_deref().unwrap_or("it is lifted")
+						)),
+					),
+					None => (Some(commit), None),
+				},
+			},
+			Err(Error::GroupError(reason)) => (None, Some(reason)),
+			Err(e) => (None, Some(e.to_string())),
+		};
+		Ok(Self {
+			steps,
+			commit,
+			skip_reason,
+		})
+	}
+}
+
+impl fmt::Display for Explanation<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for step in &self.steps {
+			writeln!(f, "{}: {}", step.name, step.outcome)?;
+		}
+		if !self.steps.is_empty() {
+			writeln!(f)?;
+		}
+		match (&self.commit, &self.skip_reason) {
+			(Some(commit), _) => {
+				writeln!(f, "Group: {}", commit.group.as_deref().unwrap_or("-"))?;
+				write!(
+					f,
+					"Scope: {}",
+					commit
+						.scope
+						.as_deref()
+						.or_else(|| {
+							commit
+								.conv
+								.as_ref()
+								.and_then(|v| v.scope())
+								.map(|v| v.as_str())
+						})
+						.or(commit.default_scope.as_deref())
+						.unwrap_or("-")
+				)
+			}
+			(None, reason) => {
+				write!(f, "Skipped: {}", reason.as_deref().unwrap_or_default())
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::config::{
+		CommitDate,
+		CommitParser,
+		TextProcessor,
+	};
+	use pretty_assertions::assert_eq;
+	use regex::Regex;
+
+	fn parser(message: &str, group: Option<&str>, skip: bool) -> CommitParser {
+		CommitParser {
+			message: Regex::new(message).ok(),
+			group: group.map(String::from),
+			skip: Some(skip),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn explain_commit() -> Result<()> {
+		let config = GitConfig {
+			commit_preprocessors: Some(vec![TextProcessor {
+				pattern:         Regex::new("Feature")?,
+				replace:         Some(String::from("feat")),
+				replace_command: None,
+			}]),
+			commit_parsers: Some(vec![
+				parser("^chore", None, true),
+				parser("^feat", Some("Features"), false),
+			]),
+			..Default::default()
+		};
+
+		let explanation = Explanation::new(
+			Commit::new(String::from("123"), String::from("Feature(ui): add x")),
+			&config,
+		)?;
+		assert_eq!(
+			[
+				"commit_preprocessors[0]: message is changed to \"feat(ui): add x\"",
+				"conventional_commits: type: feat, scope: ui, breaking: false",
+				"commit_parsers[1]: `^feat` matches \"feat(ui): add x\"",
+				"",
+				"Group: Features",
+				"Scope: ui",
+			]
+			.join("\n"),
+			explanation.to_string()
+		);
+
+		let explanation = Explanation::new(
+			Commit::new(String::from("456"), String::from("chore: release")),
+			&config,
+		)?;
+		assert!(explanation.commit.is_none());
+		assert_eq!(
+			Some(String::from("Skipping commit")),
+			explanation.skip_reason
+		);
+
+		let explanation = Explanation::new(
+			Commit::new(String::from("789"), String::from("update")),
+			&config,
+		)?;
+		assert_eq!(
+			Step::new("commit_preprocessors[0]", String::from("no match")),
+			explanation.steps[0]
+		);
+		assert!(explanation.commit.is_none());
+
+		let config = GitConfig {
+			commit_date: Some(CommitDate::Author),
+			..config
+		};
+		let mut commit =
+			Commit::new(String::from("abc"), String::from("feat: add z"));
+		commit.author.timestamp = 1700000000;
+		let explanation = Explanation::new(commit, &config)?;
+		assert_eq!(
+			Some(&Step::new(
+				"commit_date",
+				String::from("date is set to 1700000000")
+			)),
+			explanation.steps.last()
+		);
+		assert_eq!(
+			Some(1700000000),
+			explanation.commit.map(|commit| commit.date)
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,326 @@
+use crate::commit::{
+	Commit,
+	Signature,
+};
+use crate::error::{
+	Error,
+	Result,
+};
+use regex::Regex;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Token of a filter expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+	/// Commit field, e.g. `scope`.
+	Field(String),
+	/// String literal, e.g. `"feat"`.
+	Literal(String),
+	/// Comparison operator, e.g. `==`.
+	Operator(Operator),
+	/// `&&`
+	And,
+	/// `||`
+	Or,
+	/// `!`
+	Not,
+	/// `(`
+	OpenParen,
+	/// `)`
+	CloseParen,
+}
+
+/// Comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+	/// `==`
+	Equal,
+	/// `!=`
+	NotEqual,
+	/// `=~`
+	Match,
+	/// `!~`
+	NotMatch,
+}
+
+/// Value that a field is compared with.
+#[derive(Debug, Clone)]
+enum Value {
+	/// Plain text.
+	Text(String),
+	/// Regular expression.
+	Regex(Regex),
+}
+
+/// Node of a filter expression.
+#[derive(Debug, Clone)]
+enum Expr {
+	/// Comparison of a commit field.
+	Compare(String, Operator, Value),
+	/// Logical AND.
+	And(Box<Expr>, Box<Expr>),
+	/// Logical OR.
+	Or(Box<Expr>, Box<Expr>),
+	/// Logical NOT.
+	Not(Box<Expr>),
+}
+
+/// Commit filter expression.
+///
+/// e.g. `type == "feat" && scope != "ci" && author =~ "@corp.com"`
+#[derive(Debug, Clone)]
+pub struct CommitFilter {
+	expr: Expr,
+}
+
+impl CommitFilter {
+	/// Parses the given filter expression.
+	pub fn parse(expression: &str) -> Result<Self> {
+		let tokens = Self::tokenize(expression)?;
+		let mut position = 0;
+		let expr = Self::parse_or(&tokens, &mut position)?;
+		if let Some(token) = tokens.get(position) {
+			return Err(Error::FilterError(format!("unexpected token: {token:?}")));
+		}
+		Ok(Self { expr })
+	}
+
+	/// Returns `true` if the given commit matches the filter.
+	pub fn matches(&self, commit: &Commit) -> bool {
+		Self::evaluate(&self.expr, commit)
+	}
+
+	/// Splits the expression into tokens.
+	fn tokenize(expression: &str) -> Result<Vec<Token>> {
+		let mut tokens = Vec::new();
+		let mut chars = expression.chars().peekable();
+		while let Some(c) = chars.next() {
+			let token = match (c, chars.peek()) {
+				(c, _) if c.is_whitespace() => continue,
+				('=', Some('=')) => Token::Operator(Operator::Equal),
+				('!', Some('=')) => Token::Operator(Operator::NotEqual),
+				('=', Some('~')) => Token::Operator(Operator::Match),
+				('!', Some('~')) => Token::Operator(Operator::NotMatch),
+				('&', Some('&')) => Token::And,
+				('|', Some('|')) => Token::Or,
+				('!', _) => {
+					tokens.push(Token::Not);
+					continue;
+				}
+				('(', _) => {
+					tokens.push(Token::OpenParen);
+					continue;
+				}
+				(')', _) => {
+					tokens.push(Token::CloseParen);
+					continue;
+				}
+				('"', _) => {
+					tokens.push(Token::Literal(Self::read_literal(&mut chars)?));
+					continue;
+				}
+				(c, _) if c.is_alphanumeric() || c == '_' => {
+					let mut field = c.to_string();
+					while let Some(c) = chars
+						.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+					{
+						field.push(c);
+					}
+					tokens.push(Token::Field(field));
+					continue;
+				}
+				(c, _) => {
+					return Err(Error::FilterError(format!(
+						"unexpected character: {c:?}"
+					)));
+				}
+			};
+			chars.next();
+			tokens.push(token);
+		}
+		Ok(tokens)
+	}
+
+	/// Reads a string literal until the closing quote.
+	fn read_literal(chars: &mut Peekable<Chars>) -> Result<String> {
+		let mut literal = String::new();
+		while let Some(c) = chars.next() {
+			match c {
+				'"' => return Ok(literal),
+				'\\' => {
+					if let Some(c) = chars.next() {
+						literal.push(c);
+					}
+				}
+				c => literal.push(c),
+			}
+		}
+		Err(Error::FilterError(String::from("unterminated string")))
+	}
+
+	/// Parses the `||` expressions.
+	fn parse_or(tokens: &[Token], position: &mut usize) -> Result<Expr> {
+		let mut expr = Self::parse_and(tokens, position)?;
+		while tokens.get(*position) == Some(&Token::Or) {
+			*position += 1;
+			let rhs = Self::parse_and(tokens, position)?;
+			expr = Expr::Or(Box::new(expr), Box::new(rhs));
+		}
+		Ok(expr)
+	}
+
+	/// Parses the `&&` expressions.
+	fn parse_and(tokens: &[Token], position: &mut usize) -> Result<Expr> {
+		let mut expr = Self::parse_unary(tokens, position)?;
+		while tokens.get(*position) == Some(&Token::And) {
+			*position += 1;
+			let rhs = Self::parse_unary(tokens, position)?;
+			expr = Expr::And(Box::new(expr), Box::new(rhs));
+		}
+		Ok(expr)
+	}
+
+	/// Parses the negations, parentheses and comparisons.
+	fn parse_unary(tokens: &[Token], position: &mut usize) -> Result<Expr> {
+		let token = tokens.get(*position).cloned();
+		*position += 1;
+		match token {
+			Some(Token::Not) => {
+				Ok(Expr::Not(Box::new(Self::parse_unary(tokens, position)?)))
+			}
+			Some(Token::OpenParen) => {
+				let expr = Self::parse_or(tokens, position)?;
+				if tokens.get(*position) != Some(&Token::CloseParen) {
+					return Err(Error::FilterError(String::from("expected `)`")));
+				}
+				*position += 1;
+				Ok(expr)
+			}
+			Some(Token::Field(field)) => {
+				let (Some(Token::Operator(operator)), Some(value)) =
+					(tokens.get(*position), tokens.get(*position + 1))
+				else {
+					return Err(Error::FilterError(format!(
+						"expected a comparison after `{field}`"
+					)));
+				};
+				let value = match value {
+					Token::Literal(v) | Token::Field(v) => v,
+					_ => {
+						return Err(Error::FilterError(format!(
+							"expected a value after `{field}`"
+						)));
+					}
+				};
+				*position += 2;
+				let value = match operator {
+					Operator::Match | Operator::NotMatch => {
+						Value::Regex(Regex::new(value)?)
+					}
+					Operator::Equal | Operator::NotEqual => {
+						Value::Text(value.to_string())
+					}
+				};
+				Ok(Expr::Compare(field, *operator, value))
+			}
+			token => Err(Error::FilterError(format!("unexpected token: {token:?}"))),
+		}
+	}
+
+	/// Evaluates the expression for the given commit.
+	fn evaluate(expr: &Expr, commit: &Commit) -> bool {
+		match expr {
+			Expr::Compare(field, operator, value) => {
+				let field = Self::field_value(field, commit);
+				let matched = match (field, value) {
+					(Some(field), Value::Text(v)) => &field == v,
+					(Some(field), Value::Regex(v)) => v.is_match(&field),
+					(None, _) => false,
+				};
+				match operator {
+					Operator::Equal | Operator::Match => matched,
+					Operator::NotEqual | Operator::NotMatch => !matched,
+				}
+			}
+			Expr::And(lhs, rhs) => {
+				Self::evaluate(lhs, commit) && Self::evaluate(rhs, commit)
+			}
+			Expr::Or(lhs, rhs) => {
+				Self::evaluate(lhs, commit) || Self::evaluate(rhs, commit)
+			}
+			Expr::Not(expr) => !Self::evaluate(expr, commit),
+		}
+	}
+
+	/// Returns the value of the given field of the commit.
+	fn field_value(field: &str, commit: &Commit) -> Option<String> {
+		let signature =
+			|signature: &Signature| match (&signature.name, &signature.email) {
+				(Some(name), Some(email)) => Some(format!("{name} <{email}>")),
+				(name, email) => name.clone().or(email.clone()),
+			};
+		match field {
+			"id" => Some(commit.id.clone()),
+			"message" => Some(commit.message.clone()),
+			"type" => commit.conv.as_ref().map(|v| v.type_().to_string()),
+			"scope" => commit.scope_name(),
+			"group" => commit.group_name(),
+			"breaking" => Some(commit.is_breaking().to_string()),
+			"author" => signature(&commit.author),
+			"author.name" => commit.author.name.clone(),
+			"author.email" => commit.author.email.clone(),
+			"committer" => signature(&commit.committer),
+			"committer.name" => commit.committer.name.clone(),
+			"committer.email" => commit.committer.email.clone(),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn filter_commits() -> Result<()> {
+		let mut commit =
+			Commit::from(String::from("feat(app)!: add xyz")).into_conventional()?;
+		commit.author = Signature {
+			name:      Some(String::from("John Doe")),
+			email:     Some(String::from("john@corp.com")),
+			timestamp: 0,
+		};
+		for (expression, expected) in [
+			(r#"type == "feat""#, true),
+			(r#"type == "feat" && scope != "ci""#, true),
+			(r#"type == "feat" && scope != "app""#, false),
+			(r#"author.email =~ "@corp\\.com$""#, true),
+			(r#"author =~ "@corp""#, true),
+			(r#"author.name !~ "^John""#, false),
+			(r#"!(type == "fix" || breaking == true)"#, false),
+			(
+				r#"type == "fix" || (breaking == "true" && scope == app)"#,
+				true,
+			),
+			(r#"unknown == "x""#, false),
+		] {
+			assert_eq!(
+				expected,
+				CommitFilter::parse(expression)?.matches(&commit),
+				"{expression}"
+			);
+		}
+		for expression in [
+			r#"type =="#,
+			r#"type == "feat" &&"#,
+			r#"(type == "feat""#,
+			r#"type == "feat"#,
+			r#"type"#,
+			r#"type == "feat" type"#,
+		] {
+			assert!(CommitFilter::parse(expression).is_err(), "{expression}");
+		}
+		Ok(())
+	}
+}
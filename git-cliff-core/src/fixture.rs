@@ -0,0 +1,289 @@
+use crate::commit::{
+	Commit,
+	Signature,
+};
+#[cfg(feature = "github")]
+use crate::github::GitHubContributor;
+use crate::release::Release;
+use semver::Version;
+
+/// Commit types (repeated for weighting the common ones).
+const TYPES: &[&str] = &[
+	"feat", "feat", "feat", "feat", "fix", "fix", "fix", "fix", "docs", "docs",
+	"refactor", "refactor", "perf", "test", "chore", "chore", "ci", "style",
+	"build", "revert",
+];
+
+/// Commit scopes.
+const SCOPES: &[&str] = &[
+	"api", "cli", "config", "core", "deps", "parser", "template", "ui",
+];
+
+/// Verbs of the commit subjects.
+const VERBS: &[&str] = &[
+	"add", "support", "handle", "remove", "update", "improve", "simplify",
+];
+
+/// Objects of the commit subjects.
+const OBJECTS: &[&str] = &[
+	"the config parser",
+	"pagination of the requests",
+	"custom templates",
+	"remote metadata",
+	"error messages",
+	"the changelog header",
+	"commit links",
+	"release dates",
+	"nested groups",
+	"empty scopes",
+];
+
+/// Authors as name, email and remote username.
+const AUTHORS: &[(&str, &str, &str)] = &[
+	("Jane Doe", "jane@example.com", "janedoe"),
+	("John Smith", "john@example.com", "jsmith"),
+	("Alex Kim", "alex@example.org", "alexkim"),
+	("Sam Taylor", "sam@example.org", "staylor"),
+	("renovate[bot]", "bot@renovateapp.com", "renovate[bot]"),
+];
+
+/// Pull request labels.
+#[cfg(feature = "github")]
+const LABELS: &[&str] = &["bug", "enhancement", "documentation", "dependencies"];
+
+/// Pseudo-random number generator (xorshift64*).
+///
+/// The fixtures only need to look realistic and be reproducible with the same
+/// seed, so a small generator is enough.
+struct Random(u64);
+
+impl Random {
+	/// Returns the next random number.
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 >> 12;
+		self.0 ^= self.0 << 25;
+		self.0 ^= self.0 >> 27;
+		self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+	}
+
+	/// Returns a random number in `0..max`.
+	fn below(&mut self, max: u64) -> u64 {
+		self.next() % max.max(1)
+	}
+
+	/// Returns `true` with the given percentage of chance.
+	fn chance(&mut self, percentage: u64) -> bool {
+		self.below(100) < percentage
+	}
+
+	/// Returns a random element of the slice.
+	fn pick<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+		&values[self.below(values.len() as u64) as usize]
+	}
+}
+
+/// Generator of randomized changelog contexts.
+///
+/// This is useful for developing the templates without a real repository.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fixture {
+	/// Number of releases.
+	pub releases:   usize,
+	/// Total number of commits.
+	pub commits:    usize,
+	/// Whether if the latest release is unreleased.
+	pub unreleased: bool,
+	/// Seed of the random values.
+	pub seed:       u64,
+}
+
+impl Fixture {
+	/// Generates the releases from the oldest to the newest.
+	///
+	/// The commits are not processed so that they can be processed with the
+	/// configuration, e.g. via [`Changelog::new`].
+	///
+	/// [`Changelog::new`]: crate::changelog::Changelog::new
+	pub fn generate<'a>(&self) -> Vec<Release<'a>> {
+		let mut random = Random(self.seed | 1);
+		let mut timestamp = 1_600_000_000 + random.below(31_536_000) as i64;
+		let mut version = Version::new(0, 1, 0);
+		#[cfg(feature = "github")]
+		let mut contributors = Vec::new();
+		let mut releases: Vec<Release> = Vec::new();
+		let count = self.releases.max(1);
+		for index in 0..count {
+			let mut release = Release::default();
+			let mut bump = 2;
+			let commits =
+				self.commits / count + usize::from(index < self.commits % count);
+			for _ in 0..commits {
+				timestamp += 3600 + random.below(259_200) as i64;
+				let (commit, level) = Self::commit(&mut random, timestamp);
+				bump = bump.min(level);
+				#[cfg(feature = "github")]
+				let commit = {
+					let mut commit = commit;
+					commit.github =
+						Self::contributor(&mut random, &commit, &mut contributors);
+					if !release
+						.github
+						.contributors
+						.iter()
+						.any(|v| v.username == commit.github.username)
+					{
+						release.github.contributors.push(commit.github.clone());
+					}
+					commit
+				};
+				release.commits.push(commit);
+			}
+			if !(self.unreleased && index == count - 1) {
+				if !releases.is_empty() {
+					version = match (bump, version.major) {
+						(0, major) if major > 0 => Version::new(major + 1, 0, 0),
+						(0 | 1, major) => Version::new(major, version.minor + 1, 0),
+						_ => Version::new(
+							version.major,
+							version.minor,
+							version.patch + 1,
+						),
+					};
+				}
+				release.version = Some(format!("v{version}"));
+				release.commit_id =
+					release.commits.last().map(|commit| commit.id.clone());
+			}
+			release.timestamp = if release.version.is_some() {
+				timestamp
+			} else {
+				0
+			};
+			if let Some(previous) = releases.last() {
+				let mut previous = previous.clone();
+				previous.previous = None;
+				release.previous = Some(Box::new(previous));
+			}
+			releases.push(release);
+		}
+		releases
+	}
+
+	/// Generates a commit and returns it with its semantic version level.
+	///
+	/// The level is `0` for breaking changes, `1` for features and `2` for the
+	/// others.
+	fn commit<'a>(random: &mut Random, timestamp: i64) -> (Commit<'a>, u8) {
+		let type_ = *random.pick(TYPES);
+		let breaking =
+			matches!(type_, "feat" | "fix" | "refactor") && random.chance(10);
+		let mut message = match (random.chance(70), breaking && random.chance(50)) {
+			(true, bang) => format!(
+				"{type_}({}){}: ",
+				random.pick(SCOPES),
+				if bang { "!" } else { "" }
+			),
+			(false, bang) => format!("{type_}{}: ", if bang { "!" } else { "" }),
+		};
+		message += &format!("{} {}", random.pick(VERBS), random.pick(OBJECTS));
+		if random.chance(30) {
+			message +=
+				&format!("\n\nThis changes how {} works.", random.pick(OBJECTS));
+		}
+		if random.chance(20) {
+			message += &format!("\n\nCloses #{}", 1 + random.below(500));
+		}
+		if breaking && !message.contains('!') {
+			message +=
+				&format!("\n\nBREAKING CHANGE: {} is changed", random.pick(OBJECTS));
+		}
+		let (name, email, _) = random.pick(AUTHORS);
+		let signature = Signature {
+			name: Some(name.to_string()),
+			email: Some(email.to_string()),
+			timestamp,
+		};
+		let commit = Commit {
+			id: (0..5)
+				.map(|_| format!("{:08x}", random.next() as u32))
+				.collect(),
+			message,
+			author: signature.clone(),
+			committer: signature,
+			date: timestamp,
+			..Default::default()
+		};
+		let level = match (breaking, type_) {
+			(true, _) => 0,
+			(false, "feat") => 1,
+			_ => 2,
+		};
+		(commit, level)
+	}
+
+	/// Generates the remote metadata of the commit.
+	#[cfg(feature = "github")]
+	fn contributor(
+		random: &mut Random,
+		commit: &Commit,
+		contributors: &mut Vec<String>,
+	) -> GitHubContributor {
+		let username = AUTHORS
+			.iter()
+			.find(|(name, ..)| Some(name.to_string()) == commit.author.name)
+			.map(|(.., username)| username.to_string());
+		let is_first_time =
+			username.as_ref().is_some_and(|v| !contributors.contains(v));
+		contributors.extend(username.clone().filter(|_| is_first_time));
+		let pull_request = random.chance(60);
+		GitHubContributor {
+			username,
+			pr_title: pull_request
+				.then(|| commit.message.lines().next().map(String::from))
+				.flatten(),
+			pr_number: pull_request.then(|| 1 + random.below(500) as i64),
+			pr_labels: if pull_request && random.chance(50) {
+				vec![random.pick(LABELS).to_string()]
+			} else {
+				vec![]
+			},
+			pr_release_note: None,
+			pr_commits: vec![],
+			is_first_time,
+			pr_merged_at: pull_request.then_some(commit.committer.timestamp),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn generate_fixture() {
+		let fixture = Fixture {
+			releases:   3,
+			commits:    20,
+			unreleased: true,
+			seed:       42,
+		};
+		let releases = fixture.generate();
+		assert_eq!(3, releases.len());
+		assert_eq!(20, releases.iter().map(|v| v.commits.len()).sum::<usize>());
+		assert_eq!(Some("v0.1.0"), releases[0].version.as_deref());
+		assert!(releases[1].version.is_some());
+		assert!(releases[2].version.is_none());
+		assert_eq!(
+			releases[1].version,
+			releases[2]
+				.previous
+				.as_ref()
+				.and_then(|v| v.version.clone())
+		);
+		assert!(releases
+			.iter()
+			.flat_map(|v| v.commits.iter())
+			.all(|v| v.id.len() == 40 && v.clone().into_conventional().is_ok()));
+		assert_eq!(releases, fixture.generate());
+	}
+}
@@ -1,7 +1,9 @@
-use crate::config::Remote;
+use crate::config::{
+	civil_timestamp,
+	Remote,
+};
 use crate::error::*;
 use futures::{
-	future,
 	stream,
 	StreamExt,
 };
@@ -12,11 +14,20 @@ use http_cache_reqwest::{
 	HttpCache,
 	HttpCacheOptions,
 };
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
 use reqwest::header::{
 	HeaderMap,
 	HeaderValue,
 };
-use reqwest::Client;
+use reqwest::{
+	Client,
+	Response,
+	StatusCode,
+};
 use reqwest_middleware::{
 	ClientBuilder,
 	ClientWithMiddleware,
@@ -27,10 +38,17 @@ use serde::{
 	Deserialize,
 	Serialize,
 };
+use std::collections::HashMap;
 use std::hash::{
 	Hash,
 	Hasher,
 };
+use std::sync::atomic::{
+	AtomicBool,
+	AtomicUsize,
+	Ordering,
+};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// GitHub REST API url.
@@ -56,6 +74,15 @@ pub const START_FETCHING_MSG: &str = "Retrieving data from GitHub...";
 /// Log message to show when done fetching from GitHub.
 pub const FINISHED_FETCHING_MSG: &str = "Done fetching GitHub data.";
 
+/// Regex for matching the release note blocks in the pull request
+/// descriptions.
+static RELEASE_NOTE_REGEX: Lazy<Regex> =
+	lazy_regex!(r"(?s)```release-note[^\S\n]*\r?\n(.*?)```");
+
+/// Regex for matching the timestamps of the API (e.g. `2024-05-06T12:30:00Z`).
+static TIMESTAMP_REGEX: Lazy<Regex> =
+	lazy_regex!(r"^(\d{4})-(\d{2})-(\d{2})T(\d{2}):(\d{2}):(\d{2})Z$");
+
 /// Trait for handling the different entries returned from the GitHub API.
 trait GitHubEntry {
 	/// Returns the API URL for fetching the entries at the specified page.
@@ -64,6 +91,11 @@ trait GitHubEntry {
 	fn buffer_size() -> usize;
 }
 
+/// Regex for matching the GitHub noreply emails (e.g.
+/// `12345+user@users.noreply.github.com`).
+static NOREPLY_EMAIL_REGEX: Lazy<Regex> =
+	lazy_regex!(r"^(?:\d+\+)?([^@+]+)@users\.noreply\.github\.com$");
+
 /// Representation of a single commit.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GitHubCommit {
@@ -71,6 +103,18 @@ pub struct GitHubCommit {
 	pub sha:    String,
 	/// Author of the commit.
 	pub author: Option<GitHubCommitAuthor>,
+	/// Details of the commit.
+	pub commit: Option<GitHubCommitDetails>,
+}
+
+impl GitHubCommit {
+	/// Returns the email of the commit author.
+	pub fn author_email(&self) -> Option<&str> {
+		self.commit
+			.as_ref()
+			.and_then(|v| v.author.as_ref())
+			.and_then(|v| v.email.as_deref())
+	}
 }
 
 impl GitHubEntry for GitHubCommit {
@@ -93,6 +137,29 @@ pub struct GitHubCommitAuthor {
 	pub login: Option<String>,
 }
 
+/// Details of the commit.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubCommitDetails {
+	/// Git author of the commit.
+	pub author: Option<GitHubCommitSignature>,
+}
+
+/// Git signature of the commit.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubCommitSignature {
+	/// Name of the author.
+	pub name:  Option<String>,
+	/// Email of the author.
+	pub email: Option<String>,
+}
+
+/// Result of searching the users.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct GitHubUserSearch {
+	/// Users that are found.
+	items: Vec<GitHubCommitAuthor>,
+}
+
 /// Label of the pull request.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -112,6 +179,42 @@ pub struct GitHubPullRequest {
 	pub merge_commit_sha: Option<String>,
 	/// Labels of the pull request.
 	pub labels:           Vec<PullRequestLabel>,
+	/// Description of the pull request.
+	pub body:             Option<String>,
+	/// Author of the pull request.
+	pub user:             Option<GitHubCommitAuthor>,
+	/// Time when the pull request is merged.
+	pub merged_at:        Option<String>,
+}
+
+impl GitHubPullRequest {
+	/// Returns the release note of the pull request.
+	///
+	/// The release note is the content of the fenced `release-note` blocks in
+	/// the description. Blocks that only contain `NONE` are ignored.
+	pub fn release_note(&self) -> Option<String> {
+		let notes = RELEASE_NOTE_REGEX
+			.captures_iter(self.body.as_deref()?)
+			.filter_map(|v| {
+				let note = v.get(1)?.as_str().trim();
+				(!note.is_empty() && !note.eq_ignore_ascii_case("none"))
+					.then(|| note.to_string())
+			})
+			.collect::<Vec<String>>();
+		(!notes.is_empty()).then(|| notes.join("\n"))
+	}
+
+	/// Returns the time when the pull request is merged (Unix timestamp).
+	pub fn merge_timestamp(&self) -> Option<i64> {
+		let captures = TIMESTAMP_REGEX.captures(self.merged_at.as_deref()?)?;
+		let value = |i: usize| captures[i].parse::<i64>().ok();
+		Some(
+			civil_timestamp(value(1)?, value(2)?, value(3)?) +
+				value(4)? * 3600 +
+				value(5)? * 60 +
+				value(6)?,
+		)
+	}
 }
 
 impl GitHubEntry for GitHubPullRequest {
@@ -128,26 +231,95 @@ impl GitHubEntry for GitHubPullRequest {
 	}
 }
 
+/// Representation of a single milestone.
+#[derive(Default, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GitHubMilestone {
+	/// Milestone number.
+	pub number:   i64,
+	/// Title of the milestone.
+	pub title:    String,
+	/// State of the milestone (`open` or `closed`).
+	pub state:    String,
+	/// URL of the milestone page.
+	pub html_url: Option<String>,
+}
+
+impl GitHubMilestone {
+	/// Returns `true` if the title of the milestone is the given version.
+	///
+	/// The `v` prefixes are ignored (e.g. `v1.0.0` matches `1.0.0`).
+	pub fn matches_version(&self, version: &str) -> bool {
+		let normalize = |v: &str| v.trim().trim_start_matches('v').to_string();
+		normalize(&self.title) == normalize(version)
+	}
+
+	/// Returns `true` if the milestone is open.
+	pub fn is_open(&self) -> bool {
+		self.state == "open"
+	}
+}
+
+impl GitHubEntry for GitHubMilestone {
+	fn url(owner: &str, repo: &str, page: i32) -> String {
+		format!(
+			"{GITHUB_API_URL}/repos/{}/{}/milestones?per_page={MAX_PAGE_SIZE}&\
+			 page={page}&state=all",
+			owner, repo
+		)
+	}
+
+	fn buffer_size() -> usize {
+		5
+	}
+}
+
+/// Representation of a repository.
+#[derive(Default, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GitHubRepository {
+	/// Full name of the repository (e.g. `orhun/git-cliff`).
+	pub full_name:      String,
+	/// Description of the repository.
+	pub description:    Option<String>,
+	/// Topics of the repository.
+	#[serde(default)]
+	pub topics:         Vec<String>,
+	/// Default branch of the repository.
+	pub default_branch: Option<String>,
+	/// URL of the repository page.
+	pub html_url:       Option<String>,
+}
+
 /// Metadata of a GitHub release.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct GitHubReleaseMetadata {
 	/// Contributors.
 	pub contributors: Vec<GitHubContributor>,
+	/// Milestone whose title matches the version of the release.
+	pub milestone:    Option<GitHubMilestone>,
 }
 
 /// Representation of a GitHub contributor.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct GitHubContributor {
 	/// Username.
-	pub username:      Option<String>,
+	pub username:        Option<String>,
 	/// Title of the pull request.
-	pub pr_title:      Option<String>,
+	pub pr_title:        Option<String>,
 	/// The pull request that the user created.
-	pub pr_number:     Option<i64>,
+	pub pr_number:       Option<i64>,
 	/// Labels of the pull request.
-	pub pr_labels:     Vec<String>,
+	pub pr_labels:       Vec<String>,
+	/// Release note from the description of the pull request.
+	pub pr_release_note: Option<String>,
+	/// SHA1 of the commits of the pull request that are merged into a single
+	/// entry (see [`RemoteConfig::merge_pr_commits`]).
+	///
+	/// [`RemoteConfig::merge_pr_commits`]: crate::config::RemoteConfig::merge_pr_commits
+	pub pr_commits:      Vec<String>,
 	/// Whether if the user contributed for the first time.
-	pub is_first_time: bool,
+	pub is_first_time:   bool,
+	/// Time when the pull request is merged (Unix timestamp).
+	pub pr_merged_at:    Option<i64>,
 }
 
 impl Hash for GitHubContributor {
@@ -160,11 +332,15 @@ impl Hash for GitHubContributor {
 #[derive(Debug, Clone)]
 pub struct GitHubClient {
 	/// Owner of the repository.
-	owner:  String,
+	owner:    String,
 	/// GitHub repository.
-	repo:   String,
+	repo:     String,
 	/// HTTP client.
-	client: ClientWithMiddleware,
+	client:   ClientWithMiddleware,
+	/// Whether the entries are not fetched completely due to an error.
+	degraded: Arc<AtomicBool>,
+	/// Number of the requests that are sent.
+	requests: Arc<AtomicUsize>,
 }
 
 /// Constructs a GitHub client from the remote configuration.
@@ -210,6 +386,8 @@ impl TryFrom<Remote> for GitHubClient {
 			owner: remote.owner,
 			repo: remote.repo,
 			client,
+			degraded: Arc::new(AtomicBool::new(false)),
+			requests: Arc::new(AtomicUsize::new(0)),
 		})
 	}
 }
@@ -222,7 +400,8 @@ impl GitHubClient {
 	) -> Result<Vec<T>> {
 		let url = T::url(&self.owner, &self.repo, page);
 		debug!("Sending request to: {url}");
-		let response = self.client.get(&url).send().await?;
+		self.requests.fetch_add(1, Ordering::Relaxed);
+		let response = check_response(self.client.get(&url).send().await?).await?;
 		let response_text = if response.status().is_success() {
 			let text = response.text().await?;
 			trace!("Response: {:?}", text);
@@ -242,25 +421,43 @@ impl GitHubClient {
 
 	/// Fetches the GitHub API returns the given entry.
 	async fn fetch<T: DeserializeOwned + GitHubEntry>(&self) -> Result<Vec<T>> {
-		let entries: Vec<Vec<T>> = stream::iter(1..)
+		let mut pages = stream::iter(1..)
 			.map(|i| self.get_entries_with_page(i))
-			.buffered(T::buffer_size())
-			.take_while(|page| {
-				if let Err(e) = page {
-					debug!("Error while fetching page: {:?}", e);
-				}
-				future::ready(page.is_ok())
-			})
-			.map(|page| match page {
-				Ok(v) => v,
-				Err(ref e) => {
-					log::error!("{:#?}", e);
-					page.expect("failed to fetch page: {}")
+			.buffered(T::buffer_size());
+		let mut entries = Vec::new();
+		while let Some(page) = pages.next().await {
+			match page {
+				Ok(v) => entries.extend(v),
+				Err(e @ Error::RemoteAuthError(_)) => return Err(e),
+				Err(Error::PaginationError(_)) => break,
+				Err(e) => {
+					warn!("Failed to fetch the GitHub entries: {e}");
+					self.degraded.store(true, Ordering::Relaxed);
+					break;
 				}
-			})
-			.collect()
-			.await;
-		Ok(entries.into_iter().flatten().collect())
+			}
+		}
+		Ok(entries)
+	}
+
+	/// Returns `true` if the entries are not fetched completely due to an
+	/// error.
+	pub fn is_degraded(&self) -> bool {
+		self.degraded.load(Ordering::Relaxed)
+	}
+
+	/// Counts the requests with the given counter (e.g. for sharing it
+	/// between the clients).
+	pub fn with_request_counter(mut self, requests: Arc<AtomicUsize>) -> Self {
+		self.requests = requests;
+		self
+	}
+
+	/// Returns the number of the requests that are sent so far.
+	///
+	/// The requests that are answered from the HTTP cache are also counted.
+	pub fn request_count(&self) -> usize {
+		self.requests.load(Ordering::Relaxed)
 	}
 
 	/// Fetches the GitHub API and returns the commits.
@@ -272,4 +469,300 @@ impl GitHubClient {
 	pub async fn get_pull_requests(&self) -> Result<Vec<GitHubPullRequest>> {
 		self.fetch::<GitHubPullRequest>().await
 	}
+
+	/// Fetches the GitHub API and returns the milestones.
+	pub async fn get_milestones(&self) -> Result<Vec<GitHubMilestone>> {
+		self.fetch::<GitHubMilestone>().await
+	}
+
+	/// Fetches the GitHub API and returns the repository.
+	///
+	/// Returns `None` if the repository cannot be fetched (e.g. it is not
+	/// found or the rate limit is exceeded).
+	pub async fn get_repository(&self) -> Result<Option<GitHubRepository>> {
+		let url = format!("{GITHUB_API_URL}/repos/{}/{}", self.owner, self.repo);
+		debug!("Sending request to: {url}");
+		self.requests.fetch_add(1, Ordering::Relaxed);
+		let response = check_response(self.client.get(&url).send().await?)
+			.await
+			.and_then(|v| Ok(v.error_for_status()?));
+		match response {
+			Ok(response) => Ok(Some(serde_json::from_str(&response.text().await?)?)),
+			Err(e @ Error::RemoteAuthError(_)) => Err(e),
+			Err(e) => {
+				warn!("Failed to fetch the GitHub repository: {e}");
+				self.degraded.store(true, Ordering::Relaxed);
+				Ok(None)
+			}
+		}
+	}
+
+	/// Closes the milestone with the given number and returns the updated
+	/// milestone.
+	pub async fn close_milestone(&self, number: i64) -> Result<GitHubMilestone> {
+		let url = format!(
+			"{GITHUB_API_URL}/repos/{}/{}/milestones/{number}",
+			self.owner, self.repo
+		);
+		debug!("Closing the milestone: {url}");
+		self.requests.fetch_add(1, Ordering::Relaxed);
+		let response = self
+			.client
+			.patch(&url)
+			.json(&serde_json::json!({ "state": "closed" }))
+			.send()
+			.await?;
+		let response = check_response(response).await?;
+		let text = response.error_for_status()?.text().await?;
+		Ok(serde_json::from_str::<GitHubMilestone>(&text)?)
+	}
+
+	/// Searches the users by the given email and returns the username.
+	async fn search_user(&self, email: &str) -> Result<Option<String>> {
+		let url = format!("{GITHUB_API_URL}/search/users");
+		debug!("Searching the user: {email}");
+		self.requests.fetch_add(1, Ordering::Relaxed);
+		let response = self
+			.client
+			.get(&url)
+			.query(&[("q", format!("{email} in:email"))])
+			.send()
+			.await?;
+		let response = check_response(response).await?;
+		let search =
+			serde_json::from_str::<GitHubUserSearch>(&response.text().await?)?;
+		Ok(search.items.into_iter().find_map(|v| v.login))
+	}
+
+	/// Resolves the usernames of the commit authors whose emails are not
+	/// linked to a GitHub account.
+	///
+	/// See [`resolve_username`] for the fallbacks. The remaining authors are
+	/// searched by their emails if `search` is `true`. Each email is searched
+	/// once and the searching stops at the first failure (e.g. rate limit).
+	pub async fn resolve_usernames(
+		&self,
+		commits: &mut [GitHubCommit],
+		pull_requests: &[GitHubPullRequest],
+		search: bool,
+	) {
+		let mut cache = HashMap::<String, Option<String>>::new();
+		let mut search = search;
+		for commit in commits.iter_mut() {
+			if commit.author.as_ref().is_some_and(|v| v.login.is_some()) {
+				continue;
+			}
+			let mut username = resolve_username(commit, pull_requests);
+			if let (None, Some(email), true) =
+				(&username, commit.author_email(), search)
+			{
+				username = match cache.get(email) {
+					Some(username) => username.clone(),
+					None => match self.search_user(email).await {
+						Ok(username) => {
+							cache.insert(email.to_string(), username.clone());
+							username
+						}
+						Err(e) => {
+							warn!("Failed to search the GitHub users: {e}");
+							search = false;
+							None
+						}
+					},
+				};
+			}
+			if username.is_some() {
+				commit.author = Some(GitHubCommitAuthor { login: username });
+			}
+		}
+	}
+}
+
+/// Returns `true` if the response is due to exceeding the rate limit.
+///
+/// GitHub responds with `403` (or `429`) when the primary or the secondary
+/// rate limit is exceeded, along with the `x-ratelimit-remaining: 0` or the
+/// `retry-after` header.
+fn is_rate_limited(status: StatusCode, headers: &HeaderMap) -> bool {
+	status == StatusCode::TOO_MANY_REQUESTS ||
+		(status == StatusCode::FORBIDDEN &&
+			(headers
+				.get("x-ratelimit-remaining")
+				.is_some_and(|v| v.as_bytes() == b"0") ||
+				headers.contains_key("retry-after")))
+}
+
+/// Returns the response or an error if the remote rejects the request.
+///
+/// The rate limited requests are returned as
+/// [`Error::RemoteRateLimitError`] so that they are handled as a temporary
+/// failure (i.e. the remote data is fetched partially) rather than invalid
+/// credentials.
+async fn check_response(response: Response) -> Result<Response> {
+	if is_rate_limited(response.status(), response.headers()) {
+		let reset = response
+			.headers()
+			.get("x-ratelimit-reset")
+			.and_then(|v| v.to_str().ok())
+			.map(|v| format!(" (resets at {v})"))
+			.unwrap_or_default();
+		return Err(Error::RemoteRateLimitError(format!(
+			"{}{reset}",
+			response.text().await?
+		)));
+	}
+	if matches!(
+		response.status(),
+		StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+	) {
+		return Err(Error::RemoteAuthError(response.text().await?));
+	}
+	Ok(response)
+}
+
+/// Returns the username of the commit author without using the API.
+///
+/// The username is resolved from the first of:
+///
+/// - the GitHub noreply email of the author
+/// - the author of the pull request that the commit is merged with
+pub fn resolve_username(
+	commit: &GitHubCommit,
+	pull_requests: &[GitHubPullRequest],
+) -> Option<String> {
+	commit
+		.author_email()
+		.and_then(|email| NOREPLY_EMAIL_REGEX.captures(email))
+		.map(|captures| captures[1].to_string())
+		.or_else(|| {
+			pull_requests
+				.iter()
+				.find(|pr| pr.merge_commit_sha.as_ref() == Some(&commit.sha))
+				.and_then(|pr| pr.user.as_ref())
+				.and_then(|user| user.login.clone())
+		})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn resolve_commit_username() -> Result<()> {
+		let commits = serde_json::from_str::<Vec<GitHubCommit>>(
+			r#"[
+			{
+				"sha": "1a2b3c",
+				"author": null,
+				"commit": { "author": { "name": "A", "email": "12345+orhun@users.noreply.github.com" } }
+			},
+			{
+				"sha": "4d5e6f",
+				"author": null,
+				"commit": { "author": { "name": "B", "email": "someone@example.com" } }
+			},
+			{
+				"sha": "7a8b9c",
+				"author": null,
+				"commit": { "author": { "name": "C", "email": "nobody@example.com" } }
+			}
+		]"#,
+		)?;
+		let pull_requests = vec![GitHubPullRequest {
+			number: 42,
+			merge_commit_sha: Some(String::from("4d5e6f")),
+			user: Some(GitHubCommitAuthor {
+				login: Some(String::from("someone")),
+			}),
+			..Default::default()
+		}];
+		assert_eq!(
+			vec![
+				Some(String::from("orhun")),
+				Some(String::from("someone")),
+				None
+			],
+			commits
+				.iter()
+				.map(|commit| resolve_username(commit, &pull_requests))
+				.collect::<Vec<Option<String>>>()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn rate_limited_response() {
+		let headers = |values: &[(&'static str, &'static str)]| {
+			values
+				.iter()
+				.map(|(name, value)| (*name, HeaderValue::from_static(value)))
+				.fold(HeaderMap::new(), |mut headers, (name, value)| {
+					headers.insert(name, value);
+					headers
+				})
+		};
+		for (status, values, expected) in [
+			(
+				StatusCode::FORBIDDEN,
+				vec![("x-ratelimit-remaining", "0")],
+				true,
+			),
+			(StatusCode::FORBIDDEN, vec![("retry-after", "60")], true),
+			(StatusCode::TOO_MANY_REQUESTS, vec![], true),
+			(
+				StatusCode::FORBIDDEN,
+				vec![("x-ratelimit-remaining", "4999")],
+				false,
+			),
+			(StatusCode::FORBIDDEN, vec![], false),
+			(
+				StatusCode::UNAUTHORIZED,
+				vec![("x-ratelimit-remaining", "0")],
+				false,
+			),
+			(StatusCode::OK, vec![("x-ratelimit-remaining", "0")], false),
+		] {
+			assert_eq!(
+				expected,
+				is_rate_limited(status, &headers(&values)),
+				"{status} {values:?}"
+			);
+		}
+		assert_eq!(
+			"remote_rate_limit",
+			Error::RemoteRateLimitError(String::new()).code()
+		);
+	}
+
+	#[test]
+	fn pull_request_release_note() {
+		let release_note = |body: Option<&str>| {
+			GitHubPullRequest {
+				body: body.map(String::from),
+				..Default::default()
+			}
+			.release_note()
+		};
+		assert_eq!(None, release_note(None));
+		assert_eq!(None, release_note(Some("Bump deps")));
+		assert_eq!(
+			None,
+			release_note(Some("Bump deps\n\n```release-note\nNONE\n```"))
+		);
+		assert_eq!(
+			Some(String::from("Support the GitHub integration")),
+			release_note(Some(
+				"Some context\r\n\r\n```release-note\r\nSupport the GitHub \
+				 integration\r\n```\r\n"
+			))
+		);
+		assert_eq!(
+			Some(String::from("Add the parser\nFix the lexer")),
+			release_note(Some(
+				"```release-note\nAdd the parser\n```\n\n```release-note \
+				 \nnone\n```\n```release-note\nFix the lexer\n```"
+			))
+		);
+	}
 }
@@ -0,0 +1,146 @@
+use crate::config::Config;
+use crate::error::Result;
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use sha2::{
+	Digest,
+	Sha256,
+};
+use std::fs::{
+	self,
+	OpenOptions,
+};
+use std::io::Write;
+use std::path::Path;
+
+/// Digest of a generated file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputDigest {
+	/// Path of the file (or `<stdout>`).
+	pub path:   String,
+	/// SHA-256 digest of the content.
+	pub digest: String,
+}
+
+/// Record of a changelog generation.
+///
+/// The records are appended to a JSON Lines file so that the published
+/// changelogs can be traced back to their inputs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+	/// Time of the generation (Unix timestamp).
+	pub timestamp:     i64,
+	/// Version of git-cliff.
+	pub generator:     String,
+	/// Commit range that is processed.
+	pub range:         Option<String>,
+	/// Version of the latest release.
+	pub version:       Option<String>,
+	/// SHA1 of the commit of the latest release.
+	pub commit_id:     Option<String>,
+	/// SHA-256 digest of the configuration.
+	pub config_digest: String,
+	/// Digests of the generated files.
+	pub outputs:       Vec<OutputDigest>,
+}
+
+impl HistoryEntry {
+	/// Constructs a new instance.
+	///
+	/// The digest of the configuration is calculated from its serialized form
+	/// so that the changes from the command line arguments are also taken
+	/// into account.
+	pub fn new(
+		timestamp: i64,
+		config: &Config,
+		outputs: &[(&Path, &[u8])],
+	) -> Result<Self> {
+		Ok(Self {
+			timestamp,
+			generator: format!("git-cliff {}", env!("CARGO_PKG_VERSION")),
+			range: None,
+			version: None,
+			commit_id: None,
+			config_digest: digest(
+				serde_json::to_value(config)?.to_string().as_bytes(),
+			),
+			outputs: outputs
+				.iter()
+				.map(|(path, content)| OutputDigest {
+					path:   path.to_string_lossy().to_string(),
+					digest: digest(content),
+				})
+				.collect(),
+		})
+	}
+
+	/// Returns the entry as a single line of JSON.
+	pub fn as_json(&self) -> Result<String> {
+		Ok(serde_json::to_string(self)?)
+	}
+
+	/// Appends the entry to the given file as a single line of JSON.
+	pub fn append(&self, path: &Path) -> Result<()> {
+		let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+		writeln!(file, "{}", self.as_json()?)?;
+		Ok(())
+	}
+
+	/// Loads the entries from the given file.
+	///
+	/// Returns no entries if the file does not exist.
+	pub fn load(path: &Path) -> Result<Vec<Self>> {
+		if !path.exists() {
+			return Ok(Vec::new());
+		}
+		fs::read_to_string(path)?
+			.lines()
+			.filter(|line| !line.trim().is_empty())
+			.map(|line| Ok(serde_json::from_str(line)?))
+			.collect()
+	}
+}
+
+/// Returns the SHA-256 digest of the content as a hex string.
+pub fn digest(content: &[u8]) -> String {
+	format!("{:x}", Sha256::digest(content))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn append_history() -> Result<()> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("history.jsonl");
+		let config = toml::from_str::<Config>("")?;
+		let mut entry = HistoryEntry::new(1714953600, &config, &[(
+			Path::new("CHANGELOG.md"),
+			b"# Changelog",
+		)])?;
+		entry.range = Some(String::from("v1.0.0..HEAD"));
+		entry.version = Some(String::from("v1.1.0"));
+		entry.commit_id = Some(String::from("1a2b3c4d"));
+		entry.append(&path)?;
+		entry.version = Some(String::from("v1.2.0"));
+		entry.append(&path)?;
+
+		let entries = HistoryEntry::load(&path)?;
+		assert_eq!(2, fs::read_to_string(&path)?.lines().count());
+		assert_eq!(entry, entries[1]);
+		assert_eq!(Some(String::from("v1.1.0")), entries[0].version);
+		assert_eq!(
+			"4bf8b81ff9c0302132a0717589744f30d0127cc1ebe9a32aa08a4c0fa624737a",
+			entries[0].outputs[0].digest
+		);
+		assert_eq!(
+			entries[0].config_digest,
+			HistoryEntry::new(0, &config, &[])?.config_digest
+		);
+		Ok(())
+	}
+}
@@ -12,28 +12,71 @@
 	html_favicon_url = "https://raw.githubusercontent.com/orhun/git-cliff/main/website/static/favicon/favicon.ico"
 )]
 
+/// Tag history audit.
+pub mod audit;
 /// Changelog generator.
 pub mod changelog;
+/// Locale-aware sorting.
+pub mod collation;
 /// Command runner.
 pub mod command;
 /// Git commit.
 pub mod commit;
 /// Config file parser.
 pub mod config;
+/// Unified diffs of the files.
+pub mod diff;
 /// Embedded file handler.
 pub mod embed;
 /// Error handling.
 pub mod error;
+/// Explanation of the commit processing.
+pub mod explain;
+/// Commit filter expressions.
+pub mod filter;
+/// Changelog context fixtures.
+pub mod fixture;
 /// GitHub client.
 #[cfg(feature = "github")]
 pub mod github;
+/// History of the changelog generations.
+pub mod history;
+/// URL checker.
+#[cfg(feature = "link-check")]
+pub mod link_check;
+/// Commit linter.
+pub mod lint;
+/// Markdown parsing.
+pub mod markdown;
+/// Changelog merging.
+pub mod merge;
+/// External commit metadata.
+pub mod metadata;
+/// Metrics of the changelog generation.
+pub mod metrics;
+/// Configuration migration.
+pub mod migrate;
+/// Context queries.
+pub mod query;
+/// Configuration reference.
+pub mod reference;
 /// Common release type.
 pub mod release;
 #[cfg(feature = "repo")]
 /// Git repository.
 pub mod repo;
+/// Reports for the CI systems.
+pub mod report;
+/// Usage of the configuration rules.
+pub mod rules;
+/// Scope inventory.
+pub mod scope;
+/// Changelog state.
+pub mod state;
 /// Template engine.
 pub mod template;
+/// Umbrella changelog of the workspaces.
+pub mod umbrella;
 
 #[macro_use]
 extern crate log;
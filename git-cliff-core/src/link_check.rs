@@ -0,0 +1,200 @@
+use crate::error::Result;
+use futures::{
+	stream,
+	StreamExt,
+};
+use reqwest::{
+	Client,
+	StatusCode,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::collections::{
+	BTreeMap,
+	BTreeSet,
+	HashMap,
+};
+use std::fs;
+use std::path::Path;
+use std::time::{
+	Duration,
+	SystemTime,
+	UNIX_EPOCH,
+};
+
+/// User agent for checking the URLs.
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+
+/// Request timeout value in seconds.
+const REQUEST_TIMEOUT: u64 = 15;
+
+/// Default number of the URLs that are checked at the same time.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Duration in seconds for which the URLs that are alive are not checked
+/// again.
+pub const CACHE_TTL: u64 = 86400;
+
+/// Cache of the URLs that are alive.
+///
+/// This is stored in a file between the runs so that the same URLs are not
+/// requested on each run. The dead URLs are not cached.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkCache {
+	/// Times of the last successful checks (Unix timestamp) of the URLs.
+	#[serde(default)]
+	pub alive: BTreeMap<String, u64>,
+}
+
+impl LinkCache {
+	/// Loads the cache from the given file.
+	///
+	/// Returns an empty cache if the file does not exist.
+	pub fn load(path: &Path) -> Result<Self> {
+		if path.exists() {
+			Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+		} else {
+			Ok(Self::default())
+		}
+	}
+
+	/// Saves the cache to the given file.
+	pub fn save(&self, path: &Path) -> Result<()> {
+		fs::write(path, serde_json::to_string_pretty(self)?)?;
+		Ok(())
+	}
+
+	/// Removes the URLs that are not checked in the last [`CACHE_TTL`]
+	/// seconds.
+	fn remove_expired(&mut self, now: u64) {
+		self.alive
+			.retain(|_, checked_at| now.saturating_sub(*checked_at) < CACHE_TTL);
+	}
+}
+
+/// Checker for finding the dead URLs.
+#[derive(Debug, Clone)]
+pub struct LinkChecker {
+	/// HTTP client.
+	client:      Client,
+	/// Number of the URLs that are checked at the same time.
+	concurrency: usize,
+}
+
+impl LinkChecker {
+	/// Constructs a new instance.
+	pub fn new(concurrency: Option<usize>) -> Result<Self> {
+		Ok(Self {
+			client:      Client::builder()
+				.timeout(Duration::from_secs(REQUEST_TIMEOUT))
+				.user_agent(USER_AGENT)
+				.build()?,
+			concurrency: concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1),
+		})
+	}
+
+	/// Checks the URL and returns the reason if it is dead.
+	///
+	/// The URL is requested with `HEAD` first and with `GET` if the server
+	/// does not allow `HEAD`. Rate limited requests are not reported.
+	async fn check(&self, url: &str) -> Option<String> {
+		let mut response = self.client.head(url).send().await;
+		if response.as_ref().is_ok_and(|v| {
+			matches!(
+				v.status(),
+				StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+			)
+		}) {
+			response = self.client.get(url).send().await;
+		}
+		match response {
+			Ok(response)
+				if response.status().is_success() ||
+					response.status() == StatusCode::TOO_MANY_REQUESTS =>
+			{
+				None
+			}
+			Ok(response) => Some(format!("HTTP {}", response.status().as_u16())),
+			Err(e) if e.is_timeout() => Some(String::from("timed out")),
+			Err(e) => Some(e.to_string()),
+		}
+	}
+
+	/// Checks the given URLs and returns the dead ones along with the reasons.
+	///
+	/// Each URL is requested once even if it is given multiple times. The
+	/// URLs that are alive in the given cache are skipped and the cache is
+	/// updated with the results.
+	pub fn dead_urls(
+		&self,
+		urls: impl IntoIterator<Item = String>,
+		cache: &mut LinkCache,
+	) -> Result<HashMap<String, String>> {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+		cache.remove_expired(now);
+		let urls = urls
+			.into_iter()
+			.filter(|url| !cache.alive.contains_key(url))
+			.collect::<BTreeSet<String>>();
+		debug!("Checking {} URL(s)", urls.len());
+		let results = tokio::runtime::Builder::new_multi_thread()
+			.enable_all()
+			.build()?
+			.block_on(
+				stream::iter(urls)
+					.map(|url| async move {
+						let reason = self.check(&url).await;
+						(url, reason)
+					})
+					.buffer_unordered(self.concurrency)
+					.collect::<Vec<(String, Option<String>)>>(),
+			);
+		let mut dead_urls = HashMap::new();
+		for (url, reason) in results {
+			match reason {
+				Some(reason) => {
+					dead_urls.insert(url, reason);
+				}
+				None => {
+					cache.alive.insert(url, now);
+				}
+			}
+		}
+		Ok(dead_urls)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn link_cache() -> Result<()> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("links.json");
+		assert_eq!(LinkCache::default(), LinkCache::load(&path)?);
+
+		let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+		let mut cache = LinkCache {
+			alive: BTreeMap::from([
+				(String::from("https://example.com/a"), now),
+				(String::from("https://example.com/b"), now - CACHE_TTL),
+			]),
+		};
+		cache.save(&path)?;
+		assert_eq!(cache, LinkCache::load(&path)?);
+
+		// the cached URLs are not requested again
+		let dead_urls = LinkChecker::new(None)?
+			.dead_urls([String::from("https://example.com/a")], &mut cache)?;
+		assert!(dead_urls.is_empty());
+		assert_eq!(
+			vec!["https://example.com/a"],
+			cache.alive.keys().collect::<Vec<&String>>()
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,805 @@
+use crate::commit::Commit;
+use crate::config::{
+	Config,
+	LintConfig,
+	LintSeverity,
+	OutputLintConfig,
+};
+use crate::error::Result;
+use crate::markdown;
+use git_conventional::Commit as ConventionalCommit;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+};
+use regex::{
+	Regex,
+	RegexBuilder,
+};
+use std::collections::{
+	HashMap,
+	HashSet,
+};
+use std::fmt;
+use std::path::Path;
+
+/// Regex for matching the targets of the inline links and images.
+static LINK_REGEX: Lazy<Regex> =
+	lazy_regex!(r"\]\(\s*<?([^()<>\s]+)>?(?:\s+[^)]*)?\)");
+
+/// Regex for matching the absolute URLs.
+static URL_REGEX: Lazy<Regex> = lazy_regex!(r#"https?://[^\s<>()\[\]"'`]+"#);
+
+/// Regex for matching the targets of the link reference definitions.
+static LINK_DEFINITION_REGEX: Lazy<Regex> =
+	lazy_regex!(r"^ {0,3}\[[^\]]+\]:\s*<?([^<>\s]+)>?");
+
+/// Regex for matching the inline code spans.
+static INLINE_CODE_REGEX: Lazy<Regex> = lazy_regex!(r"`[^`]*`");
+
+/// Common misspellings in the commit messages along with their corrections.
+const MISSPELLINGS: &[(&str, &str)] = &[
+	("accross", "across"),
+	("adress", "address"),
+	("arguement", "argument"),
+	("begining", "beginning"),
+	("commited", "committed"),
+	("compatability", "compatibility"),
+	("configuation", "configuration"),
+	("defualt", "default"),
+	("definately", "definitely"),
+	("dependancy", "dependency"),
+	("dependecy", "dependency"),
+	("enviroment", "environment"),
+	("existant", "existent"),
+	("funtion", "function"),
+	("implmentation", "implementation"),
+	("initalize", "initialize"),
+	("lenght", "length"),
+	("neccessary", "necessary"),
+	("occured", "occurred"),
+	("occurence", "occurrence"),
+	("paramter", "parameter"),
+	("recieve", "receive"),
+	("recieved", "received"),
+	("refered", "referred"),
+	("reponse", "response"),
+	("retreive", "retrieve"),
+	("seperate", "separate"),
+	("similiar", "similar"),
+	("succesful", "successful"),
+	("sucess", "success"),
+	("teh", "the"),
+	("thier", "their"),
+	("unecessary", "unnecessary"),
+	("untill", "until"),
+	("verison", "version"),
+	("wich", "which"),
+	("writting", "writing"),
+];
+
+/// Verbs that look like they are not in the imperative mood but they are.
+const IMPERATIVE_VERBS: &[&str] = &[
+	"bring", "embed", "exceed", "feed", "focus", "need", "proceed", "seed", "shed",
+	"speed", "spring", "string", "succeed",
+];
+
+/// Rule of the commit linter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+	/// Commit is not a conventional commit.
+	Conventional,
+	/// Subject is too long.
+	SubjectLength,
+	/// Subject does not start with a verb in the imperative mood.
+	ImperativeMood,
+	/// Subject ends with a period.
+	TrailingPeriod,
+	/// Commit message contains a disallowed word.
+	DisallowedWords,
+	/// Commit message contains a misspelled word.
+	Spelling,
+}
+
+impl fmt::Display for LintRule {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Conventional => write!(f, "conventional"),
+			Self::SubjectLength => write!(f, "subject_length"),
+			Self::ImperativeMood => write!(f, "imperative_mood"),
+			Self::TrailingPeriod => write!(f, "trailing_period"),
+			Self::DisallowedWords => write!(f, "disallowed_words"),
+			Self::Spelling => write!(f, "spelling"),
+		}
+	}
+}
+
+/// Violation of a lint rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintViolation {
+	/// SHA1 of the commit.
+	pub commit_id: String,
+	/// Violated rule.
+	pub rule:      LintRule,
+	/// Severity of the rule.
+	pub severity:  LintSeverity,
+	/// Description of the violation.
+	pub message:   String,
+}
+
+impl fmt::Display for LintViolation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"{} {}[{}]: {}",
+			self.commit_id.get(..7).unwrap_or(&self.commit_id),
+			self.severity,
+			self.rule,
+			self.message
+		)
+	}
+}
+
+/// Commit linter.
+#[derive(Debug, Clone)]
+pub struct Linter {
+	/// Lint configuration.
+	config:           LintConfig,
+	/// Whether conventional commits are enabled.
+	conventional:     bool,
+	/// Regex for matching the disallowed words.
+	disallowed_words: Option<Regex>,
+	/// Misspelled words (in lowercase) along with their corrections.
+	misspellings:     HashMap<String, String>,
+}
+
+impl Linter {
+	/// Constructs a new instance from the configuration.
+	pub fn new(config: &Config) -> Result<Self> {
+		let disallowed_words = match &config.lint.disallowed_words {
+			Some(words) if !words.is_empty() => Some(
+				RegexBuilder::new(&format!(
+					r"\b(?:{})\b",
+					words
+						.iter()
+						.map(|v| regex::escape(v))
+						.collect::<Vec<String>>()
+						.join("|")
+				))
+				.case_insensitive(true)
+				.build()?,
+			),
+			_ => None,
+		};
+		let misspellings = MISSPELLINGS
+			.iter()
+			.map(|(word, correction)| (word.to_string(), correction.to_string()))
+			.chain(config.lint.misspellings.iter().flatten().map(
+				|(word, correction)| (word.to_lowercase(), correction.to_string()),
+			))
+			.collect();
+		Ok(Self {
+			config: config.lint.clone(),
+			conventional: config.git.conventional_commits.unwrap_or(true),
+			disallowed_words,
+			misspellings,
+		})
+	}
+
+	/// Returns the severity of the rule.
+	fn severity(&self, rule: LintRule) -> LintSeverity {
+		let rules = &self.config.rules;
+		match rule {
+			LintRule::Conventional => {
+				rules.conventional.unwrap_or(if self.conventional {
+					LintSeverity::Error
+				} else {
+					LintSeverity::Off
+				})
+			}
+			LintRule::SubjectLength => {
+				rules.subject_length.unwrap_or(LintSeverity::Warning)
+			}
+			LintRule::ImperativeMood => {
+				rules.imperative_mood.unwrap_or(LintSeverity::Off)
+			}
+			LintRule::TrailingPeriod => {
+				rules.trailing_period.unwrap_or(LintSeverity::Off)
+			}
+			LintRule::DisallowedWords => {
+				rules.disallowed_words.unwrap_or(LintSeverity::Error)
+			}
+			LintRule::Spelling => rules.spelling.unwrap_or(LintSeverity::Warning),
+		}
+	}
+
+	/// Checks the commit and returns the violations.
+	pub fn lint(&self, commit: &Commit) -> Vec<LintViolation> {
+		let subject = commit.message.lines().next().unwrap_or_default().trim();
+		let conv = ConventionalCommit::parse(commit.message.trim_end());
+		let mut violations = Vec::new();
+		let mut report = |rule: LintRule, message: String| {
+			let severity = self.severity(rule);
+			if severity != LintSeverity::Off {
+				violations.push(LintViolation {
+					commit_id: commit.id.to_string(),
+					rule,
+					severity,
+					message,
+				});
+			}
+		};
+		if let Err(e) = &conv {
+			report(
+				LintRule::Conventional,
+				format!("commit is not conventional ({e})"),
+			);
+		}
+		if let Some(max_length) = self.config.max_subject_length {
+			let length = subject.chars().count();
+			if length > max_length {
+				report(
+					LintRule::SubjectLength,
+					format!(
+						"subject is longer than {max_length} characters ({length})"
+					),
+				);
+			}
+		}
+		let description = match &conv {
+			Ok(conv) => conv.description(),
+			Err(_) => subject,
+		};
+		if let Some(word) = description.split_whitespace().next() {
+			if !Self::is_imperative(word) {
+				report(
+					LintRule::ImperativeMood,
+					format!(
+						"subject should start with an imperative verb: `{word}`"
+					),
+				);
+			}
+		}
+		if subject.ends_with('.') {
+			report(
+				LintRule::TrailingPeriod,
+				String::from("subject ends with a period"),
+			);
+		}
+		if let Some(regex) = &self.disallowed_words {
+			let mut words = regex
+				.find_iter(&commit.message)
+				.map(|v| format!("`{}`", v.as_str()))
+				.collect::<Vec<String>>();
+			words.dedup();
+			if !words.is_empty() {
+				report(
+					LintRule::DisallowedWords,
+					format!(
+						"message contains disallowed words: {}",
+						words.join(", ")
+					),
+				);
+			}
+		}
+		let misspellings = self.misspellings(&commit.message);
+		if !misspellings.is_empty() {
+			report(
+				LintRule::Spelling,
+				format!(
+					"message contains misspelled words: {}",
+					misspellings.join(", ")
+				),
+			);
+		}
+		violations
+	}
+
+	/// Returns the misspelled words in the message along with their
+	/// corrections, e.g. `` `recieve` (receive) ``.
+	///
+	/// The words in the inline code spans are not checked.
+	fn misspellings(&self, message: &str) -> Vec<String> {
+		let message = INLINE_CODE_REGEX.replace_all(message, " ");
+		let mut misspellings = Vec::new();
+		let mut reported = HashSet::new();
+		for word in message.split(|c: char| !c.is_alphabetic() && c != '\'') {
+			let word = word.trim_matches('\'');
+			let key = word.to_lowercase();
+			if let Some(correction) = self.misspellings.get(&key) {
+				if reported.insert(key) {
+					misspellings.push(format!("`{word}` ({correction})"));
+				}
+			}
+		}
+		misspellings
+	}
+
+	/// Returns `false` if the word looks like a verb that is not in the
+	/// imperative mood, e.g. `added`, `adding` or `adds`.
+	///
+	/// This is a heuristic and the words that are not verbs are considered to
+	/// be in the imperative mood.
+	fn is_imperative(word: &str) -> bool {
+		let word = word.to_lowercase();
+		if !word.chars().all(|c| c.is_alphabetic()) ||
+			IMPERATIVE_VERBS.contains(&word.as_str())
+		{
+			return true;
+		}
+		!(word.ends_with("ed") ||
+			word.ends_with("ing") ||
+			(word.ends_with('s') &&
+				!["ss", "us", "is"].iter().any(|v| word.ends_with(v))))
+	}
+}
+
+/// Rule for checking the rendered changelog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputLintRule {
+	/// Line is too long.
+	LineLength,
+	/// Relative link points to a file that does not exist.
+	BrokenLinks,
+	/// Heading has the same text as a sibling heading.
+	DuplicateHeadings,
+	/// Heading level is incremented by more than one.
+	HeadingIncrement,
+	/// Line ends with whitespace.
+	TrailingSpaces,
+	/// Multiple consecutive blank lines.
+	MultipleBlankLines,
+	/// Absolute URL does not respond successfully.
+	DeadLinks,
+}
+
+impl fmt::Display for OutputLintRule {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::LineLength => write!(f, "line_length"),
+			Self::BrokenLinks => write!(f, "broken_links"),
+			Self::DuplicateHeadings => write!(f, "duplicate_headings"),
+			Self::HeadingIncrement => write!(f, "heading_increment"),
+			Self::TrailingSpaces => write!(f, "trailing_spaces"),
+			Self::MultipleBlankLines => write!(f, "multiple_blank_lines"),
+			Self::DeadLinks => write!(f, "dead_links"),
+		}
+	}
+}
+
+/// Violation of a rule in the rendered changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputLintViolation {
+	/// Line number (starting from 1).
+	pub line:     usize,
+	/// Violated rule.
+	pub rule:     OutputLintRule,
+	/// Severity of the rule.
+	pub severity: LintSeverity,
+	/// Description of the violation.
+	pub message:  String,
+}
+
+impl fmt::Display for OutputLintViolation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"{} {}[{}]: {}",
+			self.line, self.severity, self.rule, self.message
+		)
+	}
+}
+
+/// Linter for the rendered changelog.
+///
+/// The checks are a subset of the [markdownlint](https://github.com/DavidAnson/markdownlint)
+/// rules, e.g. `line_length` is `MD013` and `duplicate_headings` is `MD024`
+/// with the `siblings_only` option.
+#[derive(Debug, Clone)]
+pub struct OutputLinter<'a> {
+	/// Lint configuration.
+	config: &'a OutputLintConfig,
+}
+
+impl<'a> OutputLinter<'a> {
+	/// Constructs a new instance from the configuration.
+	pub fn new(config: &'a OutputLintConfig) -> Self {
+		Self { config }
+	}
+
+	/// Returns the severity of the rule.
+	fn severity(&self, rule: OutputLintRule) -> LintSeverity {
+		let rules = &self.config.rules;
+		match rule {
+			OutputLintRule::LineLength => {
+				rules.line_length.unwrap_or(LintSeverity::Warning)
+			}
+			OutputLintRule::BrokenLinks => {
+				rules.broken_links.unwrap_or(LintSeverity::Error)
+			}
+			OutputLintRule::DuplicateHeadings => {
+				rules.duplicate_headings.unwrap_or(LintSeverity::Warning)
+			}
+			OutputLintRule::HeadingIncrement => {
+				rules.heading_increment.unwrap_or(LintSeverity::Warning)
+			}
+			OutputLintRule::TrailingSpaces => {
+				rules.trailing_spaces.unwrap_or(LintSeverity::Off)
+			}
+			OutputLintRule::MultipleBlankLines => {
+				rules.multiple_blank_lines.unwrap_or(LintSeverity::Off)
+			}
+			OutputLintRule::DeadLinks => {
+				rules.dead_links.unwrap_or(LintSeverity::Error)
+			}
+		}
+	}
+
+	/// Checks the rendered changelog and returns the violations.
+	///
+	/// The relative links are resolved from the given directory. The fenced
+	/// code blocks and HTML comments are skipped.
+	pub fn lint(&self, output: &str, base_dir: &Path) -> Vec<OutputLintViolation> {
+		let mut violations = Vec::new();
+		let mut report = |line: usize, rule: OutputLintRule, message: String| {
+			let severity = self.severity(rule);
+			if severity != LintSeverity::Off {
+				violations.push(OutputLintViolation {
+					line,
+					rule,
+					severity,
+					message,
+				});
+			}
+		};
+		let mut blank_lines = 0;
+		let mut previous_level = None;
+		let mut parents: Vec<(usize, String)> = Vec::new();
+		let mut headings = HashSet::new();
+		let mut heading_lines = markdown::headings(output)
+			.into_iter()
+			.map(|v| (v.line + 1, v.depth, v.text))
+			.peekable();
+		for (number, line) in Self::prose_lines(output) {
+			if line.trim().is_empty() {
+				blank_lines += 1;
+				if blank_lines == 2 {
+					report(
+						number,
+						OutputLintRule::MultipleBlankLines,
+						String::from("multiple consecutive blank lines"),
+					);
+				}
+			} else {
+				blank_lines = 0;
+			}
+			if let Some(max_length) = self.config.max_line_length {
+				let length = line.chars().count();
+				if length > max_length &&
+					line.chars().skip(max_length).any(char::is_whitespace)
+				{
+					report(
+						number,
+						OutputLintRule::LineLength,
+						format!(
+							"line is longer than {max_length} characters ({length})"
+						),
+					);
+				}
+			}
+			let trailing = line.len() - line.trim_end().len();
+			if trailing > 0 && !(trailing == 2 && line.ends_with("  ")) {
+				report(
+					number,
+					OutputLintRule::TrailingSpaces,
+					String::from("line ends with whitespace"),
+				);
+			}
+			if let Some((_, level, text)) =
+				heading_lines.next_if(|(v, ..)| *v == number)
+			{
+				let text = text.to_string();
+				if let Some(previous) = previous_level.filter(|v| level > v + 1) {
+					report(
+						number,
+						OutputLintRule::HeadingIncrement,
+						format!(
+							"heading level is incremented from {previous} to \
+							 {level}"
+						),
+					);
+				}
+				previous_level = Some(level);
+				parents.retain(|(v, _)| *v < level);
+				let path = parents
+					.iter()
+					.map(|(_, v)| v.as_str())
+					.chain([text.as_str()])
+					.collect::<Vec<&str>>()
+					.join("\n");
+				if !headings.insert((level, path)) {
+					report(
+						number,
+						OutputLintRule::DuplicateHeadings,
+						format!("duplicate heading: `{text}`"),
+					);
+				}
+				parents.push((level, text));
+			}
+			for target in LINK_REGEX
+				.captures_iter(line)
+				.chain(LINK_DEFINITION_REGEX.captures_iter(line))
+				.filter_map(|v| v.get(1))
+				.map(|v| v.as_str())
+			{
+				let path = target.split(['#', '?']).next().unwrap_or_default();
+				if path.is_empty() ||
+					path.starts_with('/') ||
+					path.split('/').next().is_some_and(|v| v.contains(':'))
+				{
+					continue;
+				}
+				if !base_dir.join(path.replace("%20", " ")).exists() {
+					report(
+						number,
+						OutputLintRule::BrokenLinks,
+						format!("link points to a missing file: `{target}`"),
+					);
+				}
+			}
+		}
+		violations
+	}
+
+	/// Returns the absolute URLs in the rendered changelog.
+	pub fn urls(output: &str) -> Vec<String> {
+		Self::prose_lines(output)
+			.into_iter()
+			.flat_map(|(_, line)| Self::line_urls(line))
+			.collect()
+	}
+
+	/// Reports the dead URLs in the rendered changelog.
+	///
+	/// The dead URLs are mapped to the reasons, e.g. `HTTP 404`.
+	pub fn lint_dead_links(
+		&self,
+		output: &str,
+		dead_urls: &HashMap<String, String>,
+	) -> Vec<OutputLintViolation> {
+		let severity = self.severity(OutputLintRule::DeadLinks);
+		if severity == LintSeverity::Off {
+			return Vec::new();
+		}
+		Self::prose_lines(output)
+			.into_iter()
+			.flat_map(|(number, line)| {
+				Self::line_urls(line).into_iter().filter_map(move |url| {
+					dead_urls.get(&url).map(|reason| OutputLintViolation {
+						line: number,
+						rule: OutputLintRule::DeadLinks,
+						severity,
+						message: format!("link is dead ({reason}): `{url}`"),
+					})
+				})
+			})
+			.collect()
+	}
+
+	/// Returns the absolute URLs in the line.
+	fn line_urls(line: &str) -> Vec<String> {
+		URL_REGEX
+			.find_iter(line)
+			.map(|v| {
+				v.as_str()
+					.trim_end_matches(['.', ',', ';', ':', '!', '?'])
+					.to_string()
+			})
+			.collect()
+	}
+
+	/// Returns the numbered lines (starting from 1) that are not in the fenced
+	/// code blocks or HTML comments.
+	///
+	/// See [`markdown::prose_lines`].
+	fn prose_lines(output: &str) -> Vec<(usize, &str)> {
+		markdown::prose_lines(output)
+			.into_iter()
+			.map(|(i, line)| (i + 1, line))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::config::{
+		Config,
+		LintRules,
+		OutputLintRules,
+	};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn lint_commits() -> Result<()> {
+		let mut config = Config {
+			changelog: Default::default(),
+			git:       Default::default(),
+			remote:    Default::default(),
+			lint:      LintConfig {
+				max_subject_length: Some(20),
+				disallowed_words:   Some(vec![String::from("wip")]),
+				misspellings:       Some(HashMap::from([(
+					String::from("cliffs"),
+					String::from("cliff"),
+				)])),
+				rules:              LintRules {
+					imperative_mood: Some(LintSeverity::Warning),
+					trailing_period: Some(LintSeverity::Error),
+					..Default::default()
+				},
+				output:             None,
+			},
+			bump:      Default::default(),
+			eras:      None,
+			workspace: None,
+		};
+		let linter = Linter::new(&config)?;
+		let lint = |message: &str| {
+			linter
+				.lint(&Commit::new(
+					String::from("1234567890"),
+					String::from(message),
+				))
+				.into_iter()
+				.map(|v| (v.rule, v.severity))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(
+			Vec::<(LintRule, LintSeverity)>::new(),
+			lint("feat: add xyz")
+		);
+		assert_eq!(
+			vec![(LintRule::Conventional, LintSeverity::Error)],
+			lint("add xyz")
+		);
+		assert_eq!(
+			vec![
+				(LintRule::SubjectLength, LintSeverity::Warning),
+				(LintRule::ImperativeMood, LintSeverity::Warning),
+				(LintRule::TrailingPeriod, LintSeverity::Error),
+			],
+			lint("fix(parser): fixed the parser.")
+		);
+		assert_eq!(
+			vec![(LintRule::DisallowedWords, LintSeverity::Error)],
+			lint("feat: add xyz\n\nWIP")
+		);
+		assert_eq!(
+			Vec::<(LintRule, LintSeverity)>::new(),
+			lint("fix: embed xyz")
+		);
+		assert_eq!(
+			vec![(LintRule::ImperativeMood, LintSeverity::Warning)],
+			lint("fix: updates xyz")
+		);
+		assert_eq!(
+			vec![(LintRule::Spelling, LintSeverity::Warning)],
+			lint("fix: fix xyz\n\nTeh git Cliffs")
+		);
+		assert_eq!(
+			vec!["`Teh` (the)", "`Cliffs` (cliff)"],
+			linter.misspellings("fix: `teh` xyz\n\nTeh git Cliffs, teh")
+		);
+
+		config.git.conventional_commits = Some(false);
+		config.lint.rules.subject_length = Some(LintSeverity::Off);
+		let linter = Linter::new(&config)?;
+		let violations = linter.lint(&Commit::new(
+			String::from("1234567890"),
+			String::from("adding the remote metadata"),
+		));
+		assert_eq!(1, violations.len());
+		assert_eq!(
+			"1234567 warning[imperative_mood]: subject should start with an \
+			 imperative verb: `adding`",
+			violations[0].to_string()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn lint_output() -> Result<()> {
+		let config = OutputLintConfig {
+			max_line_length:       Some(40),
+			check_urls:            None,
+			url_check_concurrency: None,
+			url_check_cache:       None,
+			rules:                 OutputLintRules {
+				trailing_spaces: Some(LintSeverity::Error),
+				..Default::default()
+			},
+		};
+		let output = [
+			"# Changelog",
+			"",
+			"## [1.1.0]",
+			"",
+			"### Features",
+			"",
+			"- Add the support for linting the rendered changelog output",
+			"- See [the docs](website/docs/configuration/lint.md) and \
+			 [x](missing.md)",
+			"- See [the website](https://git-cliff.org/docs/configuration/lint)\t",
+			"",
+			"### Features",
+			"",
+			"## [1.0.0]",
+			"",
+			"### Features",
+			"",
+			"#### Details",
+			"",
+			"```",
+			"- Not [a link](missing.md) #########################################",
+			"```",
+			"",
+			"###### Too deep",
+			"",
+			"[1.1.0]: docs/CHANGELOG.md#v110",
+			"",
+			"<!--",
+			"## [1.0.0]",
+			"-->",
+			"## [1.2.0] ##",
+			"",
+			"## [1.2.0]",
+		]
+		.join("\n");
+		let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+			.parent()
+			.ok_or_else(|| std::io::Error::other("parent directory not found"))?;
+		let violations = OutputLinter::new(&config).lint(&output, root);
+		assert_eq!(
+			vec![
+				(7, OutputLintRule::LineLength, LintSeverity::Warning),
+				(8, OutputLintRule::LineLength, LintSeverity::Warning),
+				(8, OutputLintRule::BrokenLinks, LintSeverity::Error),
+				(9, OutputLintRule::LineLength, LintSeverity::Warning),
+				(9, OutputLintRule::TrailingSpaces, LintSeverity::Error),
+				(11, OutputLintRule::DuplicateHeadings, LintSeverity::Warning),
+				(23, OutputLintRule::HeadingIncrement, LintSeverity::Warning),
+				(25, OutputLintRule::BrokenLinks, LintSeverity::Error),
+				(32, OutputLintRule::DuplicateHeadings, LintSeverity::Warning),
+			],
+			violations
+				.iter()
+				.map(|v| (v.line, v.rule, v.severity))
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			"8 error[broken_links]: link points to a missing file: `missing.md`",
+			violations[2].to_string()
+		);
+		assert_eq!(
+			vec!["https://git-cliff.org/docs/configuration/lint"],
+			OutputLinter::urls(&output)
+		);
+		let dead_urls = HashMap::from([(
+			String::from("https://git-cliff.org/docs/configuration/lint"),
+			String::from("HTTP 404"),
+		)]);
+		assert_eq!(
+			vec![String::from(
+				"9 error[dead_links]: link is dead (HTTP 404): \
+				 `https://git-cliff.org/docs/configuration/lint`"
+			)],
+			OutputLinter::new(&config)
+				.lint_dead_links(&output, &dead_urls)
+				.iter()
+				.map(|v| v.to_string())
+				.collect::<Vec<String>>()
+		);
+		Ok(())
+	}
+}
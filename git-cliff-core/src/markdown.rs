@@ -0,0 +1,389 @@
+use crate::commit::Commit;
+use crate::config::civil_timestamp;
+use crate::release::Release;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+
+/// Regex for matching the release headings (e.g. `[1.0.0] - 2024-05-06`).
+static RELEASE_HEADING_REGEX: Lazy<Regex> = lazy_regex!(
+	r"^\[?(?P<version>[^\]\s]+)\]?(?:\([^)]*\))?(?:\s+-\s+(?P<year>\d{4})-(?P<month>\d{2})-(?P<day>\d{2}))?$"
+);
+
+/// Regex for matching the entries of the changelog (e.g. `- *(ui)* Fix x`).
+static ENTRY_REGEX: Lazy<Regex> = lazy_regex!(
+	r"^[-*+]\s+(?:\*{1,2}\((?P<scope>[^)]+)\)\*{1,2}\s+)?(?P<breaking>\[\*\*breaking\*\*\]\s+)?(?P<message>.*?)(?:\s+\(\[(?P<sha>[0-9a-f]{7,40})\]\((?P<link>[^)\s]*)\)\))?$"
+);
+
+/// Regex for matching the full SHA1 of a commit.
+static SHA1_REGEX: Lazy<Regex> = lazy_regex!(r"^[0-9a-f]{40}$");
+
+/// Heading of a Markdown document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading<'a> {
+	/// Byte offset of the heading line in the document.
+	pub offset: usize,
+	/// Index of the heading line.
+	pub line:   usize,
+	/// Level of the heading (e.g. `2` for `## x`).
+	pub depth:  usize,
+	/// Text of the heading without the `#` characters.
+	pub text:   &'a str,
+}
+
+/// Line of a Markdown document.
+struct Line<'a> {
+	/// Byte offset of the line in the document.
+	offset:  usize,
+	/// Content of the line without the line ending.
+	text:    &'a str,
+	/// Whether if the line is in a fenced code block or an HTML comment.
+	literal: bool,
+}
+
+/// Splits the document into lines and marks the lines that are not parsed as
+/// Markdown.
+fn lines(document: &str) -> Vec<Line<'_>> {
+	let mut lines = Vec::new();
+	let mut fence: Option<&str> = None;
+	let mut in_comment = false;
+	let mut offset = 0;
+	for line in document.split_inclusive('\n') {
+		let trimmed = line.trim();
+		let literal = if let Some(marker) = fence {
+			if trimmed.starts_with(marker) {
+				fence = None;
+			}
+			true
+		} else if in_comment {
+			in_comment = !trimmed.contains("-->");
+			true
+		} else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+			fence = Some(&trimmed[..3]);
+			true
+		} else if trimmed.starts_with("<!--") {
+			in_comment = !trimmed.contains("-->");
+			true
+		} else {
+			false
+		};
+		lines.push(Line {
+			offset,
+			text: line.trim_end_matches(['\r', '\n']),
+			literal,
+		});
+		offset += line.len();
+	}
+	lines
+}
+
+/// Returns the lines of the document that are parsed as Markdown along with
+/// their indexes.
+///
+/// The lines in fenced code blocks and HTML comments are skipped.
+pub fn prose_lines(document: &str) -> Vec<(usize, &str)> {
+	lines(document)
+		.into_iter()
+		.enumerate()
+		.filter(|(_, line)| !line.literal)
+		.map(|(i, line)| (i, line.text))
+		.collect()
+}
+
+/// Returns the ATX headings of the document.
+///
+/// The lines in fenced code blocks and HTML comments are not considered as
+/// headings. The optional closing sequence (e.g. `## x ##`) is not a part of
+/// the heading text.
+pub fn headings(document: &str) -> Vec<Heading<'_>> {
+	lines(document)
+		.into_iter()
+		.enumerate()
+		.filter(|(_, line)| !line.literal)
+		.filter_map(|(i, line)| {
+			let text = line.text.trim_end();
+			let depth = text.chars().take_while(|c| *c == '#').count();
+			let content = &text[depth..];
+			((1..=6).contains(&depth) &&
+				(content.is_empty() || content.starts_with([' ', '\t'])))
+			.then(|| {
+				let content = content.trim();
+				let closing = content.trim_end_matches('#');
+				Heading {
+					offset: line.offset,
+					line: i,
+					depth,
+					text: if closing.is_empty() || closing.ends_with([' ', '\t']) {
+						closing.trim_end()
+					} else {
+						content
+					},
+				}
+			})
+		})
+		.collect()
+}
+
+/// Returns the release headings of the document.
+///
+/// The release headings are the headings with a version or `unreleased` (e.g.
+/// `## [1.0.0] - 2024-05-06`) at the level of the first one.
+pub fn release_headings(document: &str) -> Vec<Heading<'_>> {
+	let headings = headings(document);
+	let Some(depth) = headings
+		.iter()
+		.find(|v| parse_release_heading(v.text).is_some())
+		.map(|v| v.depth)
+	else {
+		return Vec::new();
+	};
+	headings
+		.into_iter()
+		.filter(|v| v.depth == depth && parse_release_heading(v.text).is_some())
+		.collect()
+}
+
+/// Returns the release of the given heading text.
+///
+/// The text is expected to contain a version (e.g. `[1.0.0] - 2024-05-06`)
+/// or to be `unreleased`.
+fn parse_release_heading(text: &str) -> Option<Release<'static>> {
+	let captures = RELEASE_HEADING_REGEX.captures(text)?;
+	let version = &captures["version"];
+	if !version.eq_ignore_ascii_case("unreleased") &&
+		!version.contains(|c: char| c.is_ascii_digit())
+	{
+		return None;
+	}
+	let timestamp = match (
+		captures.name("year"),
+		captures.name("month"),
+		captures.name("day"),
+	) {
+		(Some(year), Some(month), Some(day)) => civil_timestamp(
+			year.as_str().parse().ok()?,
+			month.as_str().parse().ok()?,
+			day.as_str().parse().ok()?,
+		),
+		_ => 0,
+	};
+	Some(Release {
+		version: (!version.eq_ignore_ascii_case("unreleased"))
+			.then(|| version.to_string()),
+		timestamp,
+		..Default::default()
+	})
+}
+
+/// Returns the commit of the given changelog entry.
+fn parse_entry(
+	line: &str,
+	group: Option<&str>,
+	scope: Option<&str>,
+) -> Option<Commit<'static>> {
+	let captures = ENTRY_REGEX.captures(line)?;
+	let message = captures["message"].to_string();
+	let id = match (captures.name("sha"), captures.name("link")) {
+		(_, Some(link)) if SHA1_REGEX.is_match(link.as_str()) => {
+			link.as_str().to_string()
+		}
+		(Some(sha), _) => sha.as_str().to_string(),
+		_ => String::new(),
+	};
+	let mut commit = Commit::new(id, message);
+	commit.breaking = captures.name("breaking").is_some();
+	commit.group = group.map(String::from);
+	commit.scope = captures
+		.name("scope")
+		.map(|v| v.as_str())
+		.or(scope)
+		.map(String::from);
+	Some(commit)
+}
+
+/// Parses the releases of a previously generated changelog.
+///
+/// This is the inverse of rendering a changelog with the default templates
+/// (e.g. `default`, `keepachangelog`, `detailed`, `minimal` and `scoped`):
+///
+/// * The release headings are the headings with a version or `unreleased` (e.g.
+///   `## [1.0.0] - 2024-05-06`) and the first one sets their level.
+/// * The headings one level below are the groups and the deeper headings are
+///   the scopes of the entries.
+/// * The list items are the commits, along with their scope (e.g. `*(ui)*`),
+///   breaking marker (`[**breaking**]`) and SHA1 (e.g. `([1a2b3c4](...))`). The
+///   indented lines are appended to the message of the previous entry.
+///
+/// The content outside of the release sections (e.g. header and footer) is
+/// ignored. The releases are returned in the order of the changelog (newest
+/// first) and linked to the releases that follow them.
+pub fn parse_releases(document: &str) -> Vec<Release<'static>> {
+	let Some(release_depth) = release_headings(document).first().map(|v| v.depth)
+	else {
+		return Vec::new();
+	};
+	let mut headings = headings(document).into_iter().peekable();
+	let mut releases: Vec<Release> = Vec::new();
+	let mut in_release = false;
+	let mut group = None;
+	let mut scope = None;
+	for (i, line) in lines(document).into_iter().enumerate() {
+		if let Some(heading) = headings.next_if(|v| v.line == i) {
+			if heading.depth <= release_depth {
+				in_release = match parse_release_heading(heading.text) {
+					Some(release) if heading.depth == release_depth => {
+						releases.push(release);
+						true
+					}
+					_ => false,
+				};
+				group = None;
+				scope = None;
+			} else if heading.depth == release_depth + 1 {
+				group = Some(heading.text);
+				scope = None;
+			} else {
+				scope = Some(heading.text);
+			}
+			continue;
+		}
+		let Some(release) = releases.last_mut().filter(|_| in_release) else {
+			continue;
+		};
+		if line.literal || line.text.trim().is_empty() {
+			continue;
+		} else if let Some(commit) = parse_entry(line.text, group, scope) {
+			release.commits.push(commit);
+		} else if line.text.starts_with(char::is_whitespace) {
+			if let Some(commit) = release.commits.last_mut() {
+				commit.message = format!("{}\n{}", commit.message, line.text.trim());
+			}
+		}
+	}
+	for i in (0..releases.len().saturating_sub(1)).rev() {
+		let mut previous = releases[i + 1].clone();
+		previous.previous = None;
+		releases[i].previous = Some(Box::new(previous));
+	}
+	releases
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::embed::EmbeddedConfig;
+	use crate::error::Result;
+	use crate::template::Template;
+	use pretty_assertions::assert_eq;
+	use std::collections::HashMap;
+
+	#[test]
+	fn parse_headings() {
+		let document = "# Changelog\n\n```md\n## not a heading\n```\n<!-- \
+		                comment\n## hidden\n-->\n#not a heading\n## [1.0.0]\n";
+		assert_eq!(
+			vec![
+				Heading {
+					offset: 0,
+					line:   0,
+					depth:  1,
+					text:   "Changelog",
+				},
+				Heading {
+					offset: 82,
+					line:   9,
+					depth:  2,
+					text:   "[1.0.0]",
+				},
+			],
+			headings(document)
+		);
+		assert!(headings("").is_empty());
+	}
+
+	#[test]
+	fn parse_changelog() -> Result<()> {
+		let document = [
+			"# Changelog",
+			"",
+			"All notable changes to this project will be documented in this file.",
+			"",
+			"## [unreleased]",
+			"",
+			"### 🚀 Features",
+			"",
+			"- *(ui)* [**breaking**] Remove the old theme",
+			"- Add x",
+			"",
+			"## [1.1.0] - 2024-05-06",
+			"",
+			"### 🐛 Bug Fixes",
+			"",
+			"- *(parser)* Fix y",
+			"",
+			"```md",
+			"- not an entry",
+			"```",
+			"",
+			"## [1.0.0] - 2024-01-31",
+			"",
+			"### 📚 Documentation",
+			"",
+			"- Update the readme",
+			"",
+			"<!-- generated by git-cliff -->",
+			"",
+		]
+		.join("\n");
+		let releases = parse_releases(&document);
+		assert_eq!(3, releases.len());
+		assert_eq!(None, releases[0].version);
+		assert_eq!(Some(String::from("1.1.0")), releases[1].version);
+		assert_eq!(1714953600, releases[1].timestamp);
+		assert_eq!(1706659200, releases[2].timestamp);
+		assert_eq!(
+			Some(String::from("1.0.0")),
+			releases[1]
+				.previous
+				.as_ref()
+				.and_then(|v| v.version.clone())
+		);
+		assert!(releases[2].previous.is_none());
+		assert_eq!(1, releases[1].commits.len());
+		let commit = &releases[0].commits[0];
+		assert_eq!(Some(String::from("🚀 Features")), commit.group);
+		assert_eq!(Some(String::from("ui")), commit.scope);
+		assert!(commit.breaking);
+		assert_eq!("Remove the old theme", commit.message);
+
+		let config =
+			toml::from_str::<crate::config::Config>(&EmbeddedConfig::get_config()?)?;
+		let template =
+			Template::new(config.changelog.body.unwrap_or_default(), true)?;
+		let mut rendered = config.changelog.header.unwrap_or_default();
+		for release in &releases {
+			rendered += &template.render(
+				release,
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)?;
+		}
+		rendered += &config.changelog.footer.unwrap_or_default();
+		assert_eq!(
+			document.replace("```md\n- not an entry\n```\n\n", ""),
+			rendered
+		);
+
+		let document = "# Changelog\n\n## [1.0.0] - 2024-01-31\n\n### Added\n\n\
+		                - Add x ([1a2b3c4](https://x.y/commit/1a2b3c4))\n  \
+		                continued\n\n## New Contributors\n\n- @x\n";
+		let releases = parse_releases(document);
+		assert_eq!(1, releases[0].commits.len());
+		assert_eq!("1a2b3c4", releases[0].commits[0].id);
+		assert_eq!("Add x\ncontinued", releases[0].commits[0].message);
+		assert!(parse_releases("# Changelog\n\n## Notes\n").is_empty());
+		Ok(())
+	}
+}
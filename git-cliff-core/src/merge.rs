@@ -0,0 +1,194 @@
+use crate::diff::common_lines;
+use crate::markdown;
+
+/// A release section of the changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Section<'a> {
+	/// Heading line of the section.
+	heading: &'a str,
+	/// Lines of the section, excluding the heading.
+	lines:   Vec<&'a str>,
+}
+
+/// Splits the changelog into the preamble (e.g. header) and release
+/// sections.
+///
+/// See [`markdown::release_headings`] for the headings that start a section.
+fn split_sections(changelog: &str) -> (Vec<&str>, Vec<Section<'_>>) {
+	let mut headings = markdown::release_headings(changelog)
+		.into_iter()
+		.map(|v| v.line)
+		.peekable();
+	let mut preamble = Vec::new();
+	let mut sections: Vec<Section> = Vec::new();
+	for (i, line) in changelog.lines().enumerate() {
+		if headings.next_if_eq(&i).is_some() {
+			sections.push(Section {
+				heading: line,
+				lines:   Vec::new(),
+			});
+		} else if let Some(section) = sections.last_mut() {
+			section.lines.push(line);
+		} else {
+			preamble.push(line);
+		}
+	}
+	(preamble, sections)
+}
+
+/// Returns the lines of the section with the given heading.
+fn find<'a>(sections: &[Section<'a>], heading: &str) -> Option<Vec<&'a str>> {
+	sections
+		.iter()
+		.find(|v| v.heading == heading)
+		.map(|v| v.lines.clone())
+}
+
+/// Merges the lines of two versions that are derived from the same base.
+///
+/// If only one side is changed, it is taken as is. Otherwise, the lines of
+/// both sides are combined in order, keeping the common lines once. The lines
+/// that exist in the base but are only kept in one side are treated as
+/// removed.
+fn merge_lines<'a>(
+	base: &[&'a str],
+	current: &[&'a str],
+	other: &[&'a str],
+) -> Vec<&'a str> {
+	if current == base || current == other {
+		return other.to_vec();
+	} else if other == base {
+		return current.to_vec();
+	}
+	let mut merged = Vec::new();
+	let (mut i, mut j) = (0, 0);
+	let push_unmatched = |merged: &mut Vec<&'a str>, lines: &[&'a str]| {
+		for line in lines {
+			if line.trim().is_empty() || !base.contains(line) {
+				merged.push(*line);
+			}
+		}
+	};
+	for (current_index, other_index) in common_lines(current, other)
+		.into_iter()
+		.chain([(current.len(), other.len())])
+	{
+		push_unmatched(&mut merged, &current[i..current_index]);
+		push_unmatched(&mut merged, &other[j..other_index]);
+		if let Some(line) = current.get(current_index) {
+			merged.push(*line);
+		}
+		i = current_index + 1;
+		j = other_index + 1;
+	}
+	merged
+}
+
+/// Merges two versions of a changelog that are derived from the same base
+/// version without leaving any conflict markers.
+///
+/// The changelog is split into release sections and the sections with the
+/// same heading are merged line by line. The sections that only exist in
+/// `other` are inserted before the section that follows them in `other`.
+pub fn merge(base: &str, current: &str, other: &str) -> String {
+	let (base_preamble, base_sections) = split_sections(base);
+	let (current_preamble, current_sections) = split_sections(current);
+	let (other_preamble, other_sections) = split_sections(other);
+	let mut merged_sections: Vec<Section> = current_sections
+		.iter()
+		.filter(|section| {
+			// drop the sections that are removed in other
+			find(&other_sections, section.heading).is_some() ||
+				find(&base_sections, section.heading).is_none()
+		})
+		.map(|section| {
+			let lines = match (
+				find(&base_sections, section.heading),
+				find(&other_sections, section.heading),
+			) {
+				(Some(base), Some(other)) => {
+					merge_lines(&base, &section.lines, &other)
+				}
+				(None, Some(other)) => merge_lines(&[], &section.lines, &other),
+				(_, None) => section.lines.clone(),
+			};
+			Section {
+				heading: section.heading,
+				lines,
+			}
+		})
+		.collect();
+	for (i, section) in other_sections.iter().enumerate() {
+		if find(&merged_sections, section.heading).is_some() ||
+			find(&base_sections, section.heading).is_some()
+		{
+			continue;
+		}
+		let position = other_sections[i + 1..]
+			.iter()
+			.find_map(|next| {
+				merged_sections
+					.iter()
+					.position(|v| v.heading == next.heading)
+			})
+			.unwrap_or(merged_sections.len());
+		merged_sections.insert(position, section.clone());
+	}
+	let mut lines = merge_lines(&base_preamble, &current_preamble, &other_preamble);
+	for section in merged_sections {
+		lines.push(section.heading);
+		lines.extend(section.lines);
+	}
+	let mut merged = lines.join("\n");
+	if current.ends_with('\n') || other.ends_with('\n') {
+		merged.push('\n');
+	}
+	merged
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn merge_changelog() {
+		let base = "# Changelog\n\n## [1.0.0]\n\n### Features\n\n- a\n";
+		let current = "# Changelog\n\n## [unreleased]\n\n### Features\n\n- b\n\n## \
+		               [1.0.0]\n\n### Features\n\n- a\n";
+		let other = "# Changelog\n\n## [unreleased]\n\n### Features\n\n- c\n\n### \
+		             Bug Fixes\n\n- d\n\n## [1.0.0]\n\n### Features\n\n- a\n";
+		assert_eq!(
+			"# Changelog\n\n## [unreleased]\n\n### Features\n\n- b\n- c\n\n### Bug \
+			 Fixes\n\n- d\n\n## [1.0.0]\n\n### Features\n\n- a\n",
+			merge(base, current, other)
+		);
+	}
+
+	#[test]
+	fn merge_new_sections() {
+		let base = "## [1.0.0]\n- a\n";
+		let current = "## [1.1.0]\n- b\n## [1.0.0]\n- a\n";
+		let other = "## [1.0.1]\n- c\n## [1.0.0]\n- a\n";
+		assert_eq!(
+			"## [1.1.0]\n- b\n## [1.0.1]\n- c\n## [1.0.0]\n- a\n",
+			merge(base, current, other)
+		);
+		assert_eq!(current, merge(base, current, base));
+		assert_eq!(other, merge(base, base, other));
+	}
+
+	#[test]
+	fn merge_nested_headings() {
+		let base = "# Changelog\n<!--\n## [0.9.0]\n-->\n# [1.0.0]\n## Notes\n- a\n";
+		let current = "# Changelog\n<!--\n## [0.9.0]\n-->\n# [1.1.0]\n## Notes\n- \
+		               b\n# [1.0.0]\n## Notes\n- a\n";
+		let other =
+			"# Changelog\n<!--\n## [0.9.0]\n-->\n# [1.0.0]\n## Notes\n- a\n- c\n";
+		assert_eq!(
+			"# Changelog\n<!--\n## [0.9.0]\n-->\n# [1.1.0]\n## Notes\n- b\n# \
+			 [1.0.0]\n## Notes\n- a\n- c\n",
+			merge(base, current, other)
+		);
+	}
+}
@@ -0,0 +1,272 @@
+use crate::error::{
+	Error,
+	Result,
+};
+use serde_json::{
+	Map,
+	Value,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::mem;
+use std::ops::Bound;
+use std::path::Path;
+
+/// Name of the field that contains the SHA of the commit.
+const SHA_FIELD: &str = "sha";
+
+/// Minimum length of the abbreviated SHAs.
+const MIN_SHA_LENGTH: usize = 7;
+
+/// External metadata of the commits (e.g. deployment records) keyed by SHA.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CommitMetadata {
+	/// Metadata fields of the commits.
+	entries: BTreeMap<String, Map<String, Value>>,
+}
+
+impl CommitMetadata {
+	/// Loads the metadata from the given JSON or CSV file.
+	///
+	/// The file is parsed as CSV if it has the `.csv` extension.
+	pub fn load(path: &Path) -> Result<Self> {
+		let content = fs::read_to_string(path)?;
+		if path
+			.extension()
+			.is_some_and(|v| v.eq_ignore_ascii_case("csv"))
+		{
+			Self::parse_csv(&content)
+		} else {
+			Self::parse_json(&content)
+		}
+	}
+
+	/// Parses the metadata from JSON.
+	///
+	/// It is either an object that maps the SHAs to the fields or an array of
+	/// objects with a `sha` field.
+	pub fn parse_json(content: &str) -> Result<Self> {
+		let mut metadata = Self::default();
+		match serde_json::from_str::<Value>(content)? {
+			Value::Object(object) => {
+				for (sha, fields) in object {
+					match fields {
+						Value::Object(fields) => metadata.insert(&sha, fields),
+						_ => {
+							return Err(Error::MetadataError(format!(
+								"metadata of {sha} is not an object"
+							)));
+						}
+					}
+				}
+			}
+			Value::Array(array) => {
+				for fields in array {
+					let Value::Object(mut fields) = fields else {
+						return Err(Error::MetadataError(String::from(
+							"metadata entry is not an object",
+						)));
+					};
+					match fields.remove(SHA_FIELD) {
+						Some(Value::String(sha)) => metadata.insert(&sha, fields),
+						_ => {
+							return Err(Error::MetadataError(format!(
+								"metadata entry does not have a `{SHA_FIELD}` field"
+							)));
+						}
+					}
+				}
+			}
+			_ => {
+				return Err(Error::MetadataError(String::from(
+					"metadata is not an object or an array",
+				)));
+			}
+		}
+		metadata.check_ambiguous_shas()?;
+		Ok(metadata)
+	}
+
+	/// Parses the metadata from CSV.
+	///
+	/// The first row is the header and it must contain a `sha` column. Empty
+	/// cells are ignored.
+	pub fn parse_csv(content: &str) -> Result<Self> {
+		let mut rows = Self::parse_csv_rows(content)?.into_iter();
+		let header = rows.next().unwrap_or_default();
+		let sha_column =
+			header.iter().position(|v| v == SHA_FIELD).ok_or_else(|| {
+				Error::MetadataError(format!(
+					"CSV header does not have a `{SHA_FIELD}` column"
+				))
+			})?;
+		let mut metadata = Self::default();
+		for row in rows {
+			let Some(sha) = row.get(sha_column) else {
+				continue;
+			};
+			let fields = header
+				.iter()
+				.zip(row.iter())
+				.enumerate()
+				.filter(|(i, (_, value))| *i != sha_column && !value.is_empty())
+				.map(|(_, (name, value))| {
+					(name.to_string(), Value::String(value.to_string()))
+				})
+				.collect();
+			metadata.insert(sha, fields);
+		}
+		metadata.check_ambiguous_shas()?;
+		Ok(metadata)
+	}
+
+	/// Splits the CSV content into rows of cells.
+	///
+	/// The quoted cells can contain commas, line breaks and escaped quotes
+	/// (`""`). The rows without any values are skipped.
+	fn parse_csv_rows(content: &str) -> Result<Vec<Vec<String>>> {
+		let mut rows = Vec::new();
+		let mut cells = vec![String::new()];
+		let mut quoted = false;
+		let mut chars = content.chars().peekable();
+		while let Some(c) = chars.next() {
+			match c {
+				'"' if quoted && chars.peek() == Some(&'"') => {
+					chars.next();
+					cells.last_mut().expect("no cells").push('"');
+				}
+				'"' => quoted = !quoted,
+				',' if !quoted => cells.push(String::new()),
+				'\r' if !quoted && chars.peek() == Some(&'\n') => {}
+				'\n' if !quoted => {
+					rows.push(mem::replace(&mut cells, vec![String::new()]));
+				}
+				_ => cells.last_mut().expect("no cells").push(c),
+			}
+		}
+		if quoted {
+			return Err(Error::MetadataError(String::from(
+				"CSV has an unterminated quoted cell",
+			)));
+		}
+		rows.push(cells);
+		Ok(rows
+			.into_iter()
+			.map(|row| {
+				row.into_iter()
+					.map(|v| v.trim().to_string())
+					.collect::<Vec<String>>()
+			})
+			.filter(|row| row.iter().any(|v| !v.is_empty()))
+			.collect())
+	}
+
+	/// Returns an error if an abbreviated SHA is the prefix of another SHA,
+	/// since they would match the same commits.
+	fn check_ambiguous_shas(&self) -> Result<()> {
+		let shas = self.entries.keys().collect::<Vec<&String>>();
+		for pair in shas.windows(2) {
+			// the SHAs that start with a prefix are sorted right after it
+			if pair[0].len() >= MIN_SHA_LENGTH && pair[1].starts_with(pair[0]) {
+				return Err(Error::MetadataError(format!(
+					"metadata SHA {} is ambiguous with {}",
+					pair[0], pair[1]
+				)));
+			}
+		}
+		Ok(())
+	}
+
+	/// Adds the fields of the commit.
+	fn insert(&mut self, sha: &str, fields: Map<String, Value>) {
+		self.entries
+			.entry(sha.trim().to_lowercase())
+			.or_default()
+			.extend(fields);
+	}
+
+	/// Returns the metadata of the commit with the given ID.
+	///
+	/// The metadata can be keyed by the abbreviated SHAs as well.
+	pub fn get(&self, id: &str) -> Option<&Map<String, Value>> {
+		// since the SHAs are not ambiguous, the only candidate prefix is the
+		// greatest SHA that is not greater than the ID
+		self.entries
+			.range::<str, _>((Bound::Unbounded, Bound::Included(id)))
+			.next_back()
+			.filter(|(sha, _)| {
+				sha.as_str() == id ||
+					(sha.len() >= MIN_SHA_LENGTH && id.starts_with(sha.as_str()))
+			})
+			.map(|(_, fields)| fields)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn parse_metadata() -> Result<()> {
+		let id = "a1b2c3d4e5f60718293a4b5c6d7e8f9012345678";
+		let json = CommitMetadata::parse_json(
+			r#"[
+				{ "sha": "A1B2C3D4", "deployed_at": "2024-01-01", "build_id": 42 },
+				{ "sha": "ffffff0", "deployed_at": "2024-01-02" }
+			]"#,
+		)?;
+		assert_eq!(
+			Some(
+				&serde_json::json!({ "deployed_at": "2024-01-01", "build_id": 42 })
+			),
+			json.get(id).map(|v| Value::Object(v.clone())).as_ref()
+		);
+		assert_eq!(None, json.get("ffffff1234"));
+		assert_eq!(
+			json.get("a1b2c3d4"),
+			CommitMetadata::parse_json(
+				r#"{ "a1b2c3d4": { "deployed_at": "2024-01-01", "build_id": 42 } }"#
+			)?
+			.get(id)
+		);
+		assert!(CommitMetadata::parse_json(r#"[{ "build_id": 42 }]"#).is_err());
+
+		let csv = CommitMetadata::parse_csv(
+			"build_id,sha,note\n42,a1b2c3d,\"deployed, \
+			 \"\"canary\"\"\"\n43,ffffff0,\n",
+		)?;
+		assert_eq!(
+			Some(
+				&serde_json::json!({ "build_id": "42", "note": "deployed, \"canary\"" })
+			),
+			csv.get(id).map(|v| Value::Object(v.clone())).as_ref()
+		);
+		assert_eq!(
+			Some(&serde_json::json!({ "build_id": "43" })),
+			csv.get("ffffff0")
+				.map(|v| Value::Object(v.clone()))
+				.as_ref()
+		);
+		assert!(CommitMetadata::parse_csv("id,build_id\n1,2").is_err());
+
+		let csv = CommitMetadata::parse_csv(
+			"sha,note\r\n\r\na1b2c3d,\"first line\r\nsecond line\"\r\n",
+		)?;
+		assert_eq!(
+			Some(&serde_json::json!({ "note": "first line\r\nsecond line" })),
+			csv.get(id).map(|v| Value::Object(v.clone())).as_ref()
+		);
+		assert!(
+			CommitMetadata::parse_csv("sha,note\na1b2c3d,\"unterminated\n").is_err()
+		);
+		assert!(
+			CommitMetadata::parse_csv("sha,note\na1b2c3d,x\na1b2c3d4,y\n").is_err()
+		);
+		assert!(CommitMetadata::parse_json(
+			r#"{ "a1b2c3d": {}, "a1b2c3d4e5": {}, "b1b2c3d": {} }"#
+		)
+		.is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,175 @@
+use crate::error::Result;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Metrics of a changelog generation.
+///
+/// The metrics are written in the text exposition format of Prometheus so
+/// that they can be picked up by a textfile collector (e.g. the one of
+/// node_exporter).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metrics {
+	/// Time of the generation (Unix timestamp).
+	pub timestamp:       i64,
+	/// Number of commits that are processed.
+	pub commits:         usize,
+	/// Number of releases that are rendered.
+	pub releases:        usize,
+	/// Number of requests that are sent to the remote API.
+	pub remote_requests: usize,
+	/// Durations of the phases of the generation in the order they are run.
+	pub phases:          Vec<(String, Duration)>,
+	/// Whether the generation is failed.
+	pub failed:          bool,
+}
+
+impl Metrics {
+	/// Records the duration of the given phase.
+	///
+	/// The durations of a phase that is run more than once are summed up.
+	pub fn add_phase(&mut self, name: &str, duration: Duration) {
+		match self.phases.iter_mut().find(|(phase, _)| phase == name) {
+			Some((_, total)) => *total += duration,
+			None => self.phases.push((name.to_string(), duration)),
+		}
+	}
+
+	/// Writes the metrics to the given file.
+	///
+	/// The metrics are written to a temporary file first and then it is
+	/// renamed so that the collectors never read a partially written file.
+	pub fn write(&self, path: &Path) -> Result<()> {
+		let mut temp_path = path.as_os_str().to_os_string();
+		temp_path.push(".tmp");
+		fs::write(&temp_path, self.to_string())?;
+		fs::rename(&temp_path, path)?;
+		Ok(())
+	}
+}
+
+/// Writes a single metric along with its help text and type.
+///
+/// The samples are the pairs of labels (e.g. `phase="render"`) and values.
+fn write_metric(
+	f: &mut fmt::Formatter<'_>,
+	name: &str,
+	help: &str,
+	samples: &[(String, String)],
+) -> fmt::Result {
+	writeln!(f, "# HELP git_cliff_{name} {help}")?;
+	writeln!(f, "# TYPE git_cliff_{name} gauge")?;
+	for (labels, value) in samples {
+		if labels.is_empty() {
+			writeln!(f, "git_cliff_{name} {value}")?;
+		} else {
+			writeln!(f, "git_cliff_{name}{{{labels}}} {value}")?;
+		}
+	}
+	Ok(())
+}
+
+impl fmt::Display for Metrics {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let value = |v: String| vec![(String::new(), v)];
+		let total = self.phases.iter().map(|(_, v)| *v).sum::<Duration>();
+		write_metric(f, "info", "Version of git-cliff.", &[(
+			format!(r#"version="{}""#, env!("CARGO_PKG_VERSION")),
+			String::from("1"),
+		)])?;
+		write_metric(
+			f,
+			"last_run_timestamp_seconds",
+			"Time of the last generation.",
+			&value(self.timestamp.to_string()),
+		)?;
+		write_metric(
+			f,
+			"last_run_success",
+			"Whether the last generation is succeeded.",
+			&value(u8::from(!self.failed).to_string()),
+		)?;
+		write_metric(
+			f,
+			"commits_processed",
+			"Number of processed commits.",
+			&value(self.commits.to_string()),
+		)?;
+		write_metric(
+			f,
+			"releases_rendered",
+			"Number of rendered releases.",
+			&value(self.releases.to_string()),
+		)?;
+		write_metric(
+			f,
+			"remote_requests",
+			"Number of requests sent to the remote API.",
+			&value(self.remote_requests.to_string()),
+		)?;
+		write_metric(
+			f,
+			"phase_duration_seconds",
+			"Duration of the generation phases.",
+			&self
+				.phases
+				.iter()
+				.map(|(name, duration)| {
+					(
+						format!(r#"phase="{name}""#),
+						duration.as_secs_f64().to_string(),
+					)
+				})
+				.collect::<Vec<(String, String)>>(),
+		)?;
+		write_metric(
+			f,
+			"duration_seconds",
+			"Total duration of the generation.",
+			&value(total.as_secs_f64().to_string()),
+		)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn write_metrics() -> Result<()> {
+		let mut metrics = Metrics {
+			timestamp: 1714953600,
+			commits: 42,
+			releases: 3,
+			remote_requests: 5,
+			..Default::default()
+		};
+		metrics.add_phase("repository", Duration::from_millis(250));
+		metrics.add_phase("render", Duration::from_millis(500));
+		metrics.add_phase("repository", Duration::from_millis(250));
+		assert_eq!(2, metrics.phases.len());
+
+		let text = metrics.to_string();
+		for line in [
+			"# TYPE git_cliff_commits_processed gauge",
+			"git_cliff_last_run_timestamp_seconds 1714953600",
+			"git_cliff_last_run_success 1",
+			"git_cliff_commits_processed 42",
+			"git_cliff_releases_rendered 3",
+			"git_cliff_remote_requests 5",
+			r#"git_cliff_phase_duration_seconds{phase="repository"} 0.5"#,
+			r#"git_cliff_phase_duration_seconds{phase="render"} 0.5"#,
+			"git_cliff_duration_seconds 1",
+		] {
+			assert!(text.lines().any(|v| v == line), "{line} is not found");
+		}
+
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("metrics.prom");
+		metrics.write(&path)?;
+		assert_eq!(text, fs::read_to_string(&path)?);
+		Ok(())
+	}
+}
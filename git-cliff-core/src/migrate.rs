@@ -0,0 +1,441 @@
+use crate::error::{
+	Error,
+	Result,
+};
+use std::fmt;
+use toml_edit::{
+	Document,
+	InlineTable,
+	Item,
+	Table,
+	Value,
+};
+
+/// Changes of the configuration schema.
+///
+/// The changes are applied in order by [`migrate`]. Only the keys and values
+/// that are deprecated in a released version are listed here, there are none
+/// yet.
+const CONFIG_CHANGES: &[ConfigChange] = &[];
+
+/// Tables that contain the configuration in the metadata files.
+const METADATA_TABLES: &[&str] = &[
+	"workspace.metadata.git-cliff",
+	"package.metadata.git-cliff",
+	"tool.git-cliff",
+];
+
+/// Deprecated configuration key or value.
+#[derive(Debug)]
+struct ConfigChange {
+	/// Path of the key (e.g. `bump.overrides.*.bump`).
+	///
+	/// `*` matches any key of a table or any element of an array.
+	key:        &'static str,
+	/// Keys that are not matched by the last `*` of the path.
+	except:     &'static [&'static str],
+	/// Values that the key is renamed for (any value if empty).
+	values:     &'static [&'static str],
+	/// New name of the key if it is renamed.
+	///
+	/// It is relative to the table of the key and it can contain a dot for
+	/// moving the key into a subtable. `*` is replaced with the name of the
+	/// key (e.g. `types.*`).
+	renamed_to: Option<&'static str>,
+	/// Deprecated value of the key and its replacement.
+	value:      Option<(&'static str, &'static str)>,
+}
+
+/// Change that is made while migrating the configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+	/// Path of the changed key (e.g. `bump.overrides[0].bump`).
+	pub key:         String,
+	/// Description of the change.
+	pub description: String,
+}
+
+impl fmt::Display for Migration {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {}", self.key, self.description)
+	}
+}
+
+/// Rewrites the deprecated keys and values of the configuration to the
+/// current schema.
+///
+/// The configuration can also be in the metadata section of `Cargo.toml` or
+/// `pyproject.toml`. The formatting and the comments are preserved (except
+/// for the comments of the renamed keys). Returns the migrated configuration
+/// and the changes.
+pub fn migrate(content: &str) -> Result<(String, Vec<Migration>)> {
+	apply_changes(content, CONFIG_CHANGES)
+}
+
+/// Applies the given changes to the configuration.
+fn apply_changes(
+	content: &str,
+	changes: &[ConfigChange],
+) -> Result<(String, Vec<Migration>)> {
+	let mut document = content.parse::<Document>().map_err(|e| {
+		Error::ArgumentError(format!("cannot parse the configuration: {e}"))
+	})?;
+	let mut migrations = Vec::new();
+	let root = METADATA_TABLES
+		.iter()
+		.find(|path| find_item(document.as_item(), path).is_some())
+		.copied();
+	let root_item = match root {
+		Some(path) => find_item_mut(document.as_item_mut(), path),
+		None => Some(document.as_item_mut()),
+	};
+	if let Some(root_item) = root_item {
+		for change in changes {
+			let segments = change.key.split('.').collect::<Vec<&str>>();
+			apply_change(
+				root_item,
+				root.unwrap_or_default(),
+				&segments,
+				change,
+				&mut migrations,
+			);
+		}
+	}
+	Ok((document.to_string(), migrations))
+}
+
+/// Returns the item at the given dotted path.
+fn find_item<'a>(item: &'a Item, path: &str) -> Option<&'a Item> {
+	path.split('.').try_fold(item, |item, key| item.get(key))
+}
+
+/// Returns the mutable item at the given dotted path.
+fn find_item_mut<'a>(item: &'a mut Item, path: &str) -> Option<&'a mut Item> {
+	path.split('.')
+		.try_fold(item, |item, key| item.get_mut(key))
+}
+
+/// Joins the key to the path.
+fn join_key(path: &str, key: &str) -> String {
+	if path.is_empty() {
+		key.to_string()
+	} else {
+		format!("{path}.{key}")
+	}
+}
+
+/// Applies the change to the items that match the remaining segments of the
+/// key.
+fn apply_change(
+	item: &mut Item,
+	path: &str,
+	segments: &[&str],
+	change: &ConfigChange,
+	migrations: &mut Vec<Migration>,
+) {
+	let Some((segment, rest)) = segments.split_first() else {
+		return;
+	};
+	if rest.is_empty() {
+		let inline = !item.is_table();
+		let Some(table) = item.as_table_like_mut() else {
+			return;
+		};
+		let keys = if *segment == "*" {
+			table
+				.iter()
+				.map(|(key, _)| key.to_string())
+				.filter(|key| !change.except.contains(&key.as_str()))
+				.collect()
+		} else {
+			vec![segment.to_string()]
+		};
+		for key in keys {
+			apply_to_key(table, inline, path, &key, change, migrations);
+		}
+		return;
+	}
+	match item {
+		Item::ArrayOfTables(array) if *segment == "*" => {
+			for (i, table) in array.iter_mut().enumerate() {
+				let mut table_item = Item::Table(table.clone());
+				apply_change(
+					&mut table_item,
+					&format!("{path}[{i}]"),
+					rest,
+					change,
+					migrations,
+				);
+				if let Item::Table(migrated) = table_item {
+					*table = migrated;
+				}
+			}
+		}
+		Item::Value(Value::Array(array)) if *segment == "*" => {
+			for (i, value) in array.iter_mut().enumerate() {
+				let mut value_item = Item::Value(value.clone());
+				apply_change(
+					&mut value_item,
+					&format!("{path}[{i}]"),
+					rest,
+					change,
+					migrations,
+				);
+				if let Item::Value(migrated) = value_item {
+					*value = migrated;
+				}
+			}
+		}
+		_ => {
+			let Some(table) = item.as_table_like_mut() else {
+				return;
+			};
+			let keys = if *segment == "*" {
+				table.iter().map(|(key, _)| key.to_string()).collect()
+			} else {
+				vec![segment.to_string()]
+			};
+			for key in keys {
+				if let Some(child) = table.get_mut(&key) {
+					apply_change(
+						child,
+						&join_key(path, &key),
+						rest,
+						change,
+						migrations,
+					);
+				}
+			}
+		}
+	}
+}
+
+/// Applies the change to the key of the table.
+///
+/// The subtables are created as inline tables if the table is inline.
+fn apply_to_key(
+	table: &mut dyn toml_edit::TableLike,
+	inline: bool,
+	path: &str,
+	key: &str,
+	change: &ConfigChange,
+	migrations: &mut Vec<Migration>,
+) {
+	let full_key = join_key(path, key);
+	if let Some((from, to)) = change.value {
+		if let Some(value) = table.get_mut(key).and_then(|v| v.as_value_mut()) {
+			if value.as_str() == Some(from) {
+				let decor = value.decor().clone();
+				*value = Value::from(to);
+				*value.decor_mut() = decor;
+				migrations.push(Migration {
+					key:         full_key.clone(),
+					description: format!("`{from}` is replaced with `{to}`"),
+				});
+			}
+		}
+	}
+	if let Some(renamed_to) = change.renamed_to {
+		let renamed_to = renamed_to.replace('*', key);
+		let value = table.get(key).and_then(|v| v.as_str());
+		if !change.values.is_empty() &&
+			!value.is_some_and(|v| change.values.contains(&v))
+		{
+			return;
+		}
+		let (subtable, new_key) = match renamed_to.split_once('.') {
+			Some((subtable, new_key)) => (Some(subtable), new_key),
+			None => (None, renamed_to.as_str()),
+		};
+		let exists = match subtable {
+			Some(subtable) => table
+				.get(subtable)
+				.map(|v| v.as_table_like().is_none() || v.get(new_key).is_some()),
+			None => Some(table.contains_key(new_key)),
+		};
+		if exists == Some(true) {
+			return;
+		}
+		let Some(item) = table.remove(key) else {
+			return;
+		};
+		let target = match subtable {
+			Some(subtable) => {
+				if !table.contains_key(subtable) {
+					table.insert(
+						subtable,
+						if inline {
+							Item::Value(Value::InlineTable(InlineTable::new()))
+						} else {
+							Item::Table(Table::new())
+						},
+					);
+				}
+				match table.get_mut(subtable).and_then(|v| v.as_table_like_mut()) {
+					Some(target) => target,
+					None => return,
+				}
+			}
+			None => table,
+		};
+		target.insert(new_key, item);
+		migrations.push(Migration {
+			key:         full_key,
+			description: format!("renamed to `{}`", join_key(path, &renamed_to)),
+		});
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	/// Changes for testing the migrations, the keys do not exist.
+	const TEST_CHANGES: &[ConfigChange] = &[
+		ConfigChange {
+			key:        "table.*",
+			except:     &["items", "nested"],
+			values:     &[],
+			renamed_to: Some("nested.*"),
+			value:      None,
+		},
+		ConfigChange {
+			key:        "table.nested.*",
+			except:     &[],
+			values:     &[],
+			renamed_to: None,
+			value:      Some(("old", "new")),
+		},
+		ConfigChange {
+			key:        "table.items.*.level",
+			except:     &[],
+			values:     &[],
+			renamed_to: None,
+			value:      Some(("old", "new")),
+		},
+		ConfigChange {
+			key:        "git.old_key",
+			except:     &[],
+			values:     &["a", "b"],
+			renamed_to: Some("new_key"),
+			value:      None,
+		},
+	];
+
+	#[test]
+	fn migrate_config() -> Result<()> {
+		let content =
+			"[changelog]\nbody = \"\"\n\n[git]\nsort_commits = \"oldest\"\n";
+		assert_eq!((content.to_string(), vec![]), migrate(content)?);
+		Ok(())
+	}
+
+	#[test]
+	fn apply_config_changes() -> Result<()> {
+		let (content, migrations) = apply_changes(
+			r#"[git]
+old_key = "a"
+
+# table configuration
+[table]
+first = "old" # comment
+second = "value"
+
+[[table.items]]
+name = "item"
+level = "old"
+"#,
+			TEST_CHANGES,
+		)?;
+		assert_eq!(
+			r#"[git]
+new_key = "a"
+
+# table configuration
+[table]
+
+[[table.items]]
+name = "item"
+level = "new"
+
+[table.nested]
+first = "new" # comment
+second = "value"
+"#,
+			content
+		);
+		assert_eq!(
+			vec![
+				"table.first: renamed to `table.nested.first`",
+				"table.second: renamed to `table.nested.second`",
+				"table.nested.first: `old` is replaced with `new`",
+				"table.items[0].level: `old` is replaced with `new`",
+				"git.old_key: renamed to `git.new_key`",
+			],
+			migrations
+				.iter()
+				.map(|v| v.to_string())
+				.collect::<Vec<String>>()
+		);
+
+		let (content, migrations) = apply_changes(
+			"[package]\nname = \"x\"\n\n[package.metadata.git-cliff]\ntable = { \
+			 key = \"old\" }\n",
+			TEST_CHANGES,
+		)?;
+		assert_eq!(
+			"[package]\nname = \"x\"\n\n[package.metadata.git-cliff]\ntable = { \
+			 nested = { key = \"new\" } }\n",
+			content
+		);
+		assert_eq!(
+			vec![
+				"package.metadata.git-cliff.table.key: renamed to \
+				 `package.metadata.git-cliff.table.nested.key`",
+				"package.metadata.git-cliff.table.nested.key: `old` is replaced \
+				 with `new`",
+			],
+			migrations
+				.iter()
+				.map(|v| v.to_string())
+				.collect::<Vec<String>>()
+		);
+
+		let content = "[git]\nold_key = \"c\"\n";
+		assert_eq!(
+			(content.to_string(), vec![]),
+			apply_changes(content, TEST_CHANGES)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn rename_key() {
+		let mut document = "[git]\nold_key = true # comment\n"
+			.parse::<Document>()
+			.expect("invalid TOML");
+		let mut migrations = Vec::new();
+		apply_change(
+			document.as_item_mut(),
+			"",
+			&["git", "old_key"],
+			&ConfigChange {
+				key:        "git.old_key",
+				except:     &[],
+				values:     &[],
+				renamed_to: Some("new_key"),
+				value:      None,
+			},
+			&mut migrations,
+		);
+		assert_eq!("[git]\nnew_key = true # comment\n", document.to_string());
+		assert_eq!(
+			vec![Migration {
+				key:         String::from("git.old_key"),
+				description: String::from("renamed to `git.new_key`"),
+			}],
+			migrations
+		);
+	}
+}
@@ -0,0 +1,332 @@
+use crate::error::{
+	Error,
+	Result,
+};
+use regex::Regex;
+use semver::Version;
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Segment of a query path.
+#[derive(Debug, Clone)]
+enum Segment {
+	/// Field of an object, e.g. `.version`.
+	Key(String),
+	/// Elements of an array or values of an object, e.g. `[]`.
+	Iterate,
+	/// Element of an array, e.g. `[0]` or `[-1]`.
+	Index(i64),
+	/// Slice of an array, e.g. `[1:3]`.
+	Slice(Option<i64>, Option<i64>),
+	/// Filter of the values, e.g. `[?breaking]` or `[?scope=="app"]`.
+	Filter(Vec<Segment>, Option<(Operator, Value)>),
+}
+
+/// Comparison operator of a filter.
+#[derive(Debug, Clone)]
+enum Operator {
+	/// `==`
+	Equal,
+	/// `!=`
+	NotEqual,
+	/// `=~`
+	Match(Regex),
+	/// `>`
+	Greater,
+	/// `>=`
+	GreaterOrEqual,
+	/// `<`
+	Less,
+	/// `<=`
+	LessOrEqual,
+}
+
+/// Runs the given query on the value and returns the results.
+///
+/// The query is a path that is similar to [jq](https://jqlang.github.io/jq/)
+/// filters, e.g. `.[].commits[][?breaking].message` returns the messages of
+/// the breaking commits.
+pub fn query(value: &Value, path: &str) -> Result<Vec<Value>> {
+	let segments = parse_path(path)?;
+	Ok(evaluate(vec![value.clone()], &segments))
+}
+
+/// Parses the query path into segments.
+fn parse_path(path: &str) -> Result<Vec<Segment>> {
+	let mut segments = Vec::new();
+	let chars: Vec<char> = path.trim().chars().collect();
+	let mut i = 0;
+	while i < chars.len() {
+		match chars[i] {
+			'.' => {
+				i += 1;
+				let start = i;
+				while i < chars.len() &&
+					(chars[i].is_alphanumeric() || chars[i] == '_')
+				{
+					i += 1;
+				}
+				if i > start {
+					segments.push(Segment::Key(chars[start..i].iter().collect()));
+				}
+			}
+			'[' => {
+				let start = i + 1;
+				let mut in_string = false;
+				i = start;
+				while i < chars.len() && (in_string || chars[i] != ']') {
+					if chars[i] == '"' && chars[i - 1] != '\\' {
+						in_string = !in_string;
+					}
+					i += 1;
+				}
+				if i == chars.len() {
+					return Err(Error::QueryError(String::from("expected `]`")));
+				}
+				let content: String = chars[start..i].iter().collect();
+				segments.push(parse_bracket(content.trim())?);
+				i += 1;
+			}
+			c if c.is_whitespace() => i += 1,
+			c => {
+				return Err(Error::QueryError(format!(
+					"unexpected character: {c:?}"
+				)));
+			}
+		}
+	}
+	Ok(segments)
+}
+
+/// Parses the content of the brackets.
+fn parse_bracket(content: &str) -> Result<Segment> {
+	let parse_index = |v: &str| -> Result<Option<i64>> {
+		let v = v.trim();
+		if v.is_empty() {
+			Ok(None)
+		} else {
+			v.parse()
+				.map(Some)
+				.map_err(|_| Error::QueryError(format!("invalid index: {v:?}")))
+		}
+	};
+	if content.is_empty() {
+		Ok(Segment::Iterate)
+	} else if let Some(filter) = content.strip_prefix('?') {
+		parse_filter(filter)
+	} else if let Some((start, end)) = content.split_once(':') {
+		Ok(Segment::Slice(parse_index(start)?, parse_index(end)?))
+	} else {
+		parse_index(content)?
+			.map(Segment::Index)
+			.ok_or_else(|| Error::QueryError(String::from("expected an index")))
+	}
+}
+
+/// Parses a filter, e.g. `author.name == "John"`.
+fn parse_filter(filter: &str) -> Result<Segment> {
+	let relative_path = |path: &str| {
+		let path = path.trim();
+		if path.starts_with('.') || path.starts_with('[') {
+			parse_path(path)
+		} else {
+			parse_path(&format!(".{path}"))
+		}
+	};
+	let mut in_string = false;
+	let mut position = None;
+	for (i, c) in filter.char_indices() {
+		if c == '"' {
+			in_string = !in_string;
+		} else if !in_string && "=!<>".contains(c) {
+			position = Some(i);
+			break;
+		}
+	}
+	if let Some(i) = position {
+		let operator = ["==", "!=", "=~", ">=", "<=", ">", "<"]
+			.into_iter()
+			.find(|operator| filter[i..].starts_with(operator))
+			.ok_or_else(|| {
+				Error::QueryError(format!("invalid operator in filter: {filter:?}"))
+			})?;
+		let (path, literal) = (&filter[..i], &filter[i + operator.len()..]);
+		let literal: Value = serde_json::from_str(literal.trim()).map_err(|_| {
+			Error::QueryError(format!("invalid value: {:?}", literal.trim()))
+		})?;
+		let operator =
+			match operator {
+				"==" => Operator::Equal,
+				"!=" => Operator::NotEqual,
+				"=~" => Operator::Match(Regex::new(literal.as_str().ok_or_else(
+					|| Error::QueryError(String::from("expected a regex string")),
+				)?)?),
+				">=" => Operator::GreaterOrEqual,
+				"<=" => Operator::LessOrEqual,
+				">" => Operator::Greater,
+				_ => Operator::Less,
+			};
+		return Ok(Segment::Filter(
+			relative_path(path)?,
+			Some((operator, literal)),
+		));
+	}
+	Ok(Segment::Filter(relative_path(filter)?, None))
+}
+
+/// Compares the values.
+///
+/// The strings are compared as semantic versions if possible so that the
+/// releases can be compared by their versions.
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+	let version = |v: &str| Version::parse(v.trim_start_matches('v')).ok();
+	match (lhs, rhs) {
+		(Value::Number(lhs), Value::Number(rhs)) => {
+			lhs.as_f64()?.partial_cmp(&rhs.as_f64()?)
+		}
+		(Value::String(lhs), Value::String(rhs)) => {
+			match (version(lhs), version(rhs)) {
+				(Some(lhs), Some(rhs)) => Some(lhs.cmp(&rhs)),
+				_ => Some(lhs.cmp(rhs)),
+			}
+		}
+		_ => None,
+	}
+}
+
+/// Evaluates the segments on the given values.
+fn evaluate(mut values: Vec<Value>, segments: &[Segment]) -> Vec<Value> {
+	let index = |array: &Vec<Value>, i: i64| -> Option<usize> {
+		let i = if i < 0 { array.len() as i64 + i } else { i };
+		usize::try_from(i).ok().map(|i| i.min(array.len()))
+	};
+	for segment in segments {
+		values = values
+			.into_iter()
+			.flat_map(|value| match (segment, value) {
+				(Segment::Key(key), Value::Object(mut object)) => {
+					vec![object.remove(key).unwrap_or_default()]
+				}
+				(Segment::Key(_), _) => vec![Value::Null],
+				(Segment::Iterate, Value::Array(array)) => array,
+				(Segment::Iterate, Value::Object(object)) => {
+					object.into_iter().map(|(_, v)| v).collect()
+				}
+				(Segment::Iterate, _) => vec![],
+				(Segment::Index(i), Value::Array(array)) => {
+					vec![index(&array, *i)
+						.and_then(|i| array.get(i).cloned())
+						.unwrap_or_default()]
+				}
+				(Segment::Index(_), _) => vec![Value::Null],
+				(Segment::Slice(start, end), Value::Array(array)) => {
+					let start = start.and_then(|i| index(&array, i)).unwrap_or(0);
+					let end = end
+						.and_then(|i| index(&array, i))
+						.unwrap_or(array.len())
+						.max(start);
+					vec![Value::Array(array[start..end].to_vec())]
+				}
+				(Segment::Slice(..), _) => vec![Value::Null],
+				(Segment::Filter(path, comparison), value) => {
+					let matched =
+						evaluate(vec![value.clone()], path).iter().any(|v| {
+							match comparison {
+								None => {
+									!matches!(v, Value::Null | Value::Bool(false))
+								}
+								Some((operator, literal)) => match operator {
+									Operator::Equal => v == literal,
+									Operator::NotEqual => v != literal,
+									Operator::Match(regex) => {
+										v.as_str().is_some_and(|v| regex.is_match(v))
+									}
+									Operator::Greater => {
+										compare(v, literal) ==
+											Some(Ordering::Greater)
+									}
+									Operator::GreaterOrEqual => matches!(
+										compare(v, literal),
+										Some(Ordering::Greater | Ordering::Equal)
+									),
+									Operator::Less => {
+										compare(v, literal) == Some(Ordering::Less)
+									}
+									Operator::LessOrEqual => matches!(
+										compare(v, literal),
+										Some(Ordering::Less | Ordering::Equal)
+									),
+								},
+							}
+						});
+					if matched {
+						vec![value]
+					} else {
+						vec![]
+					}
+				}
+			})
+			.collect();
+	}
+	values
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn query_context() -> Result<()> {
+		let context = json!([
+			{
+				"version": null,
+				"commits": [
+					{ "message": "add x", "breaking": true, "author": { "name": "a" } },
+					{ "message": "fix y", "breaking": false, "author": { "name": "b" } }
+				]
+			},
+			{
+				"version": "v2.0.0",
+				"commits": [
+					{ "message": "add z", "breaking": false, "author": { "name": "a" } }
+				]
+			},
+			{
+				"version": "v1.10.0",
+				"commits": [
+					{ "message": "remove w", "breaking": true, "author": { "name": "b" } }
+				]
+			}
+		]);
+		for (path, expected) in [
+			(
+				".[].commits[][?breaking].message",
+				json!(["add x", "remove w"]),
+			),
+			(
+				r#".[].commits[][?author.name == "a"].message"#,
+				json!(["add x", "add z"]),
+			),
+			(r#".[][?version >= "v2.0.0"].version"#, json!(["v2.0.0"])),
+			(r#".[][?version < "v2.0.0"].version"#, json!(["v1.10.0"])),
+			(
+				r#".[].commits[][?message =~ "^add"].message"#,
+				json!(["add x", "add z"]),
+			),
+			(".[-1].version", json!(["v1.10.0"])),
+			(".[1:][].version", json!(["v2.0.0", "v1.10.0"])),
+			(".[0].missing", json!([null])),
+		] {
+			assert_eq!(
+				expected.as_array().cloned().unwrap_or_default(),
+				query(&context, path)?,
+				"{path}"
+			);
+		}
+		for path in [".[", ".[x]", ".commits[?version == v1]", "version"] {
+			assert!(query(&context, path).is_err(), "{path}");
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,400 @@
+use crate::config::Config;
+use crate::embed::EmbeddedConfig;
+use crate::error::{
+	Error,
+	Result,
+};
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+use secrecy::SecretString;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Regex for matching the intra-doc links (e.g. [`x`](Config::x)).
+static DOC_LINK_REGEX: Lazy<Regex> = lazy_regex!(r"\[(`[^`]+`)\]\([^)]*\)");
+
+/// Type of a configuration value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueType {
+	/// Boolean.
+	Boolean,
+	/// Integer.
+	Integer,
+	/// String.
+	String,
+	/// Regular expression.
+	Regex,
+	/// Path of a file.
+	Path,
+	/// String with one of the given values.
+	Enum(Vec<&'static str>),
+	/// Array of the values of the given type.
+	Array(Box<ValueType>),
+	/// Table with arbitrary keys and the values of the given type.
+	Map(Box<ValueType>),
+	/// Table with the given fields.
+	Table(Vec<ConfigField>),
+}
+
+/// Field of a configuration table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigField {
+	/// Name of the field in the configuration file.
+	pub name:        &'static str,
+	/// Description of the field (its doc comments).
+	pub description: &'static str,
+	/// Type of the value.
+	pub value_type:  ValueType,
+}
+
+/// Type that can be a part of the configuration file.
+///
+/// This is implemented for the configuration structs and enums via
+/// `#[derive(ConfigReference)]`.
+pub trait ConfigReference {
+	/// Returns the type of the value.
+	fn value_type() -> ValueType;
+}
+
+/// Implements [`ConfigReference`] for the types with the given value type.
+macro_rules! impl_config_reference {
+	($value_type: expr, $($type: ty),+) => {
+		$(impl ConfigReference for $type {
+			fn value_type() -> ValueType {
+				$value_type
+			}
+		})+
+	};
+}
+
+impl_config_reference!(ValueType::Boolean, bool);
+impl_config_reference!(ValueType::Integer, usize, u64, u32, i64);
+impl_config_reference!(ValueType::String, String, SecretString);
+impl_config_reference!(ValueType::Regex, Regex);
+impl_config_reference!(ValueType::Path, PathBuf);
+
+impl<T: ConfigReference> ConfigReference for Option<T> {
+	fn value_type() -> ValueType {
+		T::value_type()
+	}
+}
+
+impl<T: ConfigReference> ConfigReference for Vec<T> {
+	fn value_type() -> ValueType {
+		ValueType::Array(Box::new(T::value_type()))
+	}
+}
+
+impl<T: ConfigReference> ConfigReference for HashMap<String, T> {
+	fn value_type() -> ValueType {
+		ValueType::Map(Box::new(T::value_type()))
+	}
+}
+
+impl fmt::Display for ValueType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Boolean => write!(f, "boolean"),
+			Self::Integer => write!(f, "integer"),
+			Self::String => write!(f, "string"),
+			Self::Regex => write!(f, "string (regex)"),
+			Self::Path => write!(f, "string (path)"),
+			Self::Enum(variants) => write!(
+				f,
+				"string ({})",
+				variants
+					.iter()
+					.map(|v| format!("\"{v}\""))
+					.collect::<Vec<String>>()
+					.join(" | ")
+			),
+			Self::Array(value_type) if matches!(**value_type, Self::Table(_)) => {
+				write!(f, "array of tables")
+			}
+			Self::Array(value_type) => write!(f, "array of {value_type}s"),
+			Self::Map(value_type) => write!(f, "table of {value_type}s"),
+			Self::Table(_) => write!(f, "table"),
+		}
+	}
+}
+
+/// Option of the configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigOption {
+	/// Key of the option (e.g. `git.commit_parsers[].message`).
+	pub key:         String,
+	/// Type of the value.
+	pub value_type:  String,
+	/// Description of the option.
+	pub description: String,
+	/// Value of the option in the default configuration.
+	pub default:     Option<String>,
+}
+
+impl ConfigOption {
+	/// Returns the first paragraph of the description.
+	pub fn summary(&self) -> &str {
+		self.description.split("\n\n").next().unwrap_or_default()
+	}
+}
+
+impl fmt::Display for ConfigOption {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "{}", self.key)?;
+		writeln!(f, "Type: {}", self.value_type)?;
+		if let Some(default) = &self.default {
+			writeln!(f, "Default: {default}")?;
+		}
+		write!(f, "\n{}", self.description)
+	}
+}
+
+/// Collects the options of the fields recursively.
+fn collect_options(
+	fields: &[ConfigField],
+	prefix: &str,
+	options: &mut Vec<ConfigOption>,
+) {
+	for field in fields {
+		let key = format!("{prefix}{}", field.name);
+		options.push(ConfigOption {
+			key:         key.clone(),
+			value_type:  field.value_type.to_string(),
+			description: DOC_LINK_REGEX
+				.replace_all(field.description, "$1")
+				.to_string(),
+			default:     None,
+		});
+		match &field.value_type {
+			ValueType::Table(fields) => {
+				collect_options(fields, &format!("{key}."), options)
+			}
+			ValueType::Array(value_type) => {
+				if let ValueType::Table(fields) = &**value_type {
+					collect_options(fields, &format!("{key}[]."), options)
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Returns the value of the option in the given configuration.
+fn find_value<'a>(config: &'a toml::Value, key: &str) -> Option<&'a toml::Value> {
+	key.split('.')
+		.try_fold(config, |value, segment| value.get(segment))
+}
+
+/// Returns the options of the configuration file.
+///
+/// The options are generated from the configuration structs and their doc
+/// comments (see [`ConfigReference`]). The default values are read from the
+/// default configuration.
+pub fn options() -> Result<Vec<ConfigOption>> {
+	let ValueType::Table(fields) = Config::value_type() else {
+		return Err(Error::EmbeddedError(String::from(
+			"configuration is not a table",
+		)));
+	};
+	let mut options = Vec::new();
+	collect_options(&fields, "", &mut options);
+	let default_config =
+		toml::from_str::<toml::Value>(&EmbeddedConfig::get_config()?)?;
+	for option in options.iter_mut() {
+		option.default = find_value(&default_config, &option.key)
+			.filter(|v| !v.is_table())
+			.map(|v| v.to_string());
+	}
+	Ok(options)
+}
+
+/// Returns the option with the given key.
+///
+/// The `[]` of the arrays of tables can be omitted from the key (e.g.
+/// `git.commit_parsers.message`).
+pub fn explain(key: &str) -> Result<ConfigOption> {
+	let normalize = |key: &str| key.replace("[]", "");
+	options()?
+		.into_iter()
+		.find(|option| normalize(&option.key) == normalize(key))
+		.ok_or_else(|| {
+			Error::ArgumentError(format!(
+				"{key} is not a configuration option (see `git cliff config \
+				 --list`)"
+			))
+		})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use git_cliff_derive::ConfigReference;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn derive_reference() {
+		/// Test enum.
+		#[derive(ConfigReference)]
+		#[allow(dead_code)]
+		#[serde(rename_all = "lowercase")]
+		enum Level {
+			Low,
+			#[serde(rename = "very-high")]
+			High,
+		}
+		/// Test struct.
+		#[derive(ConfigReference)]
+		#[allow(dead_code)]
+		struct Options {
+			/// Level of the
+			/// options.
+			///
+			/// See [`Level`](Level).
+			level:   Option<Level>,
+			#[serde(rename = "type")]
+			kind:    Vec<String>,
+			#[serde(skip)]
+			skipped: bool,
+		}
+		assert_eq!(
+			ValueType::Table(vec![
+				ConfigField {
+					name:        "level",
+					description: "Level of the options.\n\nSee [`Level`](Level).",
+					value_type:  ValueType::Enum(vec!["low", "very-high"]),
+				},
+				ConfigField {
+					name:        "type",
+					description: "",
+					value_type:  ValueType::Array(Box::new(ValueType::String)),
+				},
+			]),
+			Options::value_type()
+		);
+	}
+
+	#[test]
+	fn derive_reference_rename_all() {
+		macro_rules! variants {
+			($rule:literal) => {{
+				#[derive(ConfigReference)]
+				#[allow(dead_code)]
+				#[serde(rename_all = $rule)]
+				enum Order {
+					TopoOrder,
+					Newest,
+				}
+				Order::value_type()
+			}};
+		}
+		for (expected, value_type) in [
+			(vec!["topoorder", "newest"], variants!("lowercase")),
+			(vec!["TOPOORDER", "NEWEST"], variants!("UPPERCASE")),
+			(vec!["TopoOrder", "Newest"], variants!("PascalCase")),
+			(vec!["topoOrder", "newest"], variants!("camelCase")),
+			(vec!["topo_order", "newest"], variants!("snake_case")),
+			(
+				vec!["TOPO_ORDER", "NEWEST"],
+				variants!("SCREAMING_SNAKE_CASE"),
+			),
+			(vec!["topo-order", "newest"], variants!("kebab-case")),
+			(
+				vec!["TOPO-ORDER", "NEWEST"],
+				variants!("SCREAMING-KEBAB-CASE"),
+			),
+		] {
+			assert_eq!(ValueType::Enum(expected), value_type);
+		}
+
+		macro_rules! fields {
+			($rule:literal) => {{
+				#[derive(ConfigReference)]
+				#[allow(dead_code)]
+				#[serde(rename_all = $rule)]
+				struct Options {
+					sort_commits:  bool,
+					#[serde(rename = "limit")]
+					limit_commits: bool,
+				}
+				match Options::value_type() {
+					ValueType::Table(fields) => {
+						fields.into_iter().map(|v| v.name).collect::<Vec<&str>>()
+					}
+					_ => vec![],
+				}
+			}};
+		}
+		for (expected, names) in [
+			(vec!["sort_commits", "limit"], fields!("lowercase")),
+			(vec!["SORT_COMMITS", "limit"], fields!("UPPERCASE")),
+			(vec!["SortCommits", "limit"], fields!("PascalCase")),
+			(vec!["sortCommits", "limit"], fields!("camelCase")),
+			(vec!["sort_commits", "limit"], fields!("snake_case")),
+			(
+				vec!["SORT_COMMITS", "limit"],
+				fields!("SCREAMING_SNAKE_CASE"),
+			),
+			(vec!["sort-commits", "limit"], fields!("kebab-case")),
+			(
+				vec!["SORT-COMMITS", "limit"],
+				fields!("SCREAMING-KEBAB-CASE"),
+			),
+		] {
+			assert_eq!(expected, names);
+		}
+	}
+
+	#[test]
+	fn generate_reference() -> Result<()> {
+		let options = options()?;
+		let keys = options
+			.iter()
+			.map(|v| v.key.as_str())
+			.collect::<Vec<&str>>();
+		for key in [
+			"changelog",
+			"changelog.postprocessors[].pattern",
+			"git.commit_parsers[].group",
+			"remote.github.owner",
+			"bump.types",
+			"eras[].git.tag_pattern",
+		] {
+			assert!(keys.contains(&key), "{key} is not found");
+		}
+		assert!(!keys.contains(&"remote.offline"));
+
+		let default_config =
+			toml::from_str::<toml::Value>(&EmbeddedConfig::get_config()?)?;
+		for (section, values) in default_config.as_table().expect("not a table") {
+			for key in values.as_table().expect("not a table").keys() {
+				let key = format!("{section}.{key}");
+				assert!(keys.contains(&key.as_str()), "{key} is not found");
+			}
+		}
+
+		assert_eq!(
+			ConfigOption {
+				key:         String::from("git.conventional_commits"),
+				value_type:  String::from("boolean"),
+				description: String::from(
+					"Whether to enable parsing conventional commits."
+				),
+				default:     Some(String::from("true")),
+			},
+			explain("git.conventional_commits")?
+		);
+		let option = explain("git.commit_parsers.skip")?;
+		assert_eq!("git.commit_parsers[].skip", option.key);
+		assert_eq!(
+			r#"string ("week" | "month" | "quarter" | "year")"#,
+			explain("git.release_period")?.value_type
+		);
+		assert_eq!("array of tables", explain("bump.overrides")?.value_type);
+		assert!(explain("git.unknown").is_err());
+		Ok(())
+	}
+}
@@ -1,37 +1,351 @@
 use crate::commit::Commit;
+use crate::config::civil_date;
+use crate::config::{
+	BuildNumber,
+	BumpConfig,
+	BumpLevel,
+	CommitDate,
+	GitConfig,
+	ReleasePeriod,
+};
 use crate::error::Result;
 #[cfg(feature = "github")]
 use crate::github::{
 	GitHubCommit,
 	GitHubContributor,
+	GitHubMilestone,
 	GitHubPullRequest,
 	GitHubReleaseMetadata,
 };
-use next_version::VersionUpdater;
-use semver::Version;
+use crate::umbrella::Highlight;
+use git_conventional::{
+	Commit as ConventionalCommit,
+	Type,
+};
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+use next_version::NextVersion;
+use semver::{
+	BuildMetadata,
+	Version,
+};
 use serde::{
 	Deserialize,
 	Serialize,
 };
+use sha2::{
+	Digest,
+	Sha256,
+};
+use std::collections::BTreeMap;
+use std::fs::{
+	self,
+	File,
+};
+use std::io;
+use std::path::{
+	Path,
+	PathBuf,
+};
+
+/// Returns the semantic version of the given tag.
+///
+/// The prefix before the first digit (e.g. `v`) is ignored.
+pub(crate) fn tag_version(tag: &str) -> Option<Version> {
+	let index = tag.find(|c: char| c.is_ascii_digit())?;
+	Version::parse(&tag[index..]).ok()
+}
+
+/// Regular expression for matching a version with a fourth numeric component
+/// (e.g. `v1.2.3.45-rc.1`).
+static BUILD_COMPONENT_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"^(\D*\d+\.\d+\.\d+)\.(\d+)(.*)$"#);
+
+/// Identifier of the build number in the build metadata.
+const BUILD_METADATA_PREFIX: &str = "build.";
+
+/// Returns the version with the given build number in the given format.
+fn with_build_number(
+	version: &Version,
+	build: u64,
+	format: BuildNumber,
+) -> Result<String> {
+	Ok(match format {
+		BuildNumber::Metadata => Version {
+			build: BuildMetadata::new(&format!("{BUILD_METADATA_PREFIX}{build}"))?,
+			..version.clone()
+		}
+		.to_string(),
+		BuildNumber::Component => {
+			let mut component = format!(
+				"{}.{}.{}.{build}",
+				version.major, version.minor, version.patch
+			);
+			if !version.pre.is_empty() {
+				component.push_str(&format!("-{}", version.pre));
+			}
+			component
+		}
+	})
+}
+
+/// Commit trailer for overriding the increment that the commit contributes.
+pub const BUMP_TRAILER: &str = "Changelog-Bump";
+
+/// Contribution of a commit to the version bump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BumpCommit {
+	/// Commit ID.
+	pub id:      String,
+	/// First line of the commit message.
+	pub message: String,
+	/// Increment that the commit contributes.
+	pub level:   BumpLevel,
+	/// Reason of the contribution.
+	pub reason:  String,
+}
+
+/// Explanation of how the next version is calculated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BumpRationale {
+	/// Previous version.
+	pub previous_version: Option<String>,
+	/// Next version.
+	pub next_version:     String,
+	/// Applied increment.
+	pub level:            BumpLevel,
+	/// Rules that determined the increment.
+	pub rules:            Vec<String>,
+	/// Commits that triggered the increment.
+	pub commits:          Vec<BumpCommit>,
+	/// Commits whose contributions are overridden via the commit trailer or
+	/// the configuration.
+	pub overrides:        Vec<BumpCommit>,
+}
+
+impl BumpRationale {
+	/// Returns the rationale as JSON.
+	pub fn as_json(&self) -> Result<String> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+}
+
+/// Author of the commits of a release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseAuthor {
+	/// Name of the author.
+	pub name:    Option<String>,
+	/// E-mail of the author.
+	pub email:   Option<String>,
+	/// Domain of the e-mail (e.g. `example.com`).
+	pub domain:  Option<String>,
+	/// Number of the commits.
+	pub commits: usize,
+}
+
+/// Authors of the commits of a release grouped by their e-mail domain.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseAuthors {
+	/// Authors whose domain is one of the internal domains.
+	pub internal: Vec<ReleaseAuthor>,
+	/// Other authors (including the ones without an e-mail).
+	pub external: Vec<ReleaseAuthor>,
+	/// Number of the authors of each domain.
+	pub domains:  BTreeMap<String, usize>,
+}
+
+impl ReleaseAuthors {
+	/// Groups the authors of the given commits.
+	///
+	/// The authors are identified by their e-mail (or name) and sorted by
+	/// their number of commits.
+	pub fn new(commits: &[Commit], internal_domains: &[String]) -> Self {
+		let mut authors: Vec<ReleaseAuthor> = Vec::new();
+		for commit in commits {
+			let email = commit.author.email.as_ref().map(|v| v.to_lowercase());
+			let existing = authors.iter_mut().find(|author| match &email {
+				Some(email) => author.email.as_ref() == Some(email),
+				None => author.email.is_none() && author.name == commit.author.name,
+			});
+			match existing {
+				Some(author) => author.commits += 1,
+				None => authors.push(ReleaseAuthor {
+					name: commit.author.name.clone(),
+					domain: email
+						.as_ref()
+						.and_then(|v| v.rsplit_once('@'))
+						.map(|(_, domain)| domain.to_string()),
+					email,
+					commits: 1,
+				}),
+			}
+		}
+		authors.sort_by(|a, b| b.commits.cmp(&a.commits).then(a.name.cmp(&b.name)));
+		let mut release_authors = Self::default();
+		for author in authors {
+			if let Some(domain) = &author.domain {
+				*release_authors
+					.domains
+					.entry(domain.to_string())
+					.or_insert(0) += 1;
+			}
+			let internal = author.domain.as_ref().is_some_and(|domain| {
+				internal_domains.iter().any(|v| {
+					let v = v.to_lowercase();
+					*domain == v || domain.ends_with(&format!(".{v}"))
+				})
+			});
+			if internal {
+				release_authors.internal.push(author);
+			} else {
+				release_authors.external.push(author);
+			}
+		}
+		release_authors
+	}
+}
+
+/// Number of words that are read in a minute for estimating the reading time.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Size statistics of the changelog entries.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryStats {
+	/// Number of the entries.
+	pub entries:      usize,
+	/// Number of the words in the entries.
+	pub words:        usize,
+	/// Estimated reading time in minutes (rounded up).
+	pub reading_time: usize,
+}
+
+impl EntryStats {
+	/// Adds an entry with the given number of words.
+	fn add(&mut self, words: usize) {
+		self.entries += 1;
+		self.words += words;
+		self.reading_time = self.words.div_ceil(WORDS_PER_MINUTE);
+	}
+}
+
+/// Size statistics of a release.
+///
+/// The words are counted in the descriptions of the conventional commits and
+/// the first line of the other commit messages.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseStats {
+	/// Statistics of all entries.
+	#[serde(flatten)]
+	pub total:  EntryStats,
+	/// Statistics of the entries in each group.
+	pub groups: BTreeMap<String, EntryStats>,
+}
+
+impl ReleaseStats {
+	/// Computes the statistics of the given commits.
+	pub fn new(commits: &[Commit]) -> Self {
+		let mut stats = Self::default();
+		for commit in commits {
+			let description = match &commit.conv {
+				Some(conv) => conv.description(),
+				None => commit.message.lines().next().unwrap_or_default(),
+			};
+			let words = description.split_whitespace().count();
+			stats.total.add(words);
+			if let Some(group) = commit.group_name() {
+				stats.groups.entry(group).or_default().add(words);
+			}
+		}
+		stats
+	}
+}
+
+/// Artifact of a release (e.g. an archive of the binaries).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+	/// File name of the asset.
+	pub name:   String,
+	/// Size of the asset in bytes.
+	pub size:   u64,
+	/// SHA-256 checksum of the asset as a hex string.
+	pub sha256: String,
+}
+
+impl ReleaseAsset {
+	/// Reads the asset from the given file and computes its checksum.
+	pub fn from_path(path: &Path) -> Result<Self> {
+		let mut hasher = Sha256::new();
+		let size = io::copy(&mut File::open(path)?, &mut hasher)?;
+		Ok(Self {
+			name: path
+				.file_name()
+				.map(|v| v.to_string_lossy().to_string())
+				.unwrap_or_default(),
+			size,
+			sha256: format!("{:x}", hasher.finalize()),
+		})
+	}
+
+	/// Reads the assets from the given paths.
+	///
+	/// The files in the directories are included (non-recursively) in the
+	/// order of their names.
+	pub fn from_paths(paths: &[PathBuf]) -> Result<Vec<Self>> {
+		let mut assets = Vec::new();
+		for path in paths {
+			if path.is_dir() {
+				let mut files = fs::read_dir(path)?
+					.map(|entry| Ok(entry?.path()))
+					.collect::<Result<Vec<PathBuf>>>()?;
+				files.retain(|v| v.is_file());
+				files.sort();
+				for file in files {
+					assets.push(Self::from_path(&file)?);
+				}
+			} else {
+				assets.push(Self::from_path(path)?);
+			}
+		}
+		Ok(assets)
+	}
+}
 
 /// Representation of a release.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Release<'a> {
 	/// Release version, git tag.
-	pub version:   Option<String>,
+	pub version:    Option<String>,
 	/// Commits made for the release.
-	pub commits:   Vec<Commit<'a>>,
+	pub commits:    Vec<Commit<'a>>,
 	/// Commit ID of the tag.
 	#[serde(rename = "commit_id")]
-	pub commit_id: Option<String>,
+	pub commit_id:  Option<String>,
 	/// Timestamp of the release in seconds, from epoch.
-	pub timestamp: i64,
+	pub timestamp:  i64,
 	/// Previous release.
-	pub previous:  Option<Box<Release<'a>>>,
+	pub previous:   Option<Box<Release<'a>>>,
+	/// Commit ID that the tag pointed to before it was moved.
+	#[serde(rename = "moved_from")]
+	pub moved_from: Option<String>,
+	/// Authors of the commits grouped by their e-mail domain.
+	///
+	/// See [`ChangelogConfig::internal_domains`].
+	///
+	/// [`ChangelogConfig::internal_domains`]: crate::config::ChangelogConfig::internal_domains
+	pub authors:    Option<ReleaseAuthors>,
+	/// Artifacts of the release along with their checksums.
+	#[serde(default)]
+	pub assets:     Vec<ReleaseAsset>,
+	/// Size statistics of the entries.
+	#[serde(default)]
+	pub stats:      ReleaseStats,
 	/// Contributors.
 	#[cfg(feature = "github")]
-	pub github:    GitHubReleaseMetadata,
+	pub github:     GitHubReleaseMetadata,
 }
 
 impl<'a> Release<'a> {
@@ -59,23 +373,29 @@ impl<'a> Release<'a> {
 				let pull_request = github_pull_requests
 					.iter()
 					.find(|pr| pr.merge_commit_sha == Some(v.sha.clone()));
-
 				commit.github.username = v.author.clone().and_then(|v| v.login);
 				commit.github.pr_number = pull_request.map(|v| v.number);
 				commit.github.pr_title = pull_request.and_then(|v| v.title.clone());
 				commit.github.pr_labels = pull_request
 					.map(|v| v.labels.iter().map(|v| v.name.clone()).collect())
 					.unwrap_or_default();
+				commit.github.pr_release_note =
+					pull_request.and_then(|v| v.release_note());
+				commit.github.pr_merged_at =
+					pull_request.and_then(|v| v.merge_timestamp());
 				if !contributors
 					.iter()
 					.any(|v| commit.github.username == v.username)
 				{
 					contributors.push(GitHubContributor {
-						username:      commit.github.username.clone(),
-						pr_title:      commit.github.pr_title.clone(),
-						pr_number:     commit.github.pr_number,
-						pr_labels:     commit.github.pr_labels.clone(),
-						is_first_time: false,
+						username:        commit.github.username.clone(),
+						pr_title:        commit.github.pr_title.clone(),
+						pr_number:       commit.github.pr_number,
+						pr_labels:       commit.github.pr_labels.clone(),
+						pr_release_note: commit.github.pr_release_note.clone(),
+						pr_commits:      vec![],
+						is_first_time:   false,
+						pr_merged_at:    commit.github.pr_merged_at,
 					});
 				}
 				false
@@ -97,55 +417,385 @@ impl<'a> Release<'a> {
 		Ok(())
 	}
 
+	/// Sets the GitHub milestone of the release to the one that is titled
+	/// after its version (see [`GitHubMilestone::matches_version`]).
+	///
+	/// The unreleased changes do not have a milestone.
+	#[cfg(feature = "github")]
+	pub fn update_github_milestone(&mut self, milestones: &[GitHubMilestone]) {
+		self.github.milestone = self.version.as_ref().and_then(|version| {
+			milestones
+				.iter()
+				.find(|milestone| milestone.matches_version(version))
+				.cloned()
+		});
+	}
+
+	/// Removes the commits whose pull requests have one of the given labels
+	/// (case-insensitive).
+	///
+	/// The contributors who do not have any other commits in the release are
+	/// removed as well.
+	#[cfg(feature = "github")]
+	pub fn skip_labeled_commits(&mut self, labels: &[String]) {
+		self.commits.retain(|commit| {
+			!commit
+				.github
+				.pr_labels
+				.iter()
+				.any(|label| labels.iter().any(|v| v.eq_ignore_ascii_case(label)))
+		});
+		let commits = &self.commits;
+		self.github.contributors.retain(|contributor| {
+			commits
+				.iter()
+				.any(|commit| commit.github.username == contributor.username)
+		});
+	}
+
+	/// Sets the groups of the commits to the matching labels of their pull
+	/// requests.
+	///
+	/// The first capture group of the pattern is used as the group if it
+	/// exists. The commits without a matching label keep their groups.
+	#[cfg(feature = "github")]
+	pub fn group_by_labels(&mut self, pattern: &Regex) {
+		for commit in self.commits.iter_mut() {
+			if let Some(group) = commit.github.pr_labels.iter().find_map(|label| {
+				pattern
+					.captures(label)
+					.and_then(|captures| captures.get(1).or_else(|| captures.get(0)))
+					.map(|v| v.as_str().to_string())
+			}) {
+				commit.group = Some(group);
+			}
+		}
+	}
+
+	/// Sets the date of the commits to the time when their pull requests are
+	/// merged.
+	///
+	/// The commits that are not merged via a pull request keep their date.
+	#[cfg(feature = "github")]
+	pub fn set_merge_dates(&mut self) {
+		for commit in self.commits.iter_mut() {
+			if let Some(merged_at) = commit.github.pr_merged_at {
+				commit.date = merged_at;
+			}
+		}
+	}
+
+	/// Merges the commits that are associated with the same pull request into
+	/// the first one of them.
+	///
+	/// The commits are merged separately for each group so that the commits
+	/// of the pull request are still listed in all of their groups. The
+	/// message of the merged entry is the title of the pull request and the
+	/// SHA1 of the merged commits are listed in `github.pr_commits`.
+	#[cfg(feature = "github")]
+	pub fn merge_pull_request_commits(&mut self) {
+		let mut commits: Vec<Commit<'a>> = Vec::new();
+		for commit in self.commits.drain(..) {
+			if let Some(entry) = commit.github.pr_number.and_then(|number| {
+				commits.iter_mut().find(|v| {
+					v.github.pr_number == Some(number) &&
+						v.group_name() == commit.group_name()
+				})
+			}) {
+				if entry.github.pr_commits.is_empty() {
+					entry.github.pr_commits.push(entry.id.to_string());
+				}
+				entry.github.pr_commits.push(commit.id);
+				continue;
+			}
+			commits.push(commit);
+		}
+		for commit in commits.iter_mut() {
+			if let (false, Some(title)) =
+				(commit.github.pr_commits.is_empty(), &commit.github.pr_title)
+			{
+				commit.message = title.to_string();
+			}
+		}
+		self.commits = commits;
+	}
+
+	/// Splits the release into multiple releases based on the date of the
+	/// commits (see [`Commit::timestamp`]).
+	///
+	/// The releases are named after the period (see [`ReleasePeriod::name`])
+	/// and sorted from the oldest to the newest.
+	pub fn split_by_period(
+		self,
+		period: ReleasePeriod,
+		commit_date: CommitDate,
+	) -> Vec<Release<'a>> {
+		let mut releases: Vec<Release<'a>> = Vec::new();
+		for commit in self.commits {
+			let timestamp = commit.timestamp(commit_date);
+			let version = period.name(timestamp);
+			let index = match releases
+				.iter()
+				.position(|v| v.version.as_ref() == Some(&version))
+			{
+				Some(index) => index,
+				None => {
+					releases.push(Release {
+						version: Some(version),
+						..Default::default()
+					});
+					releases.len() - 1
+				}
+			};
+			let release = &mut releases[index];
+			if release.commit_id.is_none() || timestamp > release.timestamp {
+				release.commit_id = Some(commit.id.clone());
+				release.timestamp = timestamp;
+			}
+			release.commits.push(commit);
+		}
+		releases.sort_by_key(|r| r.timestamp);
+		let mut previous = self.previous;
+		for release in releases.iter_mut() {
+			release.previous = previous;
+			let mut current = release.clone();
+			current.previous = None;
+			previous = Some(Box::new(current));
+		}
+		releases
+	}
+
 	/// Calculates the next version based on the commits.
 	pub fn calculate_next_version(&self) -> Result<String> {
-		match self
+		Ok(self.bump(&BumpConfig::default())?.next_version)
+	}
+
+	/// Calculates the next version based on the commits and explains which
+	/// commits and rules determined the increment.
+	///
+	/// The increment that a commit contributes can be overridden via the
+	/// [`BUMP_TRAILER`] trailer or the [`overrides`] in the configuration.
+	///
+	/// If the [`build_number`] is configured, it is incremented along with the
+	/// version.
+	///
+	/// [`overrides`]: BumpConfig::overrides
+	/// [`build_number`]: BumpConfig::build_number
+	pub fn bump(&self, config: &BumpConfig) -> Result<BumpRationale> {
+		let Some(version) = self
 			.previous
 			.as_ref()
 			.and_then(|release| release.version.clone())
-		{
-			Some(version) => {
-				let mut semver = Version::parse(&version);
-				let mut prefix = None;
-				if semver.is_err() && version.split('.').count() >= 2 {
-					let mut found_numeric = false;
-					for (i, c) in version.chars().enumerate() {
-						if c.is_numeric() && !found_numeric {
-							found_numeric = true;
-							let version_prefix = version[..i].to_string();
-							let remaining = version[i..].to_string();
-							let version = Version::parse(&remaining);
-							if version.is_ok() {
-								semver = version;
-								prefix = Some(version_prefix);
-								break;
-							}
-						} else if !c.is_numeric() && found_numeric {
-							found_numeric = false;
-						}
+		else {
+			warn!("No releases found, using 0.1.0 as the next version.");
+			let initial_version = Version::new(0, 1, 0);
+			return Ok(BumpRationale {
+				previous_version: None,
+				next_version:     match config.build_number {
+					Some(format) => with_build_number(&initial_version, 1, format)?,
+					None => initial_version.to_string(),
+				},
+				level:            BumpLevel::None,
+				rules:            vec![String::from(
+					"there is no previous release, the initial version is used",
+				)],
+				commits:          Vec::new(),
+				overrides:        Vec::new(),
+			});
+		};
+		let mut previous_build = None;
+		let mut parsed_version = version.clone();
+		if config.build_number == Some(BuildNumber::Component) {
+			if let Some(captures) = BUILD_COMPONENT_REGEX.captures(&version) {
+				previous_build = captures[2].parse::<u64>().ok();
+				parsed_version = format!("{}{}", &captures[1], &captures[3]);
+			}
+		}
+		let version_str = parsed_version.as_str();
+		let mut semver = Version::parse(version_str);
+		let mut prefix = None;
+		if semver.is_err() && version_str.split('.').count() >= 2 {
+			let mut found_numeric = false;
+			for (i, c) in version_str.chars().enumerate() {
+				if c.is_numeric() && !found_numeric {
+					found_numeric = true;
+					let version_prefix = version_str[..i].to_string();
+					let remaining = version_str[i..].to_string();
+					let version = Version::parse(&remaining);
+					if version.is_ok() {
+						semver = version;
+						prefix = Some(version_prefix);
+						break;
 					}
+				} else if !c.is_numeric() && found_numeric {
+					found_numeric = false;
 				}
-				let next_version = VersionUpdater::new()
-					.with_features_always_increment_minor(true)
-					.with_breaking_always_increment_major(true)
-					.increment(
-						&semver?,
-						self.commits
-							.iter()
-							.map(|commit| commit.message.trim_end().to_string())
-							.collect::<Vec<String>>(),
-					)
-					.to_string();
-				if let Some(prefix) = prefix {
-					Ok(format!("{prefix}{next_version}"))
-				} else {
-					Ok(next_version)
+			}
+		}
+		let semver = semver?;
+		if config.build_number == Some(BuildNumber::Metadata) {
+			previous_build = semver
+				.build
+				.as_str()
+				.strip_prefix(BUILD_METADATA_PREFIX)
+				.and_then(|v| v.parse::<u64>().ok());
+		}
+		let mut commits = Vec::new();
+		let mut overrides = Vec::new();
+		for commit in &self.commits {
+			let (mut level, mut reason) = Self::bump_contribution(commit, config);
+			let override_level = Self::bump_override(commit, config);
+			if let Some((override_level, source)) = &override_level {
+				reason = format!("{source} (overrides {level}: {reason})");
+				level = *override_level;
+			}
+			let bump_commit = BumpCommit {
+				id: commit.id.clone(),
+				message: commit
+					.message
+					.lines()
+					.next()
+					.unwrap_or_default()
+					.to_string(),
+				level,
+				reason,
+			};
+			if override_level.is_some() {
+				overrides.push(bump_commit.clone());
+			}
+			commits.push(bump_commit);
+		}
+		let (level, rule) = if commits.is_empty() {
+			(
+				BumpLevel::None,
+				String::from("there are no commits since the previous release"),
+			)
+		} else if commits.iter().all(|v| v.level == BumpLevel::None) {
+			(
+				BumpLevel::None,
+				String::from("none of the commits affect the version"),
+			)
+		} else if !semver.pre.is_empty() {
+			(
+				BumpLevel::Prerelease,
+				String::from(
+					"previous version is a pre-release, the pre-release identifier \
+					 is incremented",
+				),
+			)
+		} else {
+			let level = commits
+				.iter()
+				.map(|v| v.level)
+				.fold(BumpLevel::Patch, BumpLevel::max);
+			(
+				level,
+				format!("the highest increment of the commits ({level}) is applied"),
+			)
+		};
+		let mut rules = vec![rule];
+		let next_version = match level {
+			BumpLevel::None => semver.clone(),
+			BumpLevel::Prerelease => semver.increment_prerelease(),
+			BumpLevel::Patch => semver.increment_patch(),
+			BumpLevel::Minor => semver.increment_minor(),
+			BumpLevel::Major => semver.increment_major(),
+		};
+		let next_version = match config.build_number {
+			_ if level == BumpLevel::None => version.clone(),
+			Some(format) => {
+				let build = previous_build.unwrap_or_default() + 1;
+				rules.push(match previous_build {
+					Some(previous_build) => format!(
+						"the build number is incremented ({previous_build} -> \
+						 {build})"
+					),
+					None => format!("the build number starts at {build}"),
+				});
+				format!(
+					"{}{}",
+					prefix.unwrap_or_default(),
+					with_build_number(&next_version, build, format)?
+				)
+			}
+			None => format!("{}{next_version}", prefix.unwrap_or_default()),
+		};
+		// All the affecting commits trigger the increment of the pre-release
+		// identifier.
+		commits.retain(|v| match level {
+			BumpLevel::Prerelease => v.level != BumpLevel::None,
+			_ => v.level == level,
+		});
+		Ok(BumpRationale {
+			previous_version: Some(version),
+			next_version,
+			level,
+			rules,
+			commits,
+			overrides,
+		})
+	}
+
+	/// Returns the increment that overrides the contribution of the commit
+	/// along with its source.
+	///
+	/// The commit trailer takes precedence over the configuration.
+	fn bump_override(
+		commit: &Commit,
+		config: &BumpConfig,
+	) -> Option<(BumpLevel, String)> {
+		let trailer = commit.message.lines().rev().find_map(|line| {
+			let (key, value) = line.split_once(':')?;
+			key.trim()
+				.eq_ignore_ascii_case(BUMP_TRAILER)
+				.then_some(value)
+		});
+		if let Some(value) = trailer {
+			match BumpLevel::parse(value) {
+				Some(level) => {
+					return Some((level, format!("`{BUMP_TRAILER}` trailer")));
 				}
+				None => warn!(
+					"Invalid `{BUMP_TRAILER}` value in {}: {}",
+					commit.id.get(..7).unwrap_or(&commit.id),
+					value.trim()
+				),
 			}
-			None => {
-				warn!("No releases found, using 0.1.0 as the next version.");
-				Ok(String::from("0.1.0"))
+		}
+		config
+			.overrides
+			.iter()
+			.flatten()
+			.enumerate()
+			.find(|(_, rule)| rule.matches(&commit.id, &commit.message))
+			.map(|(i, rule)| (rule.bump, format!("override rule #{}", i + 1)))
+	}
+
+	/// Returns the increment that the commit contributes to the version bump
+	/// along with the reason.
+	///
+	/// The increments of the types can be configured via [`types`].
+	///
+	/// [`types`]: BumpConfig::types
+	fn bump_contribution(
+		commit: &Commit,
+		config: &BumpConfig,
+	) -> (BumpLevel, String) {
+		match ConventionalCommit::parse(commit.message.trim_end()) {
+			Ok(conv) if conv.breaking() || commit.is_breaking() => {
+				(BumpLevel::Major, String::from("breaking change"))
 			}
+			Ok(conv) => match config.types.get(&conv.type_().to_lowercase()) {
+				Some(level) => (
+					*level,
+					format!("`{}` commit (configured type)", conv.type_()),
+				),
+				None if conv.type_() == Type::FEAT => {
+					(BumpLevel::Minor, String::from("feature"))
+				}
+				None => (BumpLevel::Patch, format!("`{}` commit", conv.type_())),
+			},
+			Err(_) => (BumpLevel::Patch, String::from("unconventional commit")),
 		}
 	}
 }
@@ -157,16 +807,364 @@ pub struct Releases<'a> {
 	pub releases: &'a Vec<Release<'a>>,
 }
 
+/// Metadata of the repository.
+///
+/// This is available as `repository` in the template context.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryMetadata {
+	/// Name of the repository (e.g. `orhun/git-cliff`).
+	pub name:           Option<String>,
+	/// Default branch of the repository.
+	pub default_branch: Option<String>,
+	/// URL of the remote.
+	pub url:            Option<String>,
+	/// Description of the repository on the forge.
+	pub description:    Option<String>,
+	/// Topics of the repository on the forge.
+	pub topics:         Vec<String>,
+}
+
+/// Entry of the release timeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimelineEntry {
+	/// Version of the release (`null` for the unreleased changes).
+	pub version:    Option<String>,
+	/// Date of the release (`YYYY-MM-DD`).
+	pub date:       Option<String>,
+	/// Number of the commits in each group.
+	pub groups:     BTreeMap<String, usize>,
+	/// Breaking changes and features of the release.
+	pub highlights: Vec<Highlight>,
+}
+
+/// Data of a [shields.io endpoint badge](https://shields.io/badges/endpoint-badge).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Badge {
+	/// Version of the endpoint schema (always `1`).
+	#[serde(rename = "schemaVersion")]
+	pub schema_version: u8,
+	/// Left-hand side text of the badge.
+	pub label:          String,
+	/// Right-hand side text of the badge.
+	pub message:        String,
+	/// Color of the right-hand side.
+	pub color:          String,
+}
+
+/// Schema of the SQL export of the releases.
+///
+/// Every export is recorded as a run so that the runs can be compared.
+const SQL_SCHEMA: &str = r#"CREATE TABLE IF NOT EXISTS runs (
+  id INTEGER PRIMARY KEY,
+  timestamp INTEGER NOT NULL,
+  generator TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS releases (
+  run_id INTEGER NOT NULL REFERENCES runs(id),
+  version TEXT,
+  commit_id TEXT,
+  timestamp INTEGER NOT NULL,
+  previous_version TEXT
+);
+CREATE TABLE IF NOT EXISTS commits (
+  run_id INTEGER NOT NULL REFERENCES runs(id),
+  version TEXT,
+  id TEXT NOT NULL,
+  message TEXT NOT NULL,
+  "group" TEXT,
+  scope TEXT,
+  breaking INTEGER NOT NULL,
+  author_name TEXT,
+  author_email TEXT,
+  committed_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS authors (
+  run_id INTEGER NOT NULL REFERENCES runs(id),
+  version TEXT,
+  name TEXT,
+  email TEXT,
+  domain TEXT,
+  internal INTEGER NOT NULL,
+  commits INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS links (
+  run_id INTEGER NOT NULL REFERENCES runs(id),
+  commit_id TEXT NOT NULL,
+  text TEXT NOT NULL,
+  href TEXT NOT NULL
+);
+"#;
+
+/// Returns the given value as an SQL literal.
+///
+/// The NUL characters are removed since they cannot be passed to `sqlite3`.
+fn sql_literal(value: Option<&str>) -> String {
+	match value {
+		Some(value) => {
+			format!("'{}'", value.replace('\'', "''").replace('\0', ""))
+		}
+		None => String::from("NULL"),
+	}
+}
+
+/// Format of the release graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+	/// [Mermaid](https://mermaid.js.org) flowchart.
+	Mermaid,
+	/// [DOT](https://graphviz.org/doc/info/lang.html) graph.
+	Dot,
+}
+
 impl<'a> Releases<'a> {
 	/// Returns the list of releases as JSON.
 	pub fn as_json(&self) -> Result<String> {
 		Ok(serde_json::to_string(self.releases)?)
 	}
+
+	/// Returns the releases as a compact JSON timeline (e.g. for a releases
+	/// page of a website).
+	///
+	/// The highlights are selected with the configured commit types (see
+	/// [`Commit::is_highlight`]).
+	pub fn as_timeline(&self, config: &GitConfig) -> Result<String> {
+		let timeline = self
+			.releases
+			.iter()
+			.map(|release| {
+				let mut groups = BTreeMap::new();
+				for commit in &release.commits {
+					if let Some(group) = &commit.group {
+						*groups.entry(group.to_string()).or_default() += 1;
+					}
+				}
+				TimelineEntry {
+					version: release.version.clone(),
+					date: (release.timestamp != 0).then(|| {
+						let (year, month, day) = civil_date(release.timestamp);
+						format!("{year}-{month:02}-{day:02}")
+					}),
+					groups,
+					highlights: release
+						.commits
+						.iter()
+						.filter(|commit| commit.is_highlight(config))
+						.map(|commit| Highlight {
+							message:  commit.conv.as_ref().map_or_else(
+								|| {
+									commit
+										.message
+										.lines()
+										.next()
+										.unwrap_or_default()
+										.to_string()
+								},
+								|conv| conv.description().to_string(),
+							),
+							breaking: commit.is_breaking(),
+						})
+						.collect(),
+				}
+			})
+			.collect::<Vec<TimelineEntry>>();
+		Ok(serde_json::to_string(&timeline)?)
+	}
+
+	/// Returns the releases as an SQL script for SQLite.
+	///
+	/// The script creates the tables (i.e. `runs`, `releases`, `commits`,
+	/// `authors` and `links`) if they do not exist and inserts the releases as
+	/// a new run with the given timestamp.
+	pub fn as_sql(&self, timestamp: i64) -> String {
+		let run_id = "(SELECT max(id) FROM runs)";
+		let mut script = format!("BEGIN;\n{SQL_SCHEMA}");
+		script += &format!(
+			"INSERT INTO runs (timestamp, generator) VALUES ({timestamp}, {});\n",
+			sql_literal(Some(&format!("git-cliff {}", env!("CARGO_PKG_VERSION"))))
+		);
+		for release in self.releases {
+			let version = sql_literal(release.version.as_deref());
+			script += &format!(
+				"INSERT INTO releases VALUES ({run_id}, {version}, {}, {}, {});\n",
+				sql_literal(release.commit_id.as_deref()),
+				release.timestamp,
+				sql_literal(
+					release.previous.as_ref().and_then(|v| v.version.as_deref())
+				),
+			);
+			for commit in &release.commits {
+				script += &format!(
+					"INSERT INTO commits VALUES ({run_id}, {version}, {}, {}, {}, \
+					 {}, {}, {}, {}, {});\n",
+					sql_literal(Some(&commit.id)),
+					sql_literal(Some(
+						commit
+							.conv
+							.as_ref()
+							.map(|v| v.description())
+							.unwrap_or(&commit.message)
+					)),
+					sql_literal(commit.group_name().as_deref()),
+					sql_literal(commit.scope_name().as_deref()),
+					u8::from(commit.is_breaking()),
+					sql_literal(commit.author.name.as_deref()),
+					sql_literal(commit.author.email.as_deref()),
+					commit.committer.timestamp,
+				);
+				for link in &commit.links {
+					script += &format!(
+						"INSERT INTO links VALUES ({run_id}, {}, {}, {});\n",
+						sql_literal(Some(&commit.id)),
+						sql_literal(Some(&link.text)),
+						sql_literal(Some(&link.href)),
+					);
+				}
+			}
+			let authors = release
+				.authors
+				.clone()
+				.unwrap_or_else(|| ReleaseAuthors::new(&release.commits, &[]));
+			for (internal, author) in authors
+				.internal
+				.iter()
+				.map(|v| (true, v))
+				.chain(authors.external.iter().map(|v| (false, v)))
+			{
+				script += &format!(
+					"INSERT INTO authors VALUES ({run_id}, {version}, {}, {}, {}, \
+					 {}, {});\n",
+					sql_literal(author.name.as_deref()),
+					sql_literal(author.email.as_deref()),
+					sql_literal(author.domain.as_deref()),
+					u8::from(internal),
+					author.commits,
+				);
+			}
+		}
+		script + "COMMIT;\n"
+	}
+
+	/// Returns the data of a badge for the latest release as JSON.
+	///
+	/// The message of the badge is the version and the date of the latest
+	/// release followed by the number of the unreleased commits (e.g.
+	/// `v1.0.0 (2024-05-06) +3`). The color is changed when there are
+	/// unreleased commits.
+	pub fn as_badge(&self) -> Result<String> {
+		let unreleased = self
+			.releases
+			.iter()
+			.take_while(|release| release.version.is_none())
+			.map(|release| release.commits.len())
+			.sum::<usize>();
+		let latest = self
+			.releases
+			.iter()
+			.find_map(|release| release.version.as_ref().map(|v| (v, release)));
+		let badge = Badge {
+			schema_version: 1,
+			label:          String::from("release"),
+			message:        match latest {
+				Some((version, release)) => {
+					let mut message = version.to_string();
+					if release.timestamp != 0 {
+						let (year, month, day) = civil_date(release.timestamp);
+						message += &format!(" ({year}-{month:02}-{day:02})");
+					}
+					if unreleased != 0 {
+						message += &format!(" +{unreleased}");
+					}
+					message
+				}
+				None => String::from("unreleased"),
+			},
+			color:          String::from(match (latest, unreleased) {
+				(None, _) => "lightgrey",
+				(Some(_), 0) => "blue",
+				(Some(_), _) => "orange",
+			}),
+		};
+		Ok(serde_json::to_string(&badge)?)
+	}
+
+	/// Returns the releases as a graph in the given format.
+	///
+	/// The releases are the nodes of the graph (from the oldest to the newest)
+	/// and the breaking changes are added as annotations.
+	pub fn as_graph(&self, format: GraphFormat) -> String {
+		let mut lines = vec![match format {
+			GraphFormat::Mermaid => String::from("flowchart LR"),
+			GraphFormat::Dot => String::from("digraph releases {"),
+		}];
+		let mut previous_node = None;
+		for (i, release) in self.releases.iter().rev().enumerate() {
+			let node = format!("release{i}");
+			let label = format!(
+				"{} ({} commits)",
+				release.version.as_deref().unwrap_or("unreleased"),
+				release.commits.len()
+			);
+			lines.push(match format {
+				GraphFormat::Mermaid => {
+					format!("    {node}[\"{}\"]", label.replace('"', "#quot;"))
+				}
+				GraphFormat::Dot => {
+					format!("    {node} [label=\"{}\"];", label.replace('"', "\\\""))
+				}
+			});
+			if let Some(previous_node) = previous_node {
+				lines.push(match format {
+					GraphFormat::Mermaid => {
+						format!("    {previous_node} --> {node}")
+					}
+					GraphFormat::Dot => format!("    {previous_node} -> {node};"),
+				});
+			}
+			for (j, commit) in release
+				.commits
+				.iter()
+				.filter(|commit| commit.is_breaking())
+				.enumerate()
+			{
+				let note = format!("{node}_note{j}");
+				let message = commit
+					.conv
+					.as_ref()
+					.map(|v| v.description())
+					.unwrap_or_default();
+				lines.extend(match format {
+					GraphFormat::Mermaid => [
+						format!(
+							"    {note}>\"{}\"]",
+							message.replace('"', "#quot;")
+						),
+						format!("    {node} -.- {note}"),
+					],
+					GraphFormat::Dot => [
+						format!(
+							"    {note} [label=\"{}\", shape=note];",
+							message.replace('"', "\\\"")
+						),
+						format!(
+							"    {node} -> {note} [style=dashed, arrowhead=none];"
+						),
+					],
+				});
+			}
+			previous_node = Some(node);
+		}
+		if format == GraphFormat::Dot {
+			lines.push(String::from("}"));
+		}
+		lines.join("\n")
+	}
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
+	use crate::config::BumpOverride;
+	use regex::Regex;
 	#[test]
 	fn bump_version() -> Result<()> {
 		for (version, expected_version, commits) in [
@@ -215,9 +1213,14 @@ mod test {
 					version: Some(String::from(version)),
 					..Default::default()
 				})),
+				moved_from: None,
+				authors: None,
+				assets: Vec::new(),
+				stats: Default::default(),
 				#[cfg(feature = "github")]
 				github: crate::github::GitHubReleaseMetadata {
 					contributors: vec![],
+					milestone:    None,
 				},
 			};
 			let next_version = release.calculate_next_version()?;
@@ -235,6 +1238,670 @@ mod test {
 		Ok(())
 	}
 
+	#[test]
+	fn bump_rationale() -> Result<()> {
+		let release = Release {
+			commits: vec![
+				("a1", "feat: add xyz"),
+				("a2", "fix: fix xyz"),
+				("a3", "feat(parser): add abc\n\nsome details"),
+			]
+			.into_iter()
+			.map(|(id, message)| {
+				Commit::new(String::from(id), String::from(message))
+			})
+			.collect(),
+			previous: Some(Box::new(Release {
+				version: Some(String::from("v1.0.0")),
+				..Default::default()
+			})),
+			..Default::default()
+		};
+		let rationale = release.bump(&BumpConfig::default())?;
+		assert_eq!(
+			BumpRationale {
+				previous_version: Some(String::from("v1.0.0")),
+				next_version:     String::from("v1.1.0"),
+				level:            BumpLevel::Minor,
+				rules:            vec![String::from(
+					"the highest increment of the commits (minor) is applied"
+				)],
+				commits:          vec![
+					BumpCommit {
+						id:      String::from("a1"),
+						message: String::from("feat: add xyz"),
+						level:   BumpLevel::Minor,
+						reason:  String::from("feature"),
+					},
+					BumpCommit {
+						id:      String::from("a3"),
+						message: String::from("feat(parser): add abc"),
+						level:   BumpLevel::Minor,
+						reason:  String::from("feature"),
+					},
+				],
+				overrides:        vec![],
+			},
+			rationale
+		);
+		assert!(rationale.as_json()?.contains(r#""level": "minor""#));
+
+		let rationale = Release {
+			previous: Some(Box::new(Release {
+				version: Some(String::from("v1.1.0-rc.1")),
+				..Default::default()
+			})),
+			..release
+		}
+		.bump(&BumpConfig::default())?;
+		assert_eq!(BumpLevel::Prerelease, rationale.level);
+		assert_eq!("v1.1.0-rc.2", rationale.next_version);
+		assert_eq!(
+			vec!["a1", "a2", "a3"],
+			rationale
+				.commits
+				.iter()
+				.map(|v| v.id.as_str())
+				.collect::<Vec<&str>>()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn bump_overrides() -> Result<()> {
+		let release = |messages: &[&str]| Release {
+			commits: messages
+				.iter()
+				.enumerate()
+				.map(|(i, message)| {
+					Commit::new(format!("a{i}"), message.to_string())
+				})
+				.collect(),
+			previous: Some(Box::new(Release {
+				version: Some(String::from("1.0.0")),
+				..Default::default()
+			})),
+			..Default::default()
+		};
+		let config = BumpConfig {
+			overrides: Some(vec![BumpOverride {
+				sha:     None,
+				message: Some(
+					Regex::new("^feat\\(internal\\)").expect("invalid regex"),
+				),
+				bump:    BumpLevel::Patch,
+			}]),
+			..Default::default()
+		};
+		for (expected_version, messages) in [
+			("1.0.1", vec!["feat(internal): add xyz", "fix: fix xyz"]),
+			("1.0.1", vec!["feat: add xyz\n\nChangelog-Bump: patch"]),
+			("2.0.0", vec![
+				"chore: update policy\n\nchangelog-bump: Major",
+			]),
+			("1.0.0", vec!["fix: fix xyz\n\nChangelog-Bump: skip"]),
+			("1.1.0", vec![
+				"feat(internal): add xyz\n\nChangelog-Bump: minor",
+				"fix: fix xyz\n\nChangelog-Bump: skip",
+			]),
+			("1.1.0", vec!["feat: add xyz\n\nChangelog-Bump: huge"]),
+		] {
+			assert_eq!(
+				expected_version,
+				release(&messages).bump(&config)?.next_version
+			);
+		}
+		let rationale = release(&[
+			"feat(internal): add xyz\n\nChangelog-Bump: minor",
+			"feat(internal): add abc",
+		])
+		.bump(&config)?;
+		assert_eq!(
+			vec![
+				"`Changelog-Bump` trailer (overrides minor: feature)",
+				"override rule #1 (overrides minor: feature)"
+			],
+			rationale
+				.overrides
+				.iter()
+				.map(|v| v.reason.as_str())
+				.collect::<Vec<&str>>()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn bump_types() -> Result<()> {
+		let release = |messages: &[&str]| Release {
+			commits: messages
+				.iter()
+				.enumerate()
+				.map(|(i, message)| {
+					Commit::new(format!("a{i}"), message.to_string())
+				})
+				.collect(),
+			previous: Some(Box::new(Release {
+				version: Some(String::from("1.0.0")),
+				..Default::default()
+			})),
+			..Default::default()
+		};
+		let config = BumpConfig {
+			types: [
+				("feat", BumpLevel::Patch),
+				("perf", BumpLevel::Minor),
+				("refactor", BumpLevel::None),
+				("security", BumpLevel::Major),
+			]
+			.into_iter()
+			.map(|(k, v)| (k.to_string(), v))
+			.collect(),
+			..Default::default()
+		};
+		for (expected_version, messages) in [
+			("1.0.1", vec!["feat: add xyz", "fix: fix xyz"]),
+			("1.1.0", vec!["perf: speed up xyz", "fix: fix xyz"]),
+			("1.0.0", vec!["refactor: move xyz"]),
+			("1.0.1", vec!["refactor: move xyz", "docs: update xyz"]),
+			("2.0.0", vec!["security: fix xyz"]),
+			("2.0.0", vec!["refactor!: remove xyz"]),
+		] {
+			assert_eq!(
+				expected_version,
+				release(&messages).bump(&config)?.next_version
+			);
+		}
+		let rationale = release(&["perf: speed up xyz"]).bump(&config)?;
+		assert_eq!(
+			"`perf` commit (configured type)",
+			rationale.commits[0].reason
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn bump_build_number() -> Result<()> {
+		let release = |version: &str, message: &str| Release {
+			commits: vec![Commit::new(String::from("a1"), message.to_string())],
+			previous: Some(Box::new(Release {
+				version: Some(version.to_string()),
+				..Default::default()
+			})),
+			..Default::default()
+		};
+		for (expected_version, version, message, build_number) in [
+			(
+				"1.2.4+build.46",
+				"1.2.3+build.45",
+				"fix: xyz",
+				BuildNumber::Metadata,
+			),
+			(
+				"v1.3.0+build.1",
+				"v1.2.3",
+				"feat: xyz",
+				BuildNumber::Metadata,
+			),
+			("1.2.4.46", "1.2.3.45", "fix: xyz", BuildNumber::Component),
+			("v2.0.0.1", "v1.2.3", "feat!: xyz", BuildNumber::Component),
+			(
+				"1.2.3.8-rc.2",
+				"1.2.3.7-rc.1",
+				"fix: xyz",
+				BuildNumber::Component,
+			),
+			(
+				"1.2.3.45",
+				"1.2.3.45",
+				"fix: xyz\n\nChangelog-Bump: skip",
+				BuildNumber::Component,
+			),
+		] {
+			let config = BumpConfig {
+				build_number: Some(build_number),
+				..Default::default()
+			};
+			assert_eq!(
+				expected_version,
+				release(version, message).bump(&config)?.next_version
+			);
+		}
+		let rationale = release("1.2.3.45", "fix: xyz").bump(&BumpConfig {
+			build_number: Some(BuildNumber::Component),
+			..Default::default()
+		})?;
+		assert_eq!(
+			vec![
+				"the highest increment of the commits (patch) is applied",
+				"the build number is incremented (45 -> 46)"
+			],
+			rationale.rules
+		);
+		assert_eq!(
+			"0.1.0+build.1",
+			Release::default()
+				.bump(&BumpConfig {
+					build_number: Some(BuildNumber::Metadata),
+					..Default::default()
+				})?
+				.next_version
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn release_graph() -> Result<()> {
+		let releases = vec![
+			Release {
+				version: None,
+				commits: vec![Commit::from(String::from("feat!: remove \"x\""))
+					.into_conventional()?],
+				..Default::default()
+			},
+			Release {
+				version: Some(String::from("v1.0.0")),
+				commits: vec![Commit::from(String::from("feat: add x"))],
+				..Default::default()
+			},
+		];
+		let releases = Releases {
+			releases: &releases,
+		};
+		assert_eq!(
+			r#"flowchart LR
+    release0["v1.0.0 (1 commits)"]
+    release1["unreleased (1 commits)"]
+    release0 --> release1
+    release1_note0>"remove #quot;x#quot;"]
+    release1 -.- release1_note0"#,
+			releases.as_graph(GraphFormat::Mermaid)
+		);
+		assert_eq!(
+			r#"digraph releases {
+    release0 [label="v1.0.0 (1 commits)"];
+    release1 [label="unreleased (1 commits)"];
+    release0 -> release1;
+    release1_note0 [label="remove \"x\"", shape=note];
+    release1 -> release1_note0 [style=dashed, arrowhead=none];
+}"#,
+			releases.as_graph(GraphFormat::Dot)
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn release_timeline() -> Result<()> {
+		let mut commits = vec![
+			Commit::from(String::from("feat!: remove x")).into_conventional()?,
+			Commit::from(String::from("feat: add y")).into_conventional()?,
+			Commit::from(String::from("fix: fix z")).into_conventional()?,
+		];
+		commits[0].group = Some(String::from("Features"));
+		commits[1].group = Some(String::from("Features"));
+		commits[2].group = Some(String::from("Bug Fixes"));
+		let releases = vec![
+			Release {
+				version: None,
+				..Default::default()
+			},
+			Release {
+				version: Some(String::from("v1.0.0")),
+				commits,
+				timestamp: 1714953600,
+				..Default::default()
+			},
+		];
+		assert_eq!(
+			r#"[{"version":null,"date":null,"groups":{},"highlights":[]},{"version":"v1.0.0","date":"2024-05-06","groups":{"Bug Fixes":1,"Features":2},"highlights":[{"message":"remove x","breaking":true},{"message":"add y","breaking":false}]}]"#,
+			Releases {
+				releases: &releases,
+			}
+			.as_timeline(&GitConfig::default())?
+		);
+		let config = GitConfig {
+			commit_types: Some(vec![crate::config::CommitType {
+				name: String::from("fix"),
+				highlight: Some(true),
+				..Default::default()
+			}]),
+			..Default::default()
+		};
+		assert_eq!(
+			r#"[{"version":null,"date":null,"groups":{},"highlights":[]},{"version":"v1.0.0","date":"2024-05-06","groups":{"Bug Fixes":1,"Features":2},"highlights":[{"message":"remove x","breaking":true},{"message":"add y","breaking":false},{"message":"fix z","breaking":false}]}]"#,
+			Releases {
+				releases: &releases,
+			}
+			.as_timeline(&config)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn release_authors() {
+		let commit = |name: &str, email: Option<&str>| Commit {
+			author: crate::commit::Signature {
+				name:      Some(String::from(name)),
+				email:     email.map(String::from),
+				timestamp: 0,
+			},
+			..Default::default()
+		};
+		let authors = ReleaseAuthors::new(
+			&[
+				commit("x", Some("x@corp.com")),
+				commit("y", Some("y@eu.corp.com")),
+				commit("x", Some("X@Corp.com")),
+				commit("z", Some("z@gmail.com")),
+				commit("w", None),
+			],
+			&[String::from("corp.com")],
+		);
+		assert_eq!(
+			vec![(Some("x@corp.com"), 2), (Some("y@eu.corp.com"), 1)],
+			authors
+				.internal
+				.iter()
+				.map(|v| (v.email.as_deref(), v.commits))
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			vec![Some("w"), Some("z")],
+			authors
+				.external
+				.iter()
+				.map(|v| v.name.as_deref())
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			vec![("corp.com", 1), ("eu.corp.com", 1), ("gmail.com", 1)],
+			authors
+				.domains
+				.iter()
+				.map(|(k, v)| (k.as_str(), *v))
+				.collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn release_stats() -> Result<()> {
+		let mut feature = Commit::new(
+			String::from("1"),
+			String::from("feat(ui): add the dark theme\n\nwith a long body"),
+		)
+		.into_conventional()?;
+		feature.group = Some(String::from("Features"));
+		let mut commits = vec![
+			feature,
+			Commit::new(String::from("2"), String::from("fix x\n\nbody")),
+		];
+		commits.extend((0..100).map(|i| {
+			let mut commit =
+				Commit::new(i.to_string(), String::from("update the dependency"));
+			commit.group = Some(String::from("Dependencies"));
+			commit
+		}));
+		let stats = ReleaseStats::new(&commits);
+		assert_eq!(
+			EntryStats {
+				entries:      102,
+				words:        306,
+				reading_time: 2,
+			},
+			stats.total
+		);
+		assert_eq!(
+			vec![("Dependencies", 100, 300, 2), ("Features", 1, 4, 1)],
+			stats
+				.groups
+				.iter()
+				.map(|(group, v)| (
+					group.as_str(),
+					v.entries,
+					v.words,
+					v.reading_time
+				))
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			serde_json::json!({ "entries": 0, "words": 0, "reading_time": 0, "groups": {} }),
+			serde_json::to_value(ReleaseStats::new(&[]))?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn release_assets() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let dir = temp_dir.path().to_path_buf();
+		fs::create_dir_all(dir.join("nested"))?;
+		fs::write(dir.join("b.tar.gz"), "hello")?;
+		fs::write(dir.join("a.zip"), "")?;
+		let assets = ReleaseAsset::from_paths(&[dir.clone(), dir.join("b.tar.gz")])?;
+		assert_eq!(
+			vec![
+				ReleaseAsset {
+					name:   String::from("a.zip"),
+					size:   0,
+					sha256: String::from(
+						"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+					),
+				},
+				ReleaseAsset {
+					name:   String::from("b.tar.gz"),
+					size:   5,
+					sha256: String::from(
+						"2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+					),
+				},
+			],
+			assets[..2]
+		);
+		assert_eq!(assets[1], assets[2]);
+		assert!(ReleaseAsset::from_paths(&[dir.join("missing")]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn release_sql() -> Result<()> {
+		let mut commit = Commit::new(
+			String::from("abc123"),
+			String::from("feat(ui): add o'clock"),
+		)
+		.into_conventional()?;
+		commit.links = vec![crate::commit::Link {
+			text: String::from("#1"),
+			href: String::from("https://x.y/1"),
+		}];
+		let releases = vec![Release {
+			version: Some(String::from("v1.0.0")),
+			commits: vec![commit],
+			timestamp: 1714953600,
+			..Default::default()
+		}];
+		let script = Releases {
+			releases: &releases,
+		}
+		.as_sql(1714953601);
+		assert!(script.starts_with("BEGIN;\nCREATE TABLE IF NOT EXISTS runs ("));
+		assert!(script.ends_with("COMMIT;\n"));
+		for statement in [
+			"INSERT INTO runs (timestamp, generator) VALUES (1714953601, 'git-cliff",
+			"INSERT INTO releases VALUES ((SELECT max(id) FROM runs), 'v1.0.0', \
+			 NULL, 1714953600, NULL);",
+			"(SELECT max(id) FROM runs), 'v1.0.0', 'abc123', 'add o''clock', \
+			 'feat', 'ui', 0, NULL, NULL, 0);",
+			"INSERT INTO links VALUES ((SELECT max(id) FROM runs), 'abc123', '#1', \
+			 'https://x.y/1');",
+			"INSERT INTO authors VALUES ((SELECT max(id) FROM runs), 'v1.0.0', \
+			 NULL, NULL, NULL, 0, 1);",
+		] {
+			assert!(script.contains(statement), "{statement} is not found");
+		}
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn export_sqlite() -> Result<()> {
+		crate::command::check_sqlite()?;
+		let message = "fix: it's fixed\n\n.shell echo x;\n-- DROP TABLE runs;\0";
+		let releases = vec![Release {
+			version: Some(String::from("v1.0.0")),
+			commits: vec![Commit {
+				author: crate::commit::Signature {
+					timestamp: 1,
+					..Default::default()
+				},
+				committer: crate::commit::Signature {
+					timestamp: 2,
+					..Default::default()
+				},
+				..Commit::new(String::from("abc123"), message.to_string())
+			}],
+			..Default::default()
+		}];
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("changelog.db");
+		for timestamp in [1, 2] {
+			crate::command::run_sqlite(
+				&path,
+				&Releases {
+					releases: &releases,
+				}
+				.as_sql(timestamp),
+			)?;
+		}
+		let output = std::process::Command::new("sqlite3")
+			.arg(&path)
+			.arg(
+				"SELECT count(*) FROM runs; SELECT committed_at, message FROM \
+				 commits WHERE run_id = 2;",
+			)
+			.output()?;
+		assert!(output.status.success());
+		assert_eq!(
+			format!("2\n2|{}\n", message.trim_end_matches('\0')),
+			String::from_utf8_lossy(&output.stdout)
+		);
+		assert!(crate::command::run_sqlite(&path, "SELECT x FROM y;").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn release_badge() -> Result<()> {
+		let mut releases = vec![
+			Release {
+				version: None,
+				commits: vec![
+					Commit::from(String::from("feat: add x")),
+					Commit::from(String::from("fix: fix y")),
+				],
+				..Default::default()
+			},
+			Release {
+				version: Some(String::from("v1.0.0")),
+				timestamp: 1714953600,
+				..Default::default()
+			},
+		];
+		assert_eq!(
+			r#"{"schemaVersion":1,"label":"release","message":"v1.0.0 (2024-05-06) +2","color":"orange"}"#,
+			Releases {
+				releases: &releases,
+			}
+			.as_badge()?
+		);
+		releases.remove(0);
+		assert_eq!(
+			r#"{"schemaVersion":1,"label":"release","message":"v1.0.0 (2024-05-06)","color":"blue"}"#,
+			Releases {
+				releases: &releases,
+			}
+			.as_badge()?
+		);
+		releases.clear();
+		assert_eq!(
+			r#"{"schemaVersion":1,"label":"release","message":"unreleased","color":"lightgrey"}"#,
+			Releases {
+				releases: &releases,
+			}
+			.as_badge()?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn split_by_period() {
+		let commit = |id: &str, timestamp: i64| {
+			let mut commit = Commit::new(id.to_string(), String::from("feat: xyz"));
+			commit.committer.timestamp = timestamp;
+			commit
+		};
+		let release = Release {
+			commits: vec![
+				// 2024-01-15
+				commit("a", 1705276800),
+				// 2024-03-31
+				commit("b", 1711843200),
+				// 2024-04-01
+				commit("c", 1711929600),
+				// 2024-01-20
+				commit("d", 1705708800),
+			],
+			..Default::default()
+		};
+		let releases = release
+			.clone()
+			.split_by_period(ReleasePeriod::Month, CommitDate::Committer);
+		assert_eq!(
+			vec![
+				(Some(String::from("2024-01")), Some(String::from("d")), 2),
+				(Some(String::from("2024-03")), Some(String::from("b")), 1),
+				(Some(String::from("2024-04")), Some(String::from("c")), 1),
+			],
+			releases
+				.iter()
+				.map(|v| (v.version.clone(), v.commit_id.clone(), v.commits.len()))
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			Some(String::from("2024-03")),
+			releases[2]
+				.previous
+				.as_ref()
+				.and_then(|v| v.version.clone())
+		);
+		let releases = release
+			.clone()
+			.split_by_period(ReleasePeriod::Quarter, CommitDate::Committer);
+		assert_eq!(
+			vec![Some(String::from("2024-Q1")), Some(String::from("2024-Q2"))],
+			releases
+				.iter()
+				.map(|v| v.version.clone())
+				.collect::<Vec<_>>()
+		);
+		let mut release = release;
+		for commit in release.commits.iter_mut() {
+			commit.author.timestamp = commit.committer.timestamp;
+		}
+		// authored on 2023-12-31
+		release.commits[2].author.timestamp = 1703980800;
+		let releases =
+			release.split_by_period(ReleasePeriod::Year, CommitDate::Author);
+		assert_eq!(
+			vec![
+				(Some(String::from("2023")), Some(String::from("c")), 1),
+				(Some(String::from("2024")), Some(String::from("b")), 3),
+			],
+			releases
+				.iter()
+				.map(|v| (v.version.clone(), v.commit_id.clone(), v.commits.len()))
+				.collect::<Vec<_>>()
+		);
+		assert_eq!("1969", ReleasePeriod::Year.name(-1));
+	}
+
 	#[cfg(feature = "github")]
 	#[test]
 	fn update_github_metadata() -> Result<()> {
@@ -242,8 +1909,8 @@ mod test {
 		use crate::github::PullRequestLabel;
 
 		let mut release = Release {
-			version:   None,
-			commits:   vec![
+			version:    None,
+			commits:    vec![
 				Commit::from(String::from(
 					"1d244937ee6ceb8e0314a4a201ba93a7a61f2071 add github \
 					 integration",
@@ -265,14 +1932,19 @@ mod test {
 					"6c34967147560ea09658776d4901709139b4ad66 should be fine",
 				)),
 			],
-			commit_id: None,
-			timestamp: 0,
-			previous:  Some(Box::new(Release {
+			commit_id:  None,
+			timestamp:  0,
+			previous:   Some(Box::new(Release {
 				version: Some(String::from("1.0.0")),
 				..Default::default()
 			})),
-			github:    GitHubReleaseMetadata {
+			moved_from: None,
+			authors:    None,
+			assets:     Vec::new(),
+			stats:      Default::default(),
+			github:     GitHubReleaseMetadata {
 				contributors: vec![],
+				milestone:    None,
 			},
 		};
 		release.update_github_metadata(
@@ -282,50 +1954,59 @@ mod test {
 					author: Some(GitHubCommitAuthor {
 						login: Some(String::from("orhun")),
 					}),
+					commit: None,
 				},
 				GitHubCommit {
 					sha:    String::from("21f6aa587fcb772de13f2fde0e92697c51f84162"),
 					author: Some(GitHubCommitAuthor {
 						login: Some(String::from("orhun")),
 					}),
+					commit: None,
 				},
 				GitHubCommit {
 					sha:    String::from("35d8c6b6329ecbcf131d7df02f93c3bbc5ba5973"),
 					author: Some(GitHubCommitAuthor {
 						login: Some(String::from("nuhro")),
 					}),
+					commit: None,
 				},
 				GitHubCommit {
 					sha:    String::from("4d3ffe4753b923f4d7807c490e650e6624a12074"),
 					author: Some(GitHubCommitAuthor {
 						login: Some(String::from("awesome_contributor")),
 					}),
+					commit: None,
 				},
 				GitHubCommit {
 					sha:    String::from("5a55e92e5a62dc5bf9872ffb2566959fad98bd05"),
 					author: Some(GitHubCommitAuthor {
 						login: Some(String::from("orhun")),
 					}),
+					commit: None,
 				},
 				GitHubCommit {
 					sha:    String::from("6c34967147560ea09658776d4901709139b4ad66"),
 					author: Some(GitHubCommitAuthor {
 						login: Some(String::from("someone")),
 					}),
+					commit: None,
 				},
 				GitHubCommit {
 					sha:    String::from("0c34967147560e809658776d4901709139b4ad68"),
 					author: Some(GitHubCommitAuthor {
 						login: Some(String::from("idk")),
 					}),
+					commit: None,
 				},
 				GitHubCommit {
 					sha:    String::from("kk34967147560e809658776d4901709139b4ad68"),
 					author: None,
+					commit: None,
 				},
 				GitHubCommit {
 					sha:    String::new(),
 					author: None,
+					commit: None,
 				},
 			],
 			vec![
@@ -338,6 +2019,9 @@ mod test {
 					labels:           vec![PullRequestLabel {
 						name: String::from("rust"),
 					}],
+					body:             None,
+					user:             None,
+					merged_at:        Some(String::from("2024-05-06T12:30:00Z")),
 				},
 				GitHubPullRequest {
 					title:            Some(String::from("2")),
@@ -348,6 +2032,9 @@ mod test {
 					labels:           vec![PullRequestLabel {
 						name: String::from("rust"),
 					}],
+					body:             None,
+					user:             None,
+					merged_at:        None,
 				},
 				GitHubPullRequest {
 					title:            Some(String::from("3")),
@@ -358,6 +2045,9 @@ mod test {
 					labels:           vec![PullRequestLabel {
 						name: String::from("deps"),
 					}],
+					body:             None,
+					user:             None,
+					merged_at:        None,
 				},
 				GitHubPullRequest {
 					title:            Some(String::from("4")),
@@ -368,6 +2058,9 @@ mod test {
 					labels:           vec![PullRequestLabel {
 						name: String::from("deps"),
 					}],
+					body:             None,
+					user:             None,
+					merged_at:        None,
 				},
 				GitHubPullRequest {
 					title:            Some(String::from("5")),
@@ -378,6 +2071,12 @@ mod test {
 					labels:           vec![PullRequestLabel {
 						name: String::from("github"),
 					}],
+					body:             Some(String::from(
+						"Some context\r\n\r\n```release-note\r\nSupport the GitHub \
+						 integration\r\n```\r\n",
+					)),
+					user:             None,
+					merged_at:        None,
 				},
 			],
 		)?;
@@ -386,11 +2085,14 @@ mod test {
 				id: String::from("1d244937ee6ceb8e0314a4a201ba93a7a61f2071"),
 				message: String::from("add github integration"),
 				github: GitHubContributor {
-					username:      Some(String::from("orhun")),
-					pr_title:      Some(String::from("1")),
-					pr_number:     Some(42),
-					pr_labels:     vec![String::from("rust")],
-					is_first_time: false,
+					username:        Some(String::from("orhun")),
+					pr_title:        Some(String::from("1")),
+					pr_number:       Some(42),
+					pr_labels:       vec![String::from("rust")],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   false,
+					pr_merged_at:    Some(1714998600),
 				},
 				..Default::default()
 			},
@@ -398,11 +2100,14 @@ mod test {
 				id: String::from("21f6aa587fcb772de13f2fde0e92697c51f84162"),
 				message: String::from("fix github integration"),
 				github: GitHubContributor {
-					username:      Some(String::from("orhun")),
-					pr_title:      Some(String::from("2")),
-					pr_number:     Some(66),
-					pr_labels:     vec![String::from("rust")],
-					is_first_time: false,
+					username:        Some(String::from("orhun")),
+					pr_title:        Some(String::from("2")),
+					pr_number:       Some(66),
+					pr_labels:       vec![String::from("rust")],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   false,
+					pr_merged_at:    None,
 				},
 				..Default::default()
 			},
@@ -410,11 +2115,14 @@ mod test {
 				id: String::from("35d8c6b6329ecbcf131d7df02f93c3bbc5ba5973"),
 				message: String::from("update metadata"),
 				github: GitHubContributor {
-					username:      Some(String::from("nuhro")),
-					pr_title:      Some(String::from("3")),
-					pr_number:     Some(53),
-					pr_labels:     vec![String::from("deps")],
-					is_first_time: false,
+					username:        Some(String::from("nuhro")),
+					pr_title:        Some(String::from("3")),
+					pr_number:       Some(53),
+					pr_labels:       vec![String::from("deps")],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   false,
+					pr_merged_at:    None,
 				},
 				..Default::default()
 			},
@@ -422,11 +2130,14 @@ mod test {
 				id: String::from("4d3ffe4753b923f4d7807c490e650e6624a12074"),
 				message: String::from("do some stuff"),
 				github: GitHubContributor {
-					username:      Some(String::from("awesome_contributor")),
-					pr_title:      Some(String::from("4")),
-					pr_number:     Some(1000),
-					pr_labels:     vec![String::from("deps")],
-					is_first_time: false,
+					username:        Some(String::from("awesome_contributor")),
+					pr_title:        Some(String::from("4")),
+					pr_number:       Some(1000),
+					pr_labels:       vec![String::from("deps")],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   false,
+					pr_merged_at:    None,
 				},
 				..Default::default()
 			},
@@ -434,11 +2145,16 @@ mod test {
 				id: String::from("5a55e92e5a62dc5bf9872ffb2566959fad98bd05"),
 				message: String::from("alright"),
 				github: GitHubContributor {
-					username:      Some(String::from("orhun")),
-					pr_title:      Some(String::from("5")),
-					pr_number:     Some(999999),
-					pr_labels:     vec![String::from("github")],
-					is_first_time: false,
+					username:        Some(String::from("orhun")),
+					pr_title:        Some(String::from("5")),
+					pr_number:       Some(999999),
+					pr_labels:       vec![String::from("github")],
+					pr_release_note: Some(String::from(
+						"Support the GitHub integration",
+					)),
+					pr_commits:      vec![],
+					is_first_time:   false,
+					pr_merged_at:    None,
 				},
 				..Default::default()
 			},
@@ -446,11 +2162,14 @@ mod test {
 				id: String::from("6c34967147560ea09658776d4901709139b4ad66"),
 				message: String::from("should be fine"),
 				github: GitHubContributor {
-					username:      Some(String::from("someone")),
-					pr_title:      None,
-					pr_number:     None,
-					pr_labels:     vec![],
-					is_first_time: false,
+					username:        Some(String::from("someone")),
+					pr_title:        None,
+					pr_number:       None,
+					pr_labels:       vec![],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   false,
+					pr_merged_at:    None,
 				},
 				..Default::default()
 			},
@@ -465,37 +2184,163 @@ mod test {
 		let expected_metadata = GitHubReleaseMetadata {
 			contributors: vec![
 				GitHubContributor {
-					username:      Some(String::from("someone")),
-					pr_title:      None,
-					pr_number:     None,
-					pr_labels:     vec![],
-					is_first_time: true,
+					username:        Some(String::from("someone")),
+					pr_title:        None,
+					pr_number:       None,
+					pr_labels:       vec![],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   true,
+					pr_merged_at:    None,
 				},
 				GitHubContributor {
-					username:      Some(String::from("orhun")),
-					pr_title:      Some(String::from("1")),
-					pr_number:     Some(42),
-					pr_labels:     vec![String::from("rust")],
-					is_first_time: true,
+					username:        Some(String::from("orhun")),
+					pr_title:        Some(String::from("1")),
+					pr_number:       Some(42),
+					pr_labels:       vec![String::from("rust")],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   true,
+					pr_merged_at:    Some(1714998600),
 				},
 				GitHubContributor {
-					username:      Some(String::from("nuhro")),
-					pr_title:      Some(String::from("3")),
-					pr_number:     Some(53),
-					pr_labels:     vec![String::from("deps")],
-					is_first_time: true,
+					username:        Some(String::from("nuhro")),
+					pr_title:        Some(String::from("3")),
+					pr_number:       Some(53),
+					pr_labels:       vec![String::from("deps")],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   true,
+					pr_merged_at:    None,
 				},
 				GitHubContributor {
-					username:      Some(String::from("awesome_contributor")),
-					pr_title:      Some(String::from("4")),
-					pr_number:     Some(1000),
-					pr_labels:     vec![String::from("deps")],
-					is_first_time: true,
+					username:        Some(String::from("awesome_contributor")),
+					pr_title:        Some(String::from("4")),
+					pr_number:       Some(1000),
+					pr_labels:       vec![String::from("deps")],
+					pr_release_note: None,
+					pr_commits:      vec![],
+					is_first_time:   true,
+					pr_merged_at:    None,
 				},
 			],
+			milestone:    None,
 		};
 		assert_eq!(expected_metadata, release.github);
 
+		release.set_merge_dates();
+		assert_eq!(
+			vec![1714998600, 0],
+			release.commits[..2]
+				.iter()
+				.map(|v| v.date)
+				.collect::<Vec<i64>>()
+		);
+
+		release.skip_labeled_commits(&[String::from("Deps")]);
+		assert_eq!(
+			vec![
+				"add github integration",
+				"fix github integration",
+				"alright",
+				"should be fine"
+			],
+			release
+				.commits
+				.iter()
+				.map(|v| v.message.as_str())
+				.collect::<Vec<&str>>()
+		);
+		assert_eq!(
+			vec![Some("someone"), Some("orhun")],
+			release
+				.github
+				.contributors
+				.iter()
+				.map(|v| v.username.as_deref())
+				.collect::<Vec<Option<&str>>>()
+		);
+
+		release.group_by_labels(&Regex::new("^lang/(.+)$|^rust$")?);
+		assert_eq!(
+			vec![Some("rust"), Some("rust"), None, None],
+			release
+				.commits
+				.iter()
+				.map(|v| v.group.as_deref())
+				.collect::<Vec<Option<&str>>>()
+		);
+
 		Ok(())
 	}
+
+	#[cfg(feature = "github")]
+	#[test]
+	fn update_github_milestone() {
+		let milestone = |number: i64, title: &str| GitHubMilestone {
+			number,
+			title: String::from(title),
+			state: String::from("open"),
+			html_url: Some(format!("https://github.com/o/r/milestone/{number}")),
+		};
+		let milestones = vec![milestone(1, "v1.0.0"), milestone(2, "1.1.0")];
+		let mut release = Release {
+			version: Some(String::from("v1.1.0")),
+			..Default::default()
+		};
+		release.update_github_milestone(&milestones);
+		assert_eq!(Some(milestone(2, "1.1.0")), release.github.milestone);
+		release.version = Some(String::from("1.2.0"));
+		release.update_github_milestone(&milestones);
+		assert_eq!(None, release.github.milestone);
+		release.version = None;
+		release.update_github_milestone(&milestones);
+		assert_eq!(None, release.github.milestone);
+	}
+
+	#[cfg(feature = "github")]
+	#[test]
+	fn merge_pull_request_commits() {
+		let commit =
+			|id: &str, message: &str, pr_number: Option<i64>, group| Commit {
+				group: Some(String::from(group)),
+				github: GitHubContributor {
+					pr_title: pr_number.map(|v| format!("PR #{v}")),
+					pr_number,
+					..Default::default()
+				},
+				..Commit::new(String::from(id), String::from(message))
+			};
+		let mut release = Release {
+			commits: vec![
+				commit("a1", "feat: add x", Some(1), "Features"),
+				commit("b2", "fix: fix y", None, "Bug Fixes"),
+				commit("c3", "feat: extend x", Some(1), "Features"),
+				commit("d4", "docs: document z", Some(2), "Documentation"),
+				commit("e5", "fix: fix x", Some(1), "Bug Fixes"),
+				commit("f6", "feat: test x", Some(1), "Features"),
+			],
+			..Default::default()
+		};
+		release.merge_pull_request_commits();
+		assert_eq!(
+			vec![
+				("a1", "PR #1", vec!["a1", "c3", "f6"]),
+				("b2", "fix: fix y", vec![]),
+				("d4", "docs: document z", vec![]),
+				("e5", "fix: fix x", vec![]),
+			],
+			release
+				.commits
+				.iter()
+				.map(|v| {
+					(
+						v.id.as_str(),
+						v.message.as_str(),
+						v.github.pr_commits.iter().map(|v| v.as_str()).collect(),
+					)
+				})
+				.collect::<Vec<(&str, &str, Vec<&str>)>>()
+		);
+	}
 }
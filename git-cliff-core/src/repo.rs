@@ -1,23 +1,52 @@
-use crate::config::Remote;
+use crate::audit::TagInfo;
+use crate::commit::SignatureStatus;
+use crate::config::{
+	PathRename,
+	Remote,
+};
 use crate::error::{
 	Error,
 	Result,
 };
+use crate::release::RepositoryMetadata;
 use git2::{
 	BranchType,
 	Commit,
 	DescribeOptions,
+	ObjectType,
 	Oid,
 	Repository as GitRepository,
 	Sort,
 };
 use glob::Pattern;
 use indexmap::IndexMap;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+};
 use regex::Regex;
-use std::io;
+use std::collections::{
+	HashMap,
+	HashSet,
+};
+use std::io::{
+	self,
+	Write,
+};
 use std::path::PathBuf;
+use std::process::{
+	self,
+	Stdio,
+};
+use std::str;
+use std::thread;
 use url::Url;
 
+/// Regex for matching the name of the repository in the remote URLs (e.g.
+/// `orhun/git-cliff` in `git@github.com:orhun/git-cliff.git`).
+static REMOTE_NAME_REGEX: Lazy<Regex> =
+	lazy_regex!(r"[:/]([^/:]+/[^/:]+?)(?:\.git)?/?$");
+
 /// Wrapper for [`Repository`] type from git2.
 ///
 /// [`Repository`]: GitRepository
@@ -43,16 +72,29 @@ impl Repository {
 	/// Parses and returns the commits.
 	///
 	/// Sorts the commits by their time.
+	///
+	/// The range can also be a single revision for the commits that are
+	/// reachable from it.
+	///
+	/// The changed paths are mapped with the given renames before they are
+	/// matched against the include and exclude patterns.
 	pub fn commits(
 		&self,
 		range: Option<String>,
 		include_path: Option<Vec<Pattern>>,
 		exclude_path: Option<Vec<Pattern>>,
+		path_renames: &[PathRename],
 	) -> Result<Vec<Commit>> {
 		let mut revwalk = self.inner.revwalk()?;
 		revwalk.set_sorting(Sort::TOPOLOGICAL)?;
 		if let Some(range) = range {
-			revwalk.push_range(&range)?;
+			if range.contains("..") {
+				revwalk.push_range(&range)?;
+			} else {
+				revwalk.push(
+					self.inner.revparse_single(&range)?.peel_to_commit()?.id(),
+				)?;
+			}
 		} else {
 			revwalk.push_head()?;
 		}
@@ -61,7 +103,31 @@ impl Repository {
 			.filter_map(|id| self.inner.find_commit(id).ok())
 			.collect();
 		if include_path.is_some() || exclude_path.is_some() {
+			let path_renames = path_renames
+				.iter()
+				.filter_map(|rename| match &rename.since {
+					Some(since) => match self.commits_before(since) {
+						Ok(commits) => Some((rename, Some(commits))),
+						Err(e) => {
+							warn!(
+								"Skipping the path rename of {} since {since} is \
+								 not found (e.g. in a shallow clone): {e}",
+								rename.old
+							);
+							None
+						}
+					},
+					None => Some((rename, None)),
+				})
+				.collect::<Vec<(&PathRename, Option<HashSet<Oid>>)>>();
 			commits.retain(|commit| {
+				let renames = path_renames
+					.iter()
+					.filter(|(_, before)| {
+						before.as_ref().map_or(true, |v| v.contains(&commit.id()))
+					})
+					.map(|(rename, _)| *rename)
+					.collect::<Vec<&PathRename>>();
 				if let Ok(prev_commit) = commit.parent(0) {
 					if let Ok(diff) = self.inner.diff_tree_to_tree(
 						commit.tree().ok().as_ref(),
@@ -71,15 +137,21 @@ impl Repository {
 						return diff
 							.deltas()
 							.filter_map(|delta| delta.new_file().path())
+							.map(|path| {
+								renames
+									.iter()
+									.find_map(|rename| rename.map(path))
+									.unwrap_or(path.to_path_buf())
+							})
 							.any(|new_file_path| {
 								if let Some(include_path) = &include_path {
-									include_path
-										.iter()
-										.any(|glob| glob.matches_path(new_file_path))
+									include_path.iter().any(|glob| {
+										glob.matches_path(&new_file_path)
+									})
 								} else if let Some(exclude_path) = &exclude_path {
-									!exclude_path
-										.iter()
-										.any(|glob| glob.matches_path(new_file_path))
+									!exclude_path.iter().any(|glob| {
+										glob.matches_path(&new_file_path)
+									})
 								} else {
 									false
 								}
@@ -92,6 +164,17 @@ impl Repository {
 		Ok(commits)
 	}
 
+	/// Returns the IDs of the commits that are reachable from the given
+	/// revision, excluding itself.
+	fn commits_before(&self, revision: &str) -> Result<HashSet<Oid>> {
+		let commit = self.inner.revparse_single(revision)?.peel_to_commit()?;
+		let mut revwalk = self.inner.revwalk()?;
+		for parent_id in commit.parent_ids() {
+			revwalk.push(parent_id)?;
+		}
+		Ok(revwalk.filter_map(|id| id.ok()).collect())
+	}
+
 	/// Returns the current tag.
 	///
 	/// It is the same as running `git describe --tags`
@@ -102,6 +185,175 @@ impl Repository {
 			.and_then(|describe| describe.format(None).ok())
 	}
 
+	/// Returns the names of the references (including `HEAD`) along with
+	/// their targets.
+	///
+	/// This is used for detecting the changes in the repository, e.g. the new
+	/// commits and tags.
+	pub fn references(&self) -> Result<Vec<(String, String)>> {
+		let mut references = Vec::new();
+		for reference in self
+			.inner
+			.references()?
+			.chain(self.inner.find_reference("HEAD").map(Ok))
+		{
+			let reference = reference?;
+			let target = reference
+				.target()
+				.map(|v| v.to_string())
+				.or_else(|| reference.symbolic_target().map(String::from))
+				.unwrap_or_default();
+			references.push((
+				String::from_utf8_lossy(reference.name_bytes()).to_string(),
+				target,
+			));
+		}
+		Ok(references)
+	}
+
+	/// Returns the IDs of the given commits that are already included in the
+	/// given reference (e.g. a branch that represents an environment).
+	///
+	/// A commit is included if it is reachable from the reference or if a
+	/// commit with the same patch (e.g. cherry-picked) is. Only the commits of
+	/// the reference after its merge base with the first (i.e. the latest)
+	/// commit are compared for the patches.
+	pub fn included_in(
+		&self,
+		commits: &[Commit],
+		reference: &str,
+	) -> Result<HashSet<String>> {
+		let target = self.inner.revparse_single(reference)?.peel_to_commit()?;
+		let mut revwalk = self.inner.revwalk()?;
+		revwalk.push(target.id())?;
+		for commit in commits {
+			revwalk.hide(commit.id())?;
+		}
+		if let Some(merge_bases) = commits
+			.first()
+			.and_then(|commit| self.inner.merge_bases(target.id(), commit.id()).ok())
+		{
+			for merge_base in merge_bases.iter() {
+				revwalk.hide(*merge_base)?;
+			}
+		}
+		let patch_ids = revwalk
+			.filter_map(|id| id.ok())
+			.filter_map(|id| self.inner.find_commit(id).ok())
+			.filter_map(|commit| self.patch_id(&commit))
+			.collect::<HashSet<Oid>>();
+		let mut included = HashSet::new();
+		for commit in commits {
+			if commit.id() == target.id() ||
+				self.inner.graph_descendant_of(target.id(), commit.id())? ||
+				self.patch_id(commit)
+					.is_some_and(|patch_id| patch_ids.contains(&patch_id))
+			{
+				included.insert(commit.id().to_string());
+			}
+		}
+		Ok(included)
+	}
+
+	/// Returns the IDs of the commits that are merged by the given merge
+	/// commits.
+	///
+	/// These are the commits that are reachable from the second parent of a
+	/// merge commit but not from the first one.
+	pub fn merged_commits(&self, merges: &[&Commit]) -> Result<HashSet<String>> {
+		let mut merged = HashSet::new();
+		for merge in merges {
+			let (Ok(base), Ok(head)) = (merge.parent_id(0), merge.parent_id(1))
+			else {
+				continue;
+			};
+			let mut revwalk = self.inner.revwalk()?;
+			revwalk.push(head)?;
+			revwalk.hide(base)?;
+			merged.extend(revwalk.filter_map(|id| id.ok()).map(|v| v.to_string()));
+		}
+		Ok(merged)
+	}
+
+	/// Returns the signature status and the signing key of the given commits.
+	///
+	/// The signatures are verified via `git log` so that the signing program
+	/// (e.g. GPG or SSH) and the trusted keys are used as configured in git.
+	pub fn signatures(
+		&self,
+		commits: &[Commit],
+	) -> Result<HashMap<String, (SignatureStatus, Option<String>)>> {
+		let mut child = process::Command::new("git")
+			.arg("--git-dir")
+			.arg(self.inner.path())
+			.args([
+				"log",
+				"--no-walk=unsorted",
+				"--stdin",
+				"--format=%H %G? %GF %GK",
+			])
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.spawn()?;
+		let input = commits
+			.iter()
+			.map(|commit| format!("{}\n", commit.id()))
+			.collect::<String>();
+		let mut stdin = child.stdin.take().ok_or_else(|| {
+			Error::RepoError(String::from("stdin of git is not captured"))
+		})?;
+		let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+		let output = child.wait_with_output()?;
+		writer.join().map_err(|_| {
+			Error::RepoError(String::from("failed to write the commits to git"))
+		})??;
+		if !output.status.success() {
+			return Err(Error::RepoError(format!(
+				"failed to verify the signatures: {}",
+				String::from_utf8_lossy(&output.stderr).trim()
+			)));
+		}
+		Ok(str::from_utf8(&output.stdout)?
+			.lines()
+			.filter_map(|line| {
+				let mut fields = line.split(' ');
+				let id = fields.next()?.to_string();
+				let status = SignatureStatus::from_code(fields.next()?);
+				let key = fields.find(|v| !v.is_empty()).map(String::from);
+				Some((id, (status, key)))
+			})
+			.collect())
+	}
+
+	/// Returns the patch ID of the given commit.
+	///
+	/// Merge commits do not have a patch ID.
+	fn patch_id(&self, commit: &Commit) -> Option<Oid> {
+		if commit.parent_count() > 1 {
+			return None;
+		}
+		let parent_tree = commit.parent(0).ok().and_then(|v| v.tree().ok());
+		self.inner
+			.diff_tree_to_tree(
+				parent_tree.as_ref(),
+				commit.tree().ok().as_ref(),
+				None,
+			)
+			.and_then(|diff| diff.patchid(None))
+			.ok()
+	}
+
+	/// Creates an annotated tag that points to HEAD.
+	///
+	/// The tagger is read from the git configuration.
+	pub fn create_annotated_tag(&self, name: &str, message: &str) -> Result<()> {
+		let head = self.inner.head()?.peel(ObjectType::Commit)?;
+		self.inner
+			.tag(name, &head, &self.inner.signature()?, message, false)?;
+		Ok(())
+	}
+
 	/// Returns the commit object of the given ID.
 	pub fn find_commit(&self, id: String) -> Option<Commit> {
 		if let Ok(oid) = Oid::from_str(&id) {
@@ -112,6 +364,16 @@ impl Repository {
 		None
 	}
 
+	/// Returns the commit that the given revision points to.
+	///
+	/// e.g. an abbreviated SHA1, a branch or `HEAD~1`
+	pub fn resolve_commit(&self, revision: &str) -> Option<Commit<'_>> {
+		self.inner
+			.revparse_single(revision)
+			.and_then(|v| v.peel_to_commit())
+			.ok()
+	}
+
 	/// Parses and returns a commit-tag map.
 	///
 	/// It collects lightweight and annotated tags.
@@ -152,6 +414,41 @@ impl Repository {
 			.collect())
 	}
 
+	/// Returns the details of the tags for auditing them.
+	///
+	/// The tags are sorted by the time of their commits. A tag is reachable if
+	/// its commit is contained in the given reference (e.g. the main branch).
+	pub fn tag_details(
+		&self,
+		pattern: &Option<Regex>,
+		reference: &str,
+	) -> Result<Vec<TagInfo>> {
+		let target = self.inner.revparse_single(reference)?.peel_to_commit()?;
+		let mut tags = Vec::new();
+		let tag_names = self.inner.tag_names(None)?;
+		for name in tag_names.iter().flatten().filter(|tag_name| {
+			pattern.as_ref().map_or(true, |pat| pat.is_match(tag_name))
+		}) {
+			let obj = self.inner.revparse_single(name)?;
+			let commit = obj.peel_to_commit()?;
+			let tag = obj.as_tag();
+			tags.push(TagInfo {
+				name:      name.to_string(),
+				commit_id: commit.id().to_string(),
+				timestamp: commit.time().seconds(),
+				annotated: tag.is_some(),
+				message:   tag
+					.and_then(|v| v.message())
+					.map(|v| v.trim().to_string())
+					.filter(|v| !v.is_empty()),
+				reachable: commit.id() == target.id() ||
+					self.inner.graph_descendant_of(target.id(), commit.id())?,
+			});
+		}
+		tags.sort_by_key(|v| v.timestamp);
+		Ok(tags)
+	}
+
 	/// Returns the remote of the upstream repository.
 	///
 	/// The strategy used here is the following:
@@ -159,6 +456,35 @@ impl Repository {
 	/// Find the branch that HEAD points to, and read the remote configured for
 	/// that branch returns the remote and the name of the local branch.
 	pub fn upstream_remote(&self) -> Result<Remote> {
+		let upstream_name = self.upstream_remote_name()?;
+		let origin = &self.inner.find_remote(&upstream_name)?;
+		let url = origin
+			.url()
+			.ok_or_else(|| {
+				Error::RepoError(String::from("failed to get the remote URL"))
+			})?
+			.to_string();
+		trace!("Upstream URL: {url}");
+		let url = Url::parse(&url)?;
+		let segments: Vec<&str> = url
+			.path_segments()
+			.ok_or_else(|| {
+				Error::RepoError(String::from("failed to get URL segments"))
+			})?
+			.rev()
+			.collect();
+		if let (Some(owner), Some(repo)) = (segments.get(1), segments.first()) {
+			return Ok(Remote {
+				owner: owner.to_string(),
+				repo:  repo.trim_end_matches(".git").to_string(),
+				token: None,
+			});
+		}
+		Err(Error::RepoError(String::from("no remotes configured")))
+	}
+
+	/// Returns the name of the remote that the current branch tracks.
+	fn upstream_remote_name(&self) -> Result<String> {
 		for branch in self.inner.branches(Some(BranchType::Local))? {
 			let branch = branch?.0;
 			if branch.is_head() {
@@ -168,42 +494,70 @@ impl Repository {
 						String::from("branch name is not valid")
 					))?
 				))?;
-				let upstream_name = upstream.as_str().ok_or_else(|| {
+				return upstream.as_str().map(String::from).ok_or_else(|| {
 					Error::RepoError(String::from(
 						"name of the upstream remote is not valid",
 					))
-				})?;
-				let origin = &self.inner.find_remote(upstream_name)?;
-				let url = origin
-					.url()
-					.ok_or_else(|| {
-						Error::RepoError(String::from(
-							"failed to get the remote URL",
-						))
-					})?
-					.to_string();
-				trace!("Upstream URL: {url}");
-				let url = Url::parse(&url)?;
-				let segments: Vec<&str> = url
-					.path_segments()
-					.ok_or_else(|| {
-						Error::RepoError(String::from("failed to get URL segments"))
-					})?
-					.rev()
-					.collect();
-				if let (Some(owner), Some(repo)) =
-					(segments.get(1), segments.first())
-				{
-					return Ok(Remote {
-						owner: owner.to_string(),
-						repo:  repo.trim_end_matches(".git").to_string(),
-						token: None,
-					});
-				}
+				});
 			}
 		}
 		Err(Error::RepoError(String::from("no remotes configured")))
 	}
+
+	/// Returns the metadata of the repository.
+	///
+	/// The remote is the upstream remote of the current branch or `origin`.
+	/// The name is read from the URL of the remote (e.g. `orhun/git-cliff`) or
+	/// it is the name of the working directory if there is no remote. The
+	/// default branch is the `HEAD` of the remote or the current branch if it
+	/// is not known.
+	pub fn metadata(&self) -> RepositoryMetadata {
+		let remote = self.upstream_remote_name().ok().or_else(|| {
+			self.inner
+				.find_remote("origin")
+				.ok()
+				.map(|_| String::from("origin"))
+		});
+		let url = remote
+			.as_ref()
+			.and_then(|name| self.inner.find_remote(name).ok())
+			.and_then(|remote| remote.url().map(String::from));
+		let name = url
+			.as_ref()
+			.and_then(|url| REMOTE_NAME_REGEX.captures(url))
+			.map(|captures| captures[1].to_string())
+			.or_else(|| {
+				self.inner
+					.workdir()
+					.and_then(|path| path.file_name())
+					.map(|name| name.to_string_lossy().to_string())
+			});
+		let default_branch = remote
+			.as_ref()
+			.and_then(|remote| {
+				let reference = self
+					.inner
+					.find_reference(&format!("refs/remotes/{remote}/HEAD"))
+					.ok()?;
+				reference
+					.symbolic_target()?
+					.strip_prefix(&format!("refs/remotes/{remote}/"))
+					.map(String::from)
+			})
+			.or_else(|| {
+				self.inner
+					.head()
+					.ok()
+					.filter(|head| head.is_branch())
+					.and_then(|head| head.shorthand().map(String::from))
+			});
+		RepositoryMetadata {
+			name,
+			default_branch,
+			url,
+			..Default::default()
+		}
+	}
 }
 
 #[cfg(test)]
@@ -212,6 +566,8 @@ mod test {
 	use crate::commit::Commit as AppCommit;
 	use git_conventional::ErrorKind;
 	use std::env;
+	use std::fs;
+	use std::path::Path;
 	use std::process::Command;
 	use std::str;
 
@@ -227,6 +583,35 @@ mod test {
 		.to_string())
 	}
 
+	/// Runs the given git command in the given directory.
+	fn git(dir: &Path, args: &[&str]) -> Result<String> {
+		let output = Command::new("git")
+			.arg("-C")
+			.arg(dir)
+			.args(["-c", "user.name=a", "-c", "user.email=a@b"])
+			.args(args)
+			.output()?;
+		if !output.status.success() {
+			return Err(Error::RepoError(format!(
+				"git {} failed: {}",
+				args.join(" "),
+				String::from_utf8_lossy(&output.stderr).trim()
+			)));
+		}
+		Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+	}
+
+	/// Writes the given file and commits it with the given message.
+	fn commit_file(dir: &Path, file: &str, message: &str) -> Result<String> {
+		if let Some(parent) = dir.join(file).parent() {
+			fs::create_dir_all(parent)?;
+		}
+		fs::write(dir.join(file), message)?;
+		git(dir, &["add", file])?;
+		git(dir, &["commit", "-q", "-m", message])?;
+		git(dir, &["rev-parse", "HEAD"])
+	}
+
 	fn get_last_tag() -> Result<String> {
 		Ok(str::from_utf8(
 			Command::new("git")
@@ -247,7 +632,7 @@ mod test {
 				.expect("parent directory not found")
 				.to_path_buf(),
 		)?;
-		let commits = repository.commits(None, None, None)?;
+		let commits = repository.commits(None, None, None, &[])?;
 		let last_commit =
 			AppCommit::from(&commits.first().expect("no commits found").clone());
 		assert_eq!(get_last_commit_hash()?, last_commit.id);
@@ -306,6 +691,126 @@ mod test {
 		Ok(())
 	}
 
+	#[test]
+	fn repository_metadata() -> Result<()> {
+		let repository = Repository::init(
+			PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+				.parent()
+				.expect("parent directory not found")
+				.to_path_buf(),
+		)?;
+		let metadata = repository.metadata();
+		assert!(metadata.name.is_some());
+		assert!(metadata.default_branch.is_some());
+		for (url, name) in [
+			("https://github.com/orhun/git-cliff.git", "orhun/git-cliff"),
+			("git@github.com:orhun/git-cliff.git", "orhun/git-cliff"),
+			("https://gitlab.com/group/project/", "group/project"),
+		] {
+			assert_eq!(name, &REMOTE_NAME_REGEX.captures(url).expect("no match")[1]);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn upstream_commits() -> Result<()> {
+		let dir = tempfile::tempdir()?;
+		git(dir.path(), &["init", "-q"])?;
+		commit_file(dir.path(), "a", "feat: add a")?;
+		git(dir.path(), &["checkout", "-q", "-b", "upstream"])?;
+		commit_file(dir.path(), "b", "feat: add b")?;
+		commit_file(dir.path(), "c", "fix: fix c")?;
+		git(dir.path(), &["checkout", "-q", "-"])?;
+		let picked = commit_file(dir.path(), "c", "fix: fix c")?;
+		let fork = commit_file(dir.path(), "d", "feat: add d")?;
+
+		let repository = Repository::init(dir.path().to_path_buf())?;
+		let commits = repository.commits(
+			Some(String::from("upstream..HEAD")),
+			None,
+			None,
+			&[],
+		)?;
+		assert_eq!(
+			vec![fork, picked.clone()],
+			commits
+				.iter()
+				.map(|v| v.id().to_string())
+				.collect::<Vec<String>>()
+		);
+		assert_eq!(
+			HashSet::from([picked]),
+			repository.included_in(&commits, "upstream")?
+		);
+		let commits = repository.commits(None, None, None, &[])?;
+		assert_eq!(2, repository.included_in(&commits, "upstream")?.len());
+		Ok(())
+	}
+
+	#[test]
+	fn repository_references() -> Result<()> {
+		let dir = tempfile::tempdir()?;
+		git(dir.path(), &["init", "-q"])?;
+		commit_file(dir.path(), "a", "feat: add a")?;
+		let repository = Repository::init(dir.path().to_path_buf())?;
+		let references = repository.references()?;
+		assert!(references.iter().any(|(name, _)| name == "HEAD"));
+
+		let commit = commit_file(dir.path(), "b", "feat: add b")?;
+		git(dir.path(), &["tag", "v0.1.0"])?;
+		let new_references = repository.references()?;
+		assert_ne!(references, new_references);
+		assert!(new_references.contains(&(String::from("refs/tags/v0.1.0"), commit)));
+		Ok(())
+	}
+
+	#[test]
+	fn renamed_paths() -> Result<()> {
+		let dir = tempfile::tempdir()?;
+		git(dir.path(), &["init", "-q"])?;
+		commit_file(dir.path(), "README.md", "docs: add readme")?;
+		let added = commit_file(dir.path(), "lib/core/a", "feat: add a")?;
+		let other = commit_file(dir.path(), "docs/x", "docs: add x")?;
+		fs::create_dir_all(dir.path().join("crates"))?;
+		git(dir.path(), &["mv", "lib/core", "crates/core"])?;
+		git(dir.path(), &["commit", "-q", "-m", "refactor: move core"])?;
+		let moved = git(dir.path(), &["rev-parse", "HEAD"])?;
+		let last = commit_file(dir.path(), "crates/core/b", "feat: add b")?;
+		commit_file(dir.path(), "lib/core/c", "feat: add c")?;
+
+		let include_path = Some(vec![
+			Pattern::new("crates/core/**").expect("the pattern is not valid")
+		]);
+		let commit_ids = |path: &Path, since: &str| -> Result<Vec<String>> {
+			Ok(Repository::init(path.to_path_buf())?
+				.commits(None, include_path.clone(), None, &[PathRename {
+					old:   String::from("lib/core"),
+					new:   String::from("crates/core"),
+					since: Some(since.to_string()),
+				}])?
+				.iter()
+				.map(|v| v.id().to_string())
+				.collect())
+		};
+		assert_eq!(
+			vec![last.clone(), moved.clone(), added],
+			commit_ids(dir.path(), &moved)?
+		);
+
+		let shallow = dir.path().join("shallow");
+		git(dir.path(), &[
+			"clone",
+			"-q",
+			"--depth",
+			"3",
+			&format!("file://{}", dir.path().display()),
+			&shallow.to_string_lossy(),
+		])?;
+		// the parent of the move is not fetched
+		assert_eq!(vec![last], commit_ids(&shallow, &other)?);
+		Ok(())
+	}
+
 	#[test]
 	fn git_upstream_remote() -> Result<()> {
 		let repository = Repository::init(
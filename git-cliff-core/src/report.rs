@@ -0,0 +1,267 @@
+use crate::config::LintSeverity;
+use crate::error::Result;
+use crate::lint::{
+	LintViolation,
+	OutputLintViolation,
+};
+use crate::rules::RuleUsage;
+use std::io::Write;
+use std::path::Path;
+
+/// Finding of a check (e.g. a lint violation).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+	/// Name of the violated rule.
+	pub rule:      String,
+	/// Severity of the finding.
+	pub severity:  LintSeverity,
+	/// Description of the finding.
+	pub message:   String,
+	/// File that the finding is in.
+	pub file:      Option<String>,
+	/// Line number in the file (starting from 1).
+	pub line:      Option<usize>,
+	/// SHA1 of the commit that the finding is about.
+	pub commit_id: Option<String>,
+}
+
+impl Finding {
+	/// Returns the location of the finding for displaying.
+	fn location(&self) -> Option<String> {
+		match (&self.file, self.line, &self.commit_id) {
+			(Some(file), Some(line), _) => Some(format!("{file}:{line}")),
+			(Some(file), None, _) => Some(file.to_string()),
+			(None, _, Some(commit_id)) => {
+				Some(commit_id.get(..7).unwrap_or(commit_id).to_string())
+			}
+			_ => None,
+		}
+	}
+}
+
+impl From<&LintViolation> for Finding {
+	fn from(violation: &LintViolation) -> Self {
+		Self {
+			rule:      violation.rule.to_string(),
+			severity:  violation.severity,
+			message:   violation.message.to_string(),
+			file:      None,
+			line:      None,
+			commit_id: Some(violation.commit_id.to_string()),
+		}
+	}
+}
+
+impl From<(&Path, &OutputLintViolation)> for Finding {
+	fn from((path, violation): (&Path, &OutputLintViolation)) -> Self {
+		Self {
+			rule:      violation.rule.to_string(),
+			severity:  violation.severity,
+			message:   violation.message.to_string(),
+			file:      Some(path.to_string_lossy().to_string()),
+			line:      Some(violation.line),
+			commit_id: None,
+		}
+	}
+}
+
+impl From<&RuleUsage> for Finding {
+	fn from(usage: &RuleUsage) -> Self {
+		Self {
+			rule:      String::from("dead_rule"),
+			severity:  LintSeverity::Error,
+			message:   usage.to_string(),
+			file:      None,
+			line:      None,
+			commit_id: None,
+		}
+	}
+}
+
+/// Report of the findings for the CI systems.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+	/// Name of the check (e.g. `lint`).
+	pub name:     String,
+	/// Findings of the check.
+	pub findings: Vec<Finding>,
+}
+
+impl Report {
+	/// Constructs a new instance.
+	pub fn new(name: &str, findings: Vec<Finding>) -> Self {
+		Self {
+			name: name.to_string(),
+			findings,
+		}
+	}
+
+	/// Writes the report as JUnit XML.
+	///
+	/// Each finding is a failed test case and the type of the failure is the
+	/// severity of the finding.
+	pub fn write_junit<W: Write + ?Sized>(&self, out: &mut W) -> Result<()> {
+		let name = escape_xml(&self.name);
+		let count = self.findings.len();
+		writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+		writeln!(
+			out,
+			r#"<testsuites name="git-cliff" tests="{count}" failures="{count}">"#
+		)?;
+		writeln!(
+			out,
+			r#"  <testsuite name="{name}" tests="{count}" failures="{count}" errors="0">"#
+		)?;
+		for finding in &self.findings {
+			let test_name = match finding.location() {
+				Some(location) => format!("{} ({location})", finding.rule),
+				None => finding.rule.to_string(),
+			};
+			writeln!(
+				out,
+				r#"    <testcase name="{}" classname="git-cliff.{name}">"#,
+				escape_xml(&test_name)
+			)?;
+			writeln!(
+				out,
+				r#"      <failure type="{}" message="{}"/>"#,
+				finding.severity,
+				escape_xml(&finding.message)
+			)?;
+			writeln!(out, "    </testcase>")?;
+		}
+		writeln!(out, "  </testsuite>")?;
+		writeln!(out, "</testsuites>")?;
+		Ok(())
+	}
+
+	/// Writes the report as GitHub Actions workflow commands.
+	///
+	/// The findings are shown as annotations on the pull requests.
+	pub fn write_github<W: Write + ?Sized>(&self, out: &mut W) -> Result<()> {
+		for finding in &self.findings {
+			let level = match finding.severity {
+				LintSeverity::Error => "error",
+				_ => "warning",
+			};
+			let mut properties = Vec::new();
+			if let Some(file) = &finding.file {
+				properties.push(format!("file={}", escape_property(file)));
+			}
+			if let Some(line) = finding.line {
+				properties.push(format!("line={line}"));
+			}
+			properties.push(format!(
+				"title={}",
+				escape_property(&format!(
+					"git-cliff {} [{}]",
+					self.name, finding.rule
+				))
+			));
+			let message = match (&finding.file, finding.location()) {
+				(None, Some(location)) => format!("{location}: {}", finding.message),
+				_ => finding.message.to_string(),
+			};
+			writeln!(
+				out,
+				"::{level} {}::{}",
+				properties.join(","),
+				escape_data(&message)
+			)?;
+		}
+		Ok(())
+	}
+}
+
+/// Escapes the special characters of XML.
+fn escape_xml(value: &str) -> String {
+	value
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&apos;")
+}
+
+/// Escapes the data of a workflow command.
+fn escape_data(value: &str) -> String {
+	value
+		.replace('%', "%25")
+		.replace('\r', "%0D")
+		.replace('\n', "%0A")
+}
+
+/// Escapes the property of a workflow command.
+fn escape_property(value: &str) -> String {
+	escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::lint::{
+		LintRule,
+		OutputLintRule,
+	};
+	use pretty_assertions::assert_eq;
+
+	fn report() -> Report {
+		Report::new("lint", vec![
+			Finding::from(&LintViolation {
+				commit_id: String::from("1a2b3c4d5e6f"),
+				rule:      LintRule::SubjectLength,
+				severity:  LintSeverity::Warning,
+				message:   String::from("subject is longer than 72 characters"),
+			}),
+			Finding::from((Path::new("CHANGELOG.md"), &OutputLintViolation {
+				line:     3,
+				rule:     OutputLintRule::BrokenLinks,
+				severity: LintSeverity::Error,
+				message:  String::from("<link> is not found, 100% broken"),
+			})),
+		])
+	}
+
+	#[test]
+	fn write_junit() -> Result<()> {
+		let mut output = Vec::new();
+		report().write_junit(&mut output)?;
+		assert_eq!(
+			[
+				r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+				r#"<testsuites name="git-cliff" tests="2" failures="2">"#,
+				r#"  <testsuite name="lint" tests="2" failures="2" errors="0">"#,
+				r#"    <testcase name="subject_length (1a2b3c4)" classname="git-cliff.lint">"#,
+				r#"      <failure type="warning" message="subject is longer than 72 characters"/>"#,
+				r#"    </testcase>"#,
+				r#"    <testcase name="broken_links (CHANGELOG.md:3)" classname="git-cliff.lint">"#,
+				r#"      <failure type="error" message="&lt;link&gt; is not found, 100% broken"/>"#,
+				r#"    </testcase>"#,
+				r#"  </testsuite>"#,
+				r#"</testsuites>"#,
+				"",
+			]
+			.join("\n"),
+			String::from_utf8_lossy(&output)
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn write_github() -> Result<()> {
+		let mut output = Vec::new();
+		report().write_github(&mut output)?;
+		assert_eq!(
+			[
+				"::warning title=git-cliff lint [subject_length]::1a2b3c4: subject \
+				 is longer than 72 characters",
+				"::error file=CHANGELOG.md,line=3,title=git-cliff lint \
+				 [broken_links]::<link> is not found, 100%25 broken",
+				"",
+			]
+			.join("\n"),
+			String::from_utf8_lossy(&output)
+		);
+		Ok(())
+	}
+}
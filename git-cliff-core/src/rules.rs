@@ -0,0 +1,270 @@
+use crate::commit::{
+	Commit,
+	ProcessStep,
+};
+use crate::config::{
+	CommitParser,
+	GitConfig,
+};
+use crate::error::{
+	Error,
+	Result,
+};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Usage of a rule in the configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleUsage {
+	/// Key of the rule (e.g. `git.commit_parsers[2]`).
+	pub key:     String,
+	/// What the rule matches (e.g. `message = "^feat"`).
+	pub pattern: String,
+	/// Number of the commits that the rule matches.
+	pub matches: usize,
+}
+
+impl RuleUsage {
+	/// Constructs a new instance without any matches.
+	fn new(key: String, pattern: String) -> Self {
+		Self {
+			key,
+			pattern,
+			matches: 0,
+		}
+	}
+
+	/// Returns `true` if the rule does not match any commit.
+	pub fn is_dead(&self) -> bool {
+		self.matches == 0
+	}
+}
+
+impl fmt::Display for RuleUsage {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} ({}): ", self.key, self.pattern)?;
+		match self.matches {
+			0 => write!(f, "does not match any commit"),
+			1 => write!(f, "matches 1 commit"),
+			n => write!(f, "matches {n} commits"),
+		}
+	}
+}
+
+/// Returns what the commit parser matches for displaying.
+fn describe_parser(parser: &CommitParser) -> String {
+	if let Some(message) = &parser.message {
+		format!("message = {:?}", message.as_str())
+	} else if let Some(body) = &parser.body {
+		format!("body = {:?}", body.as_str())
+	} else if let (Some(field), Some(pattern)) = (&parser.field, &parser.pattern) {
+		format!("field = {field:?}, pattern = {:?}", pattern.as_str())
+	} else if let Some(sha) = &parser.sha {
+		format!("sha = {sha:?}")
+	} else {
+		String::from("conditions")
+	}
+}
+
+/// Returns the usage of the commit preprocessors, scope aliases, commit
+/// parsers and link parsers over the given commits.
+///
+/// The commits are processed the same way as they would be while generating
+/// the changelog (see [`Commit::process_with_trace`]) so that the rules are
+/// only counted for the commits that reach them. For example, the commit
+/// parsers do not match the unconventional commits that are filtered out
+/// beforehand and the link parsers do not match the skipped commits.
+pub fn rule_usage<'a, 'b: 'a>(
+	commits: impl IntoIterator<Item = &'a Commit<'b>>,
+	config: &GitConfig,
+) -> Result<Vec<RuleUsage>> {
+	let preprocessors = config.commit_preprocessors.as_deref().unwrap_or_default();
+	let mut aliases = config
+		.scope_aliases
+		.iter()
+		.flatten()
+		.collect::<Vec<(&String, &String)>>();
+	aliases.sort();
+	let parsers = config.commit_parsers.as_deref().unwrap_or_default();
+	let link_parsers = config.link_parsers.as_deref().unwrap_or_default();
+	let mut usage = preprocessors
+		.iter()
+		.enumerate()
+		.map(|(i, v)| {
+			RuleUsage::new(
+				format!("git.commit_preprocessors[{i}]"),
+				format!("pattern = {:?}", v.pattern.as_str()),
+			)
+		})
+		.chain(aliases.iter().map(|(alias, scope)| {
+			RuleUsage::new(
+				format!("git.scope_aliases.{alias}"),
+				format!("scope = {scope:?}"),
+			)
+		}))
+		.chain(parsers.iter().enumerate().map(|(i, v)| {
+			RuleUsage::new(format!("git.commit_parsers[{i}]"), describe_parser(v))
+		}))
+		.chain(link_parsers.iter().enumerate().map(|(i, v)| {
+			RuleUsage::new(
+				format!("git.link_parsers[{i}]"),
+				format!("pattern = {:?}", v.pattern.as_str()),
+			)
+		}))
+		.collect::<Vec<RuleUsage>>();
+	let alias_offset = preprocessors.len();
+	let parser_offset = alias_offset + aliases.len();
+	let link_parser_offset = parser_offset + parsers.len();
+	for commit in commits {
+		// the rules are counted once for the lines of a split commit
+		let mut matched = HashSet::new();
+		for commit in commit.split(config) {
+			let processed = commit.process_with_trace(config, |step| match step {
+				ProcessStep::Preprocessor {
+					index,
+					matched: true,
+					..
+				} => {
+					matched.insert(index);
+				}
+				ProcessStep::ScopeAlias { alias, .. } => {
+					if let Some(i) = aliases.iter().position(|v| *v.0 == alias) {
+						matched.insert(alias_offset + i);
+					}
+				}
+				ProcessStep::Parser { index, .. } => {
+					matched.insert(parser_offset + index);
+				}
+				ProcessStep::LinkParser { index, .. } => {
+					matched.insert(link_parser_offset + index);
+				}
+				_ => {}
+			});
+			match processed {
+				Ok(_) | Err(Error::GroupError(_) | Error::ParseError(_)) => {}
+				Err(e) => return Err(e),
+			}
+		}
+		for i in matched {
+			usage[i].matches += 1;
+		}
+	}
+	Ok(usage)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::config::{
+		LinkParser,
+		TextProcessor,
+	};
+	use pretty_assertions::assert_eq;
+	use regex::Regex;
+	use std::collections::HashMap;
+
+	#[test]
+	fn dead_rules() -> Result<()> {
+		let config = GitConfig {
+			commit_preprocessors: Some(vec![
+				TextProcessor {
+					pattern:         Regex::new(r"^feat\(gui\)")?,
+					replace:         Some(String::from("feat(ui)")),
+					replace_command: None,
+				},
+				TextProcessor {
+					pattern:         Regex::new(r"^feature")?,
+					replace:         Some(String::from("feat")),
+					replace_command: None,
+				},
+				TextProcessor {
+					pattern:         Regex::new(r"\(ui\)")?,
+					replace:         Some(String::from("(ui)")),
+					replace_command: None,
+				},
+			]),
+			scope_aliases: Some(HashMap::from([
+				(String::from("web"), String::from("ui")),
+				(String::from("frontend"), String::from("ui")),
+			])),
+			commit_parsers: Some(vec![
+				CommitParser {
+					message: Regex::new("^chore").ok(),
+					skip: Some(true),
+					..Default::default()
+				},
+				CommitParser {
+					message: Regex::new("^feat").ok(),
+					group: Some(String::from("Features")),
+					..Default::default()
+				},
+				CommitParser {
+					body: Regex::new("deprecated").ok(),
+					group: Some(String::from("Deprecations")),
+					..Default::default()
+				},
+			]),
+			link_parsers: Some(vec![
+				LinkParser {
+					pattern: Regex::new("#(\\d+)")?,
+					href:    String::from("https://x.y/$1"),
+					text:    None,
+				},
+				LinkParser {
+					pattern: Regex::new("RFC(\\d+)")?,
+					href:    String::from("https://x.y/rfc$1"),
+					text:    None,
+				},
+			]),
+			..Default::default()
+		};
+		let commits = [
+			Commit::new(String::from("1"), String::from("feat(gui): add x (#1)")),
+			Commit::new(String::from("2"), String::from("feat: add y")),
+			Commit::new(String::from("3"), String::from("chore: RFC1 (#2)")),
+			Commit::new(String::from("4"), String::from("RFC2")),
+			Commit::new(
+				String::from("5"),
+				String::from("fix(frontend): add z\n\nfeat: add w"),
+			),
+		];
+		let usage = rule_usage(&commits, &config)?;
+		assert_eq!(
+			vec![
+				("git.commit_preprocessors[0]", 1),
+				("git.commit_preprocessors[1]", 0),
+				("git.commit_preprocessors[2]", 1),
+				("git.scope_aliases.frontend", 1),
+				("git.scope_aliases.web", 0),
+				("git.commit_parsers[0]", 1),
+				("git.commit_parsers[1]", 2),
+				("git.commit_parsers[2]", 0),
+				("git.link_parsers[0]", 1),
+				("git.link_parsers[1]", 0),
+			],
+			usage
+				.iter()
+				.map(|v| (v.key.as_str(), v.matches))
+				.collect::<Vec<(&str, usize)>>()
+		);
+		assert_eq!(
+			r#"git.commit_parsers[2] (body = "deprecated"): does not match any commit"#,
+			usage[7].to_string()
+		);
+		assert_eq!(
+			r#"git.commit_parsers[1] (message = "^feat"): matches 2 commits"#,
+			usage[6].to_string()
+		);
+
+		let config = GitConfig {
+			split_commits: Some(true),
+			..config
+		};
+		let usage = rule_usage(&commits, &config)?;
+		assert_eq!(
+			r#"git.commit_parsers[1] (message = "^feat"): matches 3 commits"#,
+			usage[6].to_string()
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,198 @@
+use crate::collation::Collator;
+use crate::commit::Commit;
+use crate::config::civil_date;
+use crate::error::Result;
+use git_conventional::Commit as ConventionalCommit;
+use std::io::Write;
+
+/// Usage of a commit scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeUsage {
+	/// Name of the scope.
+	pub name:            String,
+	/// Number of the commits with the scope.
+	pub count:           usize,
+	/// SHA1 of the first commit with the scope.
+	pub first_commit:    String,
+	/// Committer timestamp of the first commit with the scope.
+	pub first_timestamp: i64,
+	/// SHA1 of the last commit with the scope.
+	pub last_commit:     String,
+	/// Committer timestamp of the last commit with the scope.
+	pub last_timestamp:  i64,
+}
+
+/// Inventory of the scopes that are used in the conventional commits.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScopeInventory {
+	/// Scopes sorted by the number of commits.
+	pub scopes: Vec<ScopeUsage>,
+}
+
+impl ScopeInventory {
+	/// Constructs a new instance from the given commits.
+	///
+	/// The commits that are not conventional or do not have a scope are
+	/// ignored.
+	pub fn new<'a, 'b: 'a>(
+		commits: impl IntoIterator<Item = &'a Commit<'b>>,
+	) -> Self {
+		let mut scopes: Vec<ScopeUsage> = Vec::new();
+		for commit in commits {
+			let Some(scope) = ConventionalCommit::parse(commit.message.trim_end())
+				.ok()
+				.and_then(|v| v.scope().map(|v| v.to_string()))
+			else {
+				continue;
+			};
+			let timestamp = commit.committer.timestamp;
+			match scopes.iter_mut().find(|v| v.name == scope) {
+				Some(usage) => {
+					usage.count += 1;
+					if timestamp < usage.first_timestamp {
+						usage.first_commit = commit.id.to_string();
+						usage.first_timestamp = timestamp;
+					}
+					if timestamp >= usage.last_timestamp {
+						usage.last_commit = commit.id.to_string();
+						usage.last_timestamp = timestamp;
+					}
+				}
+				None => scopes.push(ScopeUsage {
+					name:            scope,
+					count:           1,
+					first_commit:    commit.id.to_string(),
+					first_timestamp: timestamp,
+					last_commit:     commit.id.to_string(),
+					last_timestamp:  timestamp,
+				}),
+			}
+		}
+		scopes.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(&b.name)));
+		Self { scopes }
+	}
+
+	/// Sorts the scopes with the same number of commits by name with the
+	/// given collator.
+	pub fn collate(&mut self, collator: &Collator) {
+		self.scopes.sort_by(|a, b| {
+			b.count
+				.cmp(&a.count)
+				.then_with(|| collator.compare(&a.name, &b.name))
+		});
+	}
+
+	/// Returns the scopes that are not in the given allowed scopes.
+	pub fn disallowed(&self, allowed: &[String]) -> Vec<&ScopeUsage> {
+		self.scopes
+			.iter()
+			.filter(|v| !allowed.contains(&v.name))
+			.collect()
+	}
+
+	/// Writes the scopes as a table.
+	pub fn write<W: Write>(&self, out: &mut W) -> Result<()> {
+		let usage = |commit: &str, timestamp: i64| {
+			let (year, month, day) = civil_date(timestamp);
+			format!(
+				"{year}-{month:02}-{day:02} ({})",
+				commit.get(..7).unwrap_or(commit)
+			)
+		};
+		let rows = self
+			.scopes
+			.iter()
+			.map(|v| {
+				[
+					v.name.to_string(),
+					v.count.to_string(),
+					usage(&v.first_commit, v.first_timestamp),
+					usage(&v.last_commit, v.last_timestamp),
+				]
+			})
+			.collect::<Vec<[String; 4]>>();
+		let header = ["SCOPE", "COMMITS", "FIRST", "LAST"].map(String::from);
+		let widths = (0..3)
+			.map(|i| {
+				rows.iter()
+					.chain(Some(&header))
+					.map(|row| row[i].chars().count())
+					.max()
+					.unwrap_or_default()
+			})
+			.collect::<Vec<usize>>();
+		for row in Some(&header).into_iter().chain(rows.iter()) {
+			writeln!(
+				out,
+				"{:<w0$}  {:<w1$}  {:<w2$}  {}",
+				row[0],
+				row[1],
+				row[2],
+				row[3],
+				w0 = widths[0],
+				w1 = widths[1],
+				w2 = widths[2],
+			)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::commit::Signature;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn scope_inventory() -> Result<()> {
+		let commits = [
+			("a1", "feat(api): add xyz", 86400),
+			("a2", "fix(cli): fix xyz", 86400 * 2),
+			("a3", "fix(api): fix xyz", 86400 * 3),
+			("a4", "docs: update xyz", 86400 * 4),
+			("a5", "update xyz", 86400 * 5),
+		]
+		.into_iter()
+		.map(|(id, message, timestamp)| Commit {
+			committer: Signature {
+				timestamp,
+				..Default::default()
+			},
+			..Commit::new(String::from(id), String::from(message))
+		})
+		.collect::<Vec<Commit>>();
+		let inventory = ScopeInventory::new(&commits);
+		assert_eq!(
+			vec![("api", 2, "a1", "a3"), ("cli", 1, "a2", "a2")],
+			inventory
+				.scopes
+				.iter()
+				.map(|v| (
+					v.name.as_str(),
+					v.count,
+					v.first_commit.as_str(),
+					v.last_commit.as_str()
+				))
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			vec!["cli"],
+			inventory
+				.disallowed(&[String::from("api")])
+				.iter()
+				.map(|v| v.name.as_str())
+				.collect::<Vec<_>>()
+		);
+		let mut output = Vec::new();
+		inventory.write(&mut output)?;
+		assert_eq!(
+			"SCOPE  COMMITS  FIRST            LAST
+api    2        1970-01-02 (a1)  1970-01-04 (a3)
+cli    1        1970-01-03 (a2)  1970-01-03 (a2)
+",
+			String::from_utf8(output).expect("invalid UTF-8")
+		);
+		Ok(())
+	}
+}
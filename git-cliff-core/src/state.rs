@@ -0,0 +1,167 @@
+use crate::error::Result;
+use crate::release::Release;
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::fs;
+use std::path::Path;
+
+/// State of a generated release.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseState {
+	/// Release version, git tag.
+	pub version:   String,
+	/// Commit ID of the tag.
+	pub commit_id: String,
+}
+
+/// State of the previously generated changelog.
+///
+/// This is stored in a file between the runs for detecting the changes in
+/// the repository (e.g. moved tags).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct State {
+	/// Generated releases.
+	#[serde(default)]
+	pub releases:    Vec<ReleaseState>,
+	/// ID of the last commit that is included in the changelog.
+	#[serde(default)]
+	pub last_commit: Option<String>,
+}
+
+impl State {
+	/// Loads the state from the given file.
+	///
+	/// Returns the default state if the file does not exist.
+	pub fn load(path: &Path) -> Result<Self> {
+		if path.exists() {
+			Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+		} else {
+			Ok(Self::default())
+		}
+	}
+
+	/// Returns the state as JSON.
+	pub fn as_json(&self) -> Result<String> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+
+	/// Saves the state to the given file.
+	pub fn save(&self, path: &Path) -> Result<()> {
+		fs::write(path, self.as_json()?)?;
+		Ok(())
+	}
+
+	/// Annotates the releases whose tags are moved since the last run.
+	///
+	/// Sets [`moved_from`] to the commit ID that the tag previously pointed
+	/// to.
+	///
+	/// [`moved_from`]: Release::moved_from
+	pub fn annotate_moved_tags(&self, releases: &mut [Release]) {
+		for release in releases.iter_mut() {
+			if let (Some(version), Some(commit_id)) =
+				(&release.version, &release.commit_id)
+			{
+				if let Some(state) =
+					self.releases.iter().find(|state| &state.version == version)
+				{
+					if &state.commit_id != commit_id {
+						warn!(
+							"Tag {version} is moved from {} to {}",
+							state.commit_id, commit_id
+						);
+						release.moved_from = Some(state.commit_id.clone());
+					}
+				}
+			}
+		}
+	}
+
+	/// Updates the state with the given releases.
+	pub fn update(&mut self, releases: &[Release]) {
+		for release in releases {
+			if let (Some(version), Some(commit_id)) =
+				(&release.version, &release.commit_id)
+			{
+				match self
+					.releases
+					.iter_mut()
+					.find(|state| &state.version == version)
+				{
+					Some(state) => state.commit_id = commit_id.to_string(),
+					None => self.releases.push(ReleaseState {
+						version:   version.to_string(),
+						commit_id: commit_id.to_string(),
+					}),
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn save_and_load() -> Result<()> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("state.json");
+		let state = State {
+			releases:    vec![ReleaseState {
+				version:   String::from("v1.0.0"),
+				commit_id: String::from("abc123"),
+			}],
+			last_commit: Some(String::from("def456")),
+		};
+		state.save(&path)?;
+		assert_eq!(state, State::load(&path)?);
+		fs::remove_file(&path)?;
+		assert_eq!(State::default(), State::load(&path)?);
+		Ok(())
+	}
+
+	#[test]
+	fn moved_tags() {
+		let mut state = State::default();
+		let mut releases = vec![
+			Release {
+				version: Some(String::from("v1.0.0")),
+				commit_id: Some(String::from("abc123")),
+				..Default::default()
+			},
+			Release {
+				version: Some(String::from("v1.1.0")),
+				commit_id: Some(String::from("def456")),
+				..Default::default()
+			},
+			Release::default(),
+		];
+		state.update(&releases);
+		assert_eq!(2, state.releases.len());
+
+		releases[1].commit_id = Some(String::from("fff000"));
+		state.annotate_moved_tags(&mut releases);
+		assert_eq!(None, releases[0].moved_from);
+		assert_eq!(Some(String::from("def456")), releases[1].moved_from);
+		assert_eq!(None, releases[2].moved_from);
+
+		state.update(&releases);
+		assert_eq!(
+			vec![
+				ReleaseState {
+					version:   String::from("v1.0.0"),
+					commit_id: String::from("abc123"),
+				},
+				ReleaseState {
+					version:   String::from("v1.1.0"),
+					commit_id: String::from("fff000"),
+				}
+			],
+			state.releases
+		);
+	}
+}
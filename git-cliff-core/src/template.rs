@@ -1,16 +1,48 @@
 use crate::{
-	config::TextProcessor,
+	collation::Collator,
+	config::{
+		CommitGroup,
+		OutputFlavor,
+		TemplateLimits,
+		TextProcessor,
+	},
+	embed::BuiltinTemplates,
 	error::{
 		Error,
 		Result,
 	},
+	history::digest,
+	release::tag_version,
+};
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
 };
+use regex::Regex;
+use semver::VersionReq;
 use serde::Serialize;
+use std::cmp::Ordering;
 use std::collections::{
 	HashMap,
 	HashSet,
 };
 use std::error::Error as ErrorImpl;
+use std::io::{
+	self,
+	Write,
+};
+use std::path::{
+	Path,
+	PathBuf,
+};
+use std::process::{
+	Command,
+	Output,
+};
+use std::time::{
+	Duration,
+	Instant,
+};
 use tera::{
 	ast,
 	Context as TeraContext,
@@ -19,17 +51,108 @@ use tera::{
 	Value,
 };
 
-/// Wrapper for [`Tera`].
+/// Version of the template context.
+///
+/// This is incremented when a variable of the context is renamed or removed
+/// and the change is recorded in [`CHANGED_VARIABLES`]. The value is available
+/// in the templates as `context_version`.
+pub const CONTEXT_VERSION: u32 = 1;
+
+/// Variables of the template context that are renamed or removed.
+///
+/// The changes are appended here along with the increment of
+/// [`CONTEXT_VERSION`], the first version does not have any.
+const CHANGED_VARIABLES: &[ChangedVariable] = &[];
+
+/// Regex for matching the `v` prefixes of the versions (e.g. `^v1.0.0`).
+static VERSION_PREFIX_REGEX: Lazy<Regex> = lazy_regex!(r"\bv(\d)");
+
+/// Variable of the template context that is renamed or removed.
 #[derive(Debug)]
+struct ChangedVariable {
+	/// Name of the variable (e.g. `commit.message`).
+	name:       &'static str,
+	/// New name of the variable if it is renamed.
+	renamed_to: Option<&'static str>,
+	/// Context version that the variable is changed in.
+	version:    u32,
+}
+
+/// Output of rendering that enforces the [`TemplateLimits`] while the
+/// template is being rendered.
+///
+/// Exceeding a limit fails the next write, which makes [`Tera`] stop
+/// rendering.
+struct LimitedOutput {
+	/// Rendered output.
+	buffer:          Vec<u8>,
+	/// Maximum size of the output in bytes.
+	max_output_size: Option<usize>,
+	/// Timeout of rendering along with the time that it is reached.
+	deadline:        Option<(u64, Instant)>,
+	/// Exceeded limit.
+	error:           Option<String>,
+}
+
+impl LimitedOutput {
+	/// Constructs a new instance with the given limits.
+	fn new(limits: &TemplateLimits) -> Self {
+		Self {
+			buffer:          Vec::new(),
+			max_output_size: limits.max_output_size,
+			deadline:        limits.timeout.map(|timeout| {
+				(timeout, Instant::now() + Duration::from_millis(timeout))
+			}),
+			error:           None,
+		}
+	}
+}
+
+impl Write for LimitedOutput {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		if let Some((timeout, deadline)) = self.deadline {
+			if Instant::now() >= deadline {
+				self.error =
+					Some(format!("rendering takes longer than {timeout} ms"));
+			}
+		}
+		if let Some(max_output_size) = self.max_output_size {
+			let size = self.buffer.len() + buf.len();
+			if size > max_output_size {
+				self.error = Some(format!(
+					"the output (at least {size} bytes) exceeds the maximum size \
+					 ({max_output_size} bytes)"
+				));
+			}
+		}
+		if let Some(error) = &self.error {
+			return Err(io::Error::new(io::ErrorKind::Other, error.to_string()));
+		}
+		self.buffer.write(buf)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+/// Wrapper for [`Tera`].
+#[derive(Debug, Clone)]
 pub struct Template {
-	tera:          Tera,
+	tera:              Tera,
 	/// Template variables.
 	#[cfg_attr(not(feature = "github"), allow(dead_code))]
-	pub variables: Vec<String>,
+	pub variables:     Vec<String>,
+	/// Flavor of the output.
+	pub output_flavor: OutputFlavor,
+	/// Limits of rendering.
+	limits:            TemplateLimits,
 }
 
 impl Template {
 	/// Constructs a new instance.
+	///
+	/// See [`TemplateCache`] for parsing the same templates only once.
 	pub fn new(mut template: String, trim: bool) -> Result<Self> {
 		if trim {
 			template = template
@@ -38,8 +161,14 @@ impl Template {
 				.collect::<Vec<&str>>()
 				.join("\n")
 		}
+		template = Self::apply_compatibility_shim(template, CHANGED_VARIABLES);
+		let template_digest = digest(template.as_bytes());
 		let mut tera = Tera::default();
-		if let Err(e) = tera.add_raw_template("template", &template) {
+		let templates = BuiltinTemplates::get_templates()?
+			.iter()
+			.map(|(name, content)| (name.as_str(), content.as_str()))
+			.chain([("template", template.as_str())]);
+		if let Err(e) = tera.add_raw_templates(templates) {
 			return if let Some(error_source) = e.source() {
 				Err(Error::TemplateParseError(error_source.to_string()))
 			} else {
@@ -47,10 +176,375 @@ impl Template {
 			};
 		}
 		tera.register_filter("upper_first", Self::upper_first_filter);
-		Ok(Self {
+		tera.register_filter(
+			"entry_id",
+			move |value: &Value, _: &HashMap<String, Value>| {
+				let id =
+					tera::try_get_value!("entry_id_filter", "value", String, value);
+				Ok(Value::String(Self::entry_id(&id, &template_digest)))
+			},
+		);
+		tera.register_function("semver_gt", |args: &HashMap<String, Value>| {
+			Self::semver_compare("semver_gt", args, Ordering::Greater)
+		});
+		tera.register_function("semver_lt", |args: &HashMap<String, Value>| {
+			Self::semver_compare("semver_lt", args, Ordering::Less)
+		});
+		tera.register_function("semver_satisfies", Self::semver_satisfies);
+		let mut template = Self {
 			variables: Self::get_template_variables(&tera)?,
 			tera,
-		})
+			output_flavor: OutputFlavor::default(),
+			limits: TemplateLimits::default(),
+		};
+		template.set_commit_groups(Vec::new(), None);
+		template.set_output_flavor(OutputFlavor::default());
+		Ok(template)
+	}
+
+	/// Warns about the changed variables that are used in the template and
+	/// replaces the renamed ones with their new names.
+	fn apply_compatibility_shim(
+		mut template: String,
+		changed_variables: &[ChangedVariable],
+	) -> String {
+		for variable in changed_variables {
+			let Ok(regex) = Regex::new(&format!(
+				r"(^|[^\w.]){}\b",
+				regex::escape(variable.name)
+			)) else {
+				continue;
+			};
+			if !regex.is_match(&template) {
+				continue;
+			}
+			if let Some(renamed_to) = variable.renamed_to {
+				warn!(
+					"Template variable `{}` is renamed to `{renamed_to}` in \
+					 context version {}, please update your template.",
+					variable.name, variable.version
+				);
+				template = regex
+					.replace_all(&template, format!("${{1}}{renamed_to}"))
+					.to_string();
+			} else {
+				warn!(
+					"Template variable `{}` is removed in context version {}.",
+					variable.name, variable.version
+				);
+			}
+		}
+		template
+	}
+
+	/// Registers the functions for reading the git objects of the repository:
+	///
+	/// - `git_show`: returns the content of a file at the given revision.
+	/// - `file_exists_at`: returns whether a file exists at the given revision.
+	/// - `first_release_of`: returns the first tag (that matches the tag
+	///   pattern) which contains the given commit.
+	///
+	/// The objects are read via `git cat-file` and `git tag` and nothing else
+	/// is executed.
+	pub fn allow_git_read(
+		&mut self,
+		repository: PathBuf,
+		tag_pattern: Option<Regex>,
+	) {
+		let path = repository.clone();
+		self.tera.register_function(
+			"git_show",
+			move |args: &HashMap<String, Value>| -> TeraResult<Value> {
+				let object = format!(
+					"{}:{}",
+					Self::git_arg("git_show", args, "rev")?,
+					Self::git_arg("git_show", args, "path")?
+				);
+				let output =
+					Self::git("git_show", &path, &["cat-file", "blob", &object])?;
+				if !output.status.success() {
+					return Err(tera::Error::msg(format!(
+						"git_show: cannot read {object}: {}",
+						String::from_utf8_lossy(&output.stderr).trim()
+					)));
+				}
+				Ok(Value::String(
+					String::from_utf8_lossy(&output.stdout).to_string(),
+				))
+			},
+		);
+		let path = repository.clone();
+		self.tera.register_function(
+			"file_exists_at",
+			move |args: &HashMap<String, Value>| -> TeraResult<Value> {
+				let object = format!(
+					"{}:{}",
+					Self::git_arg("file_exists_at", args, "rev")?,
+					Self::git_arg("file_exists_at", args, "path")?
+				);
+				let output = Self::git("file_exists_at", &path, &[
+					"cat-file", "-e", &object,
+				])?;
+				Ok(Value::Bool(output.status.success()))
+			},
+		);
+		self.tera.register_function(
+			"first_release_of",
+			move |args: &HashMap<String, Value>| -> TeraResult<Value> {
+				let sha = Self::git_arg("first_release_of", args, "sha")?;
+				let output = Self::git("first_release_of", &repository, &[
+					"tag",
+					"--sort=creatordate",
+					"--contains",
+					&sha,
+				])?;
+				if !output.status.success() {
+					return Err(tera::Error::msg(format!(
+						"first_release_of: cannot find the tags of {sha}: {}",
+						String::from_utf8_lossy(&output.stderr).trim()
+					)));
+				}
+				Ok(String::from_utf8_lossy(&output.stdout)
+					.lines()
+					.find(|tag| {
+						tag_pattern.as_ref().map_or(true, |v| v.is_match(tag))
+					})
+					.map_or(Value::Null, |tag| Value::String(tag.to_string())))
+			},
+		);
+	}
+
+	/// Returns the argument of a git function.
+	///
+	/// The arguments that look like options are rejected.
+	fn git_arg(
+		function: &str,
+		args: &HashMap<String, Value>,
+		name: &str,
+	) -> TeraResult<String> {
+		match args.get(name).and_then(|v| v.as_str()) {
+			Some(v) if !v.is_empty() && !v.starts_with('-') => Ok(v.to_string()),
+			_ => Err(tera::Error::msg(format!(
+				"{function}: invalid or missing `{name}` argument"
+			))),
+		}
+	}
+
+	/// Runs `git` with the given arguments in the repository.
+	fn git(function: &str, repository: &Path, args: &[&str]) -> TeraResult<Output> {
+		Command::new("git")
+			.arg("-C")
+			.arg(repository)
+			.args(args)
+			.output()
+			.map_err(|e| tera::Error::msg(format!("{function}: {e}")))
+	}
+
+	/// Registers the `commit_groups` filter with the metadata of the configured
+	/// commit groups.
+	///
+	/// The configured groups come first in their order and the other groups
+	/// are sorted by name (with the given collator if it is set).
+	pub fn set_commit_groups(
+		&mut self,
+		groups: Vec<CommitGroup>,
+		collator: Option<Collator>,
+	) {
+		self.tera.register_filter(
+			"commit_groups",
+			move |value: &Value, _: &HashMap<String, Value>| {
+				Self::commit_groups_filter(value, &groups, collator.as_ref())
+			},
+		);
+	}
+
+	/// Filter for grouping the commits along with the metadata of the groups.
+	fn commit_groups_filter(
+		value: &Value,
+		groups: &[CommitGroup],
+		collator: Option<&Collator>,
+	) -> TeraResult<Value> {
+		let commits =
+			tera::try_get_value!("commit_groups_filter", "value", Vec<Value>, value);
+		let mut commit_groups: Vec<(&str, Vec<&Value>)> = Vec::new();
+		for commit in &commits {
+			let Some(group) = commit.get("group").and_then(|v| v.as_str()) else {
+				continue;
+			};
+			match commit_groups.iter_mut().find(|(name, _)| *name == group) {
+				Some((_, commits)) => commits.push(commit),
+				None => commit_groups.push((group, vec![commit])),
+			}
+		}
+		let position = |name: &str| {
+			groups
+				.iter()
+				.position(|v| v.name == name)
+				.unwrap_or(groups.len())
+		};
+		commit_groups.sort_by(|a, b| {
+			position(a.0)
+				.cmp(&position(b.0))
+				.then_with(|| match collator {
+					Some(collator) => collator.compare(a.0, b.0),
+					None => a.0.cmp(b.0),
+				})
+		});
+		Ok(Value::Array(
+			commit_groups
+				.into_iter()
+				.map(|(name, group_commits)| {
+					let group = groups.iter().find(|v| v.name == name);
+					serde_json::json!({
+						"name": name,
+						"title": group.and_then(|v| v.title.as_deref()).unwrap_or(name),
+						"icon": group.and_then(|v| v.icon.as_deref()),
+						"collapsed": group.and_then(|v| v.collapse).unwrap_or(false),
+						"count": group_commits.len(),
+						"proportion": group_commits.len() as f64 / commits.len() as f64,
+						"commits": group_commits,
+					})
+				})
+				.collect(),
+		))
+	}
+
+	/// Sets the flavor of the output.
+	///
+	/// The flavor is available in the templates as `output_flavor` and the
+	/// `escape_output` and `details` filters adapt to it.
+	pub fn set_output_flavor(&mut self, flavor: OutputFlavor) {
+		self.output_flavor = flavor;
+		self.tera.register_filter(
+			"escape_output",
+			move |value: &Value, _: &HashMap<String, Value>| {
+				let s = tera::try_get_value!(
+					"escape_output_filter",
+					"value",
+					String,
+					value
+				);
+				Ok(tera::to_value(Self::escape_output(&s, flavor))?)
+			},
+		);
+		self.tera.register_filter(
+			"details",
+			move |value: &Value, args: &HashMap<String, Value>| {
+				let body =
+					tera::try_get_value!("details_filter", "value", String, value);
+				let summary = match args.get("summary") {
+					Some(summary) => tera::try_get_value!(
+						"details_filter",
+						"summary",
+						String,
+						summary
+					),
+					None => {
+						return Err(tera::Error::msg(
+							"the `details` filter requires a `summary` argument",
+						));
+					}
+				};
+				Ok(tera::to_value(if flavor.allows_html() {
+					format!(
+						"<details>\n<summary>{summary}</summary>\n\n{body}\n\n</\
+						 details>"
+					)
+				} else {
+					format!("**{summary}**\n\n{body}")
+				})?)
+			},
+		);
+	}
+
+	/// Escapes the characters of the text that have a special meaning in the
+	/// given flavor.
+	fn escape_output(text: &str, flavor: OutputFlavor) -> String {
+		let mut escaped = String::with_capacity(text.len());
+		for c in text.chars() {
+			match (c, flavor) {
+				('<', _) => escaped.push_str("&lt;"),
+				('>', _) => escaped.push_str("&gt;"),
+				('{' | '}', OutputFlavor::Mdx) => {
+					escaped.push('\\');
+					escaped.push(c);
+				}
+				('&', OutputFlavor::Html) => escaped.push_str("&amp;"),
+				('"', OutputFlavor::Html) => escaped.push_str("&quot;"),
+				('\'', OutputFlavor::Html) => escaped.push_str("&#39;"),
+				_ => escaped.push(c),
+			}
+		}
+		escaped
+	}
+
+	/// Returns the stable identifier of the entry of the given commit.
+	///
+	/// The identifier is derived from the SHA1 of the commit and the digest of
+	/// the template, so it only changes if the template changes.
+	fn entry_id(commit_id: &str, template_digest: &str) -> String {
+		let mut id = digest(format!("{commit_id}:{template_digest}").as_bytes());
+		id.truncate(12);
+		id
+	}
+
+	/// Returns the version argument of a semver function.
+	///
+	/// The prefix of the version is ignored (e.g. `v1.0.0`) and `None` is
+	/// returned if the argument is null (e.g. `previous.version` of the first
+	/// release).
+	fn semver_arg(
+		function: &str,
+		args: &HashMap<String, Value>,
+		name: &str,
+	) -> TeraResult<Option<semver::Version>> {
+		match args.get(name) {
+			Some(Value::Null) => Ok(None),
+			Some(Value::String(version)) => {
+				tag_version(version).map(Some).ok_or_else(|| {
+					tera::Error::msg(format!(
+						"{function}: `{version}` is not a semantic version"
+					))
+				})
+			}
+			_ => Err(tera::Error::msg(format!(
+				"{function}: invalid or missing `{name}` argument"
+			))),
+		}
+	}
+
+	/// Function for comparing the versions `a` and `b`.
+	///
+	/// Returns `true` if the comparison of `a` to `b` is the given ordering
+	/// and `false` if one of them is null.
+	fn semver_compare(
+		function: &str,
+		args: &HashMap<String, Value>,
+		ordering: Ordering,
+	) -> TeraResult<Value> {
+		let a = Self::semver_arg(function, args, "a")?;
+		let b = Self::semver_arg(function, args, "b")?;
+		Ok(Value::Bool(match (a, b) {
+			(Some(a), Some(b)) => a.cmp(&b) == ordering,
+			_ => false,
+		}))
+	}
+
+	/// Function for checking if the `version` satisfies the requirement `req`
+	/// (e.g. `>=1.0.0, <2.0.0`).
+	///
+	/// The `v` prefixes of the versions in the requirement are ignored.
+	/// Returns `false` if the version is null.
+	fn semver_satisfies(args: &HashMap<String, Value>) -> TeraResult<Value> {
+		let version = Self::semver_arg("semver_satisfies", args, "version")?;
+		let req = args.get("req").and_then(|v| v.as_str()).ok_or_else(|| {
+			tera::Error::msg("semver_satisfies: invalid or missing `req` argument")
+		})?;
+		let req = VERSION_PREFIX_REGEX.replace_all(req, "$1");
+		let req = VersionReq::parse(&req).map_err(|e| {
+			tera::Error::msg(format!("semver_satisfies: `{req}` is invalid: {e}"))
+		})?;
+		Ok(Value::Bool(version.is_some_and(|v| req.matches(&v))))
 	}
 
 	/// Filter for making the first character of a string uppercase.
@@ -76,11 +570,15 @@ impl Template {
 					Self::find_identifiers(node, names);
 				}
 			}
-			ast::Node::VariableBlock(_, expr) => {
-				if let ast::ExprVal::Ident(v) = &expr.val {
+			ast::Node::VariableBlock(_, expr) => match &expr.val {
+				ast::ExprVal::Ident(v) => {
 					names.insert(v.clone());
 				}
-			}
+				ast::ExprVal::MacroCall(call) => {
+					names.insert(format!("{}::{}", call.namespace, call.name));
+				}
+				_ => {}
+			},
 			ast::Node::MacroDefinition(_, def, _) => {
 				for node in &def.body {
 					Self::find_identifiers(node, names);
@@ -122,24 +620,209 @@ impl Template {
 	}
 
 	/// Returns the variable names that are used in the template.
+	///
+	/// The variables that are used in the called macros of the imported
+	/// templates (e.g. `cliff_macros`) are included as well.
 	fn get_template_variables(tera: &Tera) -> Result<Vec<String>> {
 		let mut variables = HashSet::new();
-		let ast = &tera.get_template("template")?.ast;
-		for node in ast {
+		let template = tera.get_template("template")?;
+		for node in &template.ast {
 			Self::find_identifiers(node, &mut variables);
 		}
-		Ok(variables.into_iter().collect())
+		for (name, namespace) in &template.imported_macro_files {
+			let macros = &tera.get_template(name)?.macros;
+			for call in variables.clone() {
+				let Some(macro_def) = call
+					.strip_prefix(&format!("{namespace}::"))
+					.and_then(|v| macros.get(v))
+				else {
+					continue;
+				};
+				for node in &macro_def.body {
+					Self::find_identifiers(node, &mut variables);
+				}
+			}
+		}
+		Ok(variables
+			.into_iter()
+			.filter(|v| !v.contains("::"))
+			.collect())
+	}
+
+	/// Sets the limits of rendering the template.
+	///
+	/// The depth of the template is checked here rather than while rendering.
+	pub fn set_limits(&mut self, limits: TemplateLimits) -> Result<()> {
+		if let Some(max_depth) = limits.max_depth {
+			let template = self.tera.get_template("template")?;
+			let depth = Self::get_depth(
+				&self.tera,
+				"template",
+				&template.ast,
+				&mut Vec::new(),
+			)?;
+			if depth > max_depth {
+				return Err(Error::TemplateLimitError(format!(
+					"the depth of the template ({depth}) exceeds the maximum depth \
+					 ({max_depth})"
+				)));
+			}
+		}
+		self.limits = limits;
+		Ok(())
+	}
+
+	/// Returns the maximum depth of the nested blocks, macro calls and
+	/// includes in the given nodes.
+	///
+	/// The templates and macros that are being visited are kept in the stack
+	/// for detecting the recursion.
+	fn get_depth(
+		tera: &Tera,
+		template: &str,
+		nodes: &[ast::Node],
+		stack: &mut Vec<String>,
+	) -> Result<usize> {
+		let mut depth = 0;
+		for node in nodes {
+			let node_depth = match node {
+				ast::Node::Block(_, block, _) => {
+					1 + Self::get_depth(tera, template, &block.body, stack)?
+				}
+				ast::Node::FilterSection(_, section, _) => {
+					1 + Self::get_depth(tera, template, &section.body, stack)?
+				}
+				ast::Node::Forloop(_, forloop, _) => {
+					1 + Self::get_depth(tera, template, &forloop.body, stack)?.max(
+						Self::get_depth(
+							tera,
+							template,
+							forloop.empty_body.as_deref().unwrap_or_default(),
+							stack,
+						)?,
+					)
+				}
+				ast::Node::If(cond, _) => {
+					let mut if_depth = 0;
+					for (_, _, nodes) in &cond.conditions {
+						if_depth = if_depth
+							.max(Self::get_depth(tera, template, nodes, stack)?);
+					}
+					if let Some((_, nodes)) = &cond.otherwise {
+						if_depth = if_depth
+							.max(Self::get_depth(tera, template, nodes, stack)?);
+					}
+					1 + if_depth
+				}
+				ast::Node::VariableBlock(
+					_,
+					ast::Expr {
+						val: ast::ExprVal::MacroCall(call),
+						..
+					},
+				) |
+				ast::Node::Set(
+					_,
+					ast::Set {
+						value:
+							ast::Expr {
+								val: ast::ExprVal::MacroCall(call),
+								..
+							},
+						..
+					},
+				) => {
+					let file = if call.namespace == "self" {
+						Some(template.to_string())
+					} else {
+						tera.get_template(template)?
+							.imported_macro_files
+							.iter()
+							.find(|(_, namespace)| *namespace == call.namespace)
+							.map(|(file, _)| file.to_string())
+					};
+					let Some((file, macro_def)) = file.and_then(|file| {
+						let macro_def =
+							tera.get_template(&file).ok()?.macros.get(&call.name)?;
+						Some((file, macro_def))
+					}) else {
+						continue;
+					};
+					let key = format!("{file}::{}", call.name);
+					if stack.contains(&key) {
+						return Err(Error::TemplateLimitError(format!(
+							"the depth of the recursive macro `{}::{}` cannot be \
+							 limited",
+							call.namespace, call.name
+						)));
+					}
+					stack.push(key);
+					let macro_depth =
+						Self::get_depth(tera, &file, &macro_def.body, stack)?;
+					stack.pop();
+					1 + macro_depth
+				}
+				ast::Node::Include(_, files, _) => {
+					let mut include_depth = 0;
+					for file in files {
+						let Ok(included) = tera.get_template(file) else {
+							continue;
+						};
+						if stack.contains(file) {
+							return Err(Error::TemplateLimitError(format!(
+								"the depth of the recursive include `{file}` \
+								 cannot be limited"
+							)));
+						}
+						stack.push(file.to_string());
+						include_depth = include_depth.max(Self::get_depth(
+							tera,
+							file,
+							&included.ast,
+							stack,
+						)?);
+						stack.pop();
+					}
+					1 + include_depth
+				}
+				_ => 0,
+			};
+			depth = depth.max(node_depth);
+		}
+		Ok(depth)
 	}
 
 	/// Returns `true` if the template contains GitHub related variables.
 	///
-	/// Note that this checks the variables starting with "github" and
-	/// "commit.github" and ignores "remote.github" values.
+	/// Note that this checks the variables starting with "github",
+	/// "commit.github" and the repository values that are only known by the
+	/// forge (e.g. "repository.description") and ignores "remote.github"
+	/// values.
 	#[cfg(feature = "github")]
 	pub(crate) fn contains_github_variable(&self) -> bool {
-		self.variables
-			.iter()
-			.any(|v| v.starts_with("github") || v.starts_with("commit.github"))
+		self.variables.iter().any(|v| {
+			v.starts_with("github") ||
+				v.starts_with("commit.github") ||
+				v.starts_with("repository.description") ||
+				v.starts_with("repository.topics")
+		})
+	}
+
+	/// Returns `true` if the template contains any of the given variables.
+	///
+	/// The fields of the variables (e.g. "github.milestone.title" for
+	/// "github.milestone") and the objects that contain them (e.g. "github")
+	/// are matched as well.
+	#[cfg(feature = "github")]
+	pub(crate) fn contains_variable(&self, variables: &[&str]) -> bool {
+		self.variables.iter().any(|v| {
+			variables.iter().any(|name| {
+				v == name ||
+					v.strip_prefix(name).is_some_and(|v| v.starts_with('.')) ||
+					name.strip_prefix(v.as_str())
+						.is_some_and(|v| v.starts_with('.'))
+			})
+		})
 	}
 
 	/// Renders the template.
@@ -150,12 +833,19 @@ impl Template {
 		postprocessors: &[TextProcessor],
 	) -> Result<String> {
 		let mut context = TeraContext::from_serialize(context)?;
+		context.insert("context_version", &CONTEXT_VERSION);
+		context.insert("output_flavor", &self.output_flavor);
 		if let Some(additional_context) = additional_context {
 			for (key, value) in additional_context {
 				context.insert(*key, &value);
 			}
 		}
-		match self.tera.render("template", &context) {
+		let mut output = LimitedOutput::new(&self.limits);
+		let result = self.tera.render_to("template", &context, &mut output);
+		if let Some(error) = output.error {
+			return Err(Error::TemplateLimitError(error));
+		}
+		match result.map(|_| String::from_utf8_lossy(&output.buffer).into_owned()) {
 			Ok(mut v) => {
 				for postprocessor in postprocessors {
 					postprocessor.replace(&mut v, vec![])?;
@@ -173,6 +863,34 @@ impl Template {
 	}
 }
 
+/// Cache of the parsed templates.
+///
+/// The templates are cached by their content and whether if they are
+/// trimmed. The cache is owned by the caller, e.g. it can be kept between
+/// the renders of a long-running process.
+#[derive(Debug, Default)]
+pub struct TemplateCache {
+	/// Parsed templates.
+	templates: HashMap<(String, bool), Template>,
+}
+
+impl TemplateCache {
+	/// Returns the parsed template and parses it only if it is not cached.
+	///
+	/// The returned template is a copy, so changing it (e.g. via
+	/// [`Template::allow_git_read`]) does not change the cached one.
+	pub fn get(&mut self, template: String, trim: bool) -> Result<Template> {
+		let key = (template, trim);
+		if let Some(template) = self.templates.get(&key) {
+			trace!("Using the cached template");
+			return Ok(template.clone());
+		}
+		let template = Template::new(key.0.clone(), trim)?;
+		self.templates.insert(key, template.clone());
+		Ok(template)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -220,9 +938,14 @@ mod test {
 					commit_id: None,
 					timestamp: 0,
 					previous: None,
+					moved_from: None,
+					authors: None,
+					assets: Vec::new(),
+					stats: Default::default(),
 					#[cfg(feature = "github")]
 					github: crate::github::GitHubReleaseMetadata {
 						contributors: vec![],
+						milestone:    None,
 					},
 				},
 				Option::<HashMap<&str, String>>::None.as_ref(),
@@ -246,6 +969,470 @@ mod test {
 		);
 		#[cfg(feature = "github")]
 		assert!(!template.contains_github_variable());
+		#[cfg(feature = "github")]
+		assert!(Template::new(
+			String::from("{{ repository.name }}: {{ repository.description }}"),
+			false
+		)?
+		.contains_github_variable());
+		#[cfg(feature = "github")]
+		for (template, expected) in [
+			("{{ github.milestone.title }}", true),
+			("{{ github | json_encode }}", true),
+			("{{ github.milestone }}", true),
+			("{{ github.contributors | length }}", false),
+			("{{ github.milestones }}", false),
+		] {
+			assert_eq!(
+				expected,
+				Template::new(String::from(template), false)?
+					.contains_variable(&["github.milestone"]),
+				"{template}"
+			);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn compatibility_shim() -> Result<()> {
+		let changed_variables = [
+			ChangedVariable {
+				name:       "commit.old",
+				renamed_to: Some("commit.new"),
+				version:    2,
+			},
+			ChangedVariable {
+				name:       "removed",
+				renamed_to: None,
+				version:    2,
+			},
+		];
+		assert_eq!(
+			"{{ commit.new }} {{ commit.older }} {{ x.commit.old }} {{ removed }}",
+			Template::apply_compatibility_shim(
+				String::from(
+					"{{ commit.old }} {{ commit.older }} {{ x.commit.old }} {{ \
+					 removed }}"
+				),
+				&changed_variables
+			)
+		);
+		assert_eq!(
+			CONTEXT_VERSION.to_string(),
+			Template::new(String::from("{{ context_version }}"), false)?.render(
+				&Release::default(),
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn entry_id() -> Result<()> {
+		let render = |template: &str| -> Result<String> {
+			Template::new(template.to_string(), false)?.render(
+				&Release {
+					commits: vec![
+						Commit::new(
+							String::from("123"),
+							String::from("feat: add x"),
+						),
+						Commit::new(String::from("456"), String::from("fix: fix y")),
+					],
+					..Default::default()
+				},
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)
+		};
+		let template = "{% for commit in commits %}<!-- {{ commit.id | entry_id }} \
+		                -->{% endfor %}";
+		let output = render(template)?;
+		assert_eq!(output, render(template)?);
+		assert_eq!(
+			format!(
+				"<!-- {} --><!-- {} -->",
+				Template::entry_id("123", &digest(template.as_bytes())),
+				Template::entry_id("456", &digest(template.as_bytes()))
+			),
+			output
+		);
+		assert_eq!(12, Template::entry_id("123", "").len());
+		assert_ne!(output, render(&format!("{template}\n"))?.trim_end());
+		Ok(())
+	}
+
+	#[test]
+	fn template_limits() -> Result<()> {
+		let render = |template: &str, limits: TemplateLimits| -> Result<String> {
+			let mut template = Template::new(template.to_string(), false)?;
+			template.set_limits(limits)?;
+			template.render(
+				&Release::default(),
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)
+		};
+		let template = "{% macro m(x) %}{% if x %}{{ x }}{% endif %}{% endmacro m \
+		                %}{% for i in [1] %}{{ self::m(x=i) }}{% endfor %}";
+		let limits = |max_depth| TemplateLimits {
+			max_depth: Some(max_depth),
+			..Default::default()
+		};
+		assert_eq!("1", render(template, limits(3))?);
+		assert!(matches!(
+			render(template, limits(2)),
+			Err(Error::TemplateLimitError(_))
+		));
+		assert!(render(
+			"{% macro m(x) %}{{ self::m(x=x) }}{% endmacro m %}{{ self::m(x=1) }}",
+			limits(100)
+		)
+		.is_err());
+
+		let limits = TemplateLimits {
+			max_output_size: Some(3),
+			..Default::default()
+		};
+		assert_eq!("abc", render("abc", limits)?);
+		assert!(render("abcd", limits).is_err());
+		assert!(matches!(
+			render(
+				"{% for i in range(end=1000000) %}{{ i }}{% endfor %}",
+				limits
+			),
+			Err(Error::TemplateLimitError(e)) if e.contains("at least 4 bytes")
+		));
+
+		let limits = |timeout| TemplateLimits {
+			timeout: Some(timeout),
+			..Default::default()
+		};
+		assert_eq!("abc", render("abc", limits(60_000))?);
+		assert!(matches!(
+			render("abc", limits(0)),
+			Err(Error::TemplateLimitError(_))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn semver_functions() -> Result<()> {
+		let render = |template: &str| -> Result<String> {
+			Template::new(template.to_string(), false)?.render(
+				&Release {
+					version: Some(String::from("v2.0.0")),
+					previous: Some(Box::new(Release {
+						version: Some(String::from("v1.4.0")),
+						..Default::default()
+					})),
+					..Default::default()
+				},
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)
+		};
+		assert_eq!(
+			"true false false",
+			render(
+				"{{ semver_gt(a=version, b=previous.version) }} {{ \
+				 semver_lt(a=version, b=previous.version) }} {{ \
+				 semver_gt(a=version, b=\"2.0.0\") }}"
+			)?
+		);
+		assert_eq!(
+			"upgrade guide",
+			render(
+				"{% if not semver_satisfies(version=version, req=\"^\" ~ \
+				 previous.version) %}upgrade guide{% endif %}"
+			)?
+		);
+		assert_eq!(
+			"false false",
+			render(
+				"{{ semver_satisfies(version=version, req=\"0.*\") }} {{ \
+				 semver_gt(a=version, b=previous.commit_id) }}"
+			)?
+		);
+		assert!(render("{{ semver_gt(a=version, b=\"latest\") }}").is_err());
+		assert!(
+			render("{{ semver_satisfies(version=version, req=\"~>\") }}").is_err()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn output_flavor() -> Result<()> {
+		let render = |flavor: Option<OutputFlavor>| -> Result<String> {
+			let mut template = Template::new(
+				String::from(
+					"{{ output_flavor }}: {{ \"<b>{x}</b> & 'y'\" | escape_output \
+					 }}\n{{ \"- a\" | details(summary=\"More\") }}",
+				),
+				false,
+			)?;
+			if let Some(flavor) = flavor {
+				template.set_output_flavor(flavor);
+			}
+			template.render(
+				&Release::default(),
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)
+		};
+		assert_eq!(
+			"markdown: &lt;b&gt;{x}&lt;/b&gt; & \
+			 'y'\n<details>\n<summary>More</summary>\n\n- a\n\n</details>",
+			render(None)?
+		);
+		assert_eq!(
+			"mdx: &lt;b&gt;\\{x\\}&lt;/b&gt; & 'y'\n**More**\n\n- a",
+			render(Some(OutputFlavor::Mdx))?
+		);
+		assert_eq!(
+			"html: &lt;b&gt;{x}&lt;/b&gt; &amp; \
+			 &#39;y&#39;\n<details>\n<summary>More</summary>\n\n- a\n\n</details>",
+			render(Some(OutputFlavor::Html))?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn builtin_macros() -> Result<()> {
+		let template = r#"{% import "cliff_macros" as m -%}
+		{{ m::breaking_changes(commits=commits) }}
+		{%- for commit in commits %}
+		{{ m::entry(commit=commit, remote_url="https://x.org") }}
+		{%- endfor %}
+		{{ m::contributors(contributors=contributors, remote_url="https://x.org") }}
+		{{- m::compare_link(release=release, remote_url="https://x.org") }}"#;
+		let release = Release {
+			version: Some(String::from("v1.1.0")),
+			commits: vec![
+				Commit::new(
+					String::from("1231231231"),
+					String::from(
+						"feat(xyz)!: add xyz\n\nBREAKING CHANGE: remove abc",
+					),
+				),
+				Commit::new(
+					String::from("1241241241"),
+					String::from("fix: fix abc"),
+				),
+			]
+			.into_iter()
+			.filter_map(|c| c.into_conventional().ok())
+			.collect(),
+			previous: Some(Box::new(Release {
+				version: Some(String::from("v1.0.0")),
+				..Default::default()
+			})),
+			..Default::default()
+		};
+		let mut additional_context = HashMap::new();
+		additional_context.insert("release", serde_json::to_value(&release)?);
+		additional_context.insert(
+			"contributors",
+			serde_json::json!([
+				{ "username": "abc", "pr_number": 1, "is_first_time": true },
+				{ "username": "xyz", "pr_number": 2, "is_first_time": false },
+			]),
+		);
+		let template = Template::new(template.to_string(), true)?;
+		assert_eq!(
+			"### Breaking Changes\n- *(xyz)* Remove abc\n\n- *(xyz)* [**breaking**] \
+			 Add xyz ([1231231](https://x.org/commit/1231231231))\n- Fix abc \
+			 ([1241241](https://x.org/commit/1241241241))\n* @abc made their first \
+			 contribution in [#1](https://x.org/pull/1)\n* @xyz\n[1.1.0]: \
+			 https://x.org/compare/v1.0.0..v1.1.0\n",
+			template.render(&release, Some(&additional_context), &[])?
+		);
+		#[cfg(feature = "github")]
+		assert!(template.contains_github_variable());
+		#[cfg(feature = "github")]
+		assert!(!Template::new(
+			String::from(
+				r#"{% import "cliff_macros" as m %}{{ m::pr_link(number=1) }}"#
+			),
+			false
+		)?
+		.contains_github_variable());
+		#[cfg(feature = "github")]
+		{
+			additional_context.insert(
+				"commit",
+				serde_json::json!({
+					"message": "fix: fix abc",
+					"github": { "pr_release_note": "fix the parser", "pr_number": 3 },
+				}),
+			);
+			assert_eq!(
+				"- Fix the parser in #3",
+				Template::new(
+					String::from(
+						r#"{% import "cliff_macros" as m %}{{ m::entry(commit=commit) }}"#
+					),
+					false
+				)?
+				.render(&release, Some(&additional_context), &[])?
+			);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn commit_groups() -> Result<()> {
+		let template = r#"
+		{%- for group in commits | commit_groups -%}
+		{{ group.icon }} {{ group.title }} ({{ group.count }}, {{ group.proportion }}):
+		{%- if group.collapsed %} [collapsed]{% endif %}
+		{%- for commit in group.commits %} {{ commit.message }}{% endfor %}
+		{% endfor -%}"#;
+		let release = Release {
+			commits: vec![
+				("feat: add abc", "Features"),
+				("fix: fix abc", "Bug Fixes"),
+				("feat: add xyz", "Features"),
+				("ci: update xyz", "CI"),
+			]
+			.into_iter()
+			.map(|(message, group)| Commit {
+				group: Some(String::from(group)),
+				..Commit::new(String::from("123123"), String::from(message))
+			})
+			.collect(),
+			..Default::default()
+		};
+		let mut template = Template::new(template.to_string(), true)?;
+		template.set_commit_groups(
+			vec![
+				CommitGroup {
+					name:      String::from("Features"),
+					title:     Some(String::from("New Features")),
+					icon:      Some(String::from("*")),
+					collapse:  None,
+					normalize: None,
+				},
+				CommitGroup {
+					name:      String::from("Bug Fixes"),
+					title:     None,
+					icon:      Some(String::from("!")),
+					collapse:  Some(true),
+					normalize: None,
+				},
+			],
+			None,
+		);
+		assert_eq!(
+			"* New Features (2, 0.5): feat: add abc feat: add xyz\n! Bug Fixes (1, \
+			 0.25): [collapsed] fix: fix abc\n CI (1, 0.25): ci: update xyz\n",
+			template.render(
+				&release,
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[]
+			)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn git_show() -> Result<()> {
+		let render = |template: &str, allow: bool| -> Result<String> {
+			let mut template = Template::new(template.to_string(), false)?;
+			if allow {
+				template
+					.allow_git_read(PathBuf::from(env!("CARGO_MANIFEST_DIR")), None);
+			}
+			template.render(
+				&Release::default(),
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)
+		};
+		let template =
+			r#"{{ git_show(path="git-cliff-core/Cargo.toml", rev="HEAD") }}"#;
+		assert!(render(template, false).is_err());
+		assert!(render(template, true)?.contains("name = \"git-cliff-core\""));
+		assert!(
+			render(r#"{{ git_show(path="Cargo.toml", rev="--help") }}"#, true)
+				.is_err()
+		);
+		assert_eq!(
+			"true false",
+			render(
+				r#"{{ file_exists_at(path="git-cliff-core/Cargo.toml", rev="HEAD") }} {{ file_exists_at(path="x.toml", rev="HEAD") }}"#,
+				true
+			)?
+		);
+		assert!(
+			render(r#"{{ first_release_of(sha="--points-at") }}"#, true).is_err()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn cached_template() -> Result<()> {
+		let template = r#"{{ git_show(path="Cargo.toml", rev="HEAD") }}"#;
+		let mut cache = TemplateCache::default();
+		let mut allowed = cache.get(template.to_string(), false)?;
+		allowed.allow_git_read(PathBuf::from(env!("CARGO_MANIFEST_DIR")), None);
+		let cached = cache.get(template.to_string(), false)?;
+		assert_eq!(1, cache.templates.len());
+		assert_eq!(allowed.variables, cached.variables);
+		assert!(cached
+			.render(
+				&Release::default(),
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)
+			.is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn first_release_of() -> Result<()> {
+		let dir = tempfile::tempdir()?;
+		let git = |args: &[&str]| -> Result<String> {
+			let output = Command::new("git")
+				.arg("-C")
+				.arg(dir.path())
+				.args(["-c", "user.name=a", "-c", "user.email=a@b"])
+				.args(args)
+				.output()?;
+			if !output.status.success() {
+				return Err(Error::RepoError(format!(
+					"git {} failed: {}",
+					args.join(" "),
+					String::from_utf8_lossy(&output.stderr).trim()
+				)));
+			}
+			Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+		};
+		git(&["init", "-q"])?;
+		git(&["commit", "-q", "--allow-empty", "-m", "feat: add abc"])?;
+		let sha = git(&["rev-parse", "HEAD"])?;
+		git(&["tag", "nightly"])?;
+		git(&["commit", "-q", "--allow-empty", "-m", "fix: fix abc"])?;
+		git(&["tag", "v1.3.0"])?;
+		git(&["commit", "-q", "--allow-empty", "-m", "fix: fix xyz"])?;
+		let unreleased = git(&["rev-parse", "HEAD"])?;
+		let mut template = Template::new(
+			format!(
+				r#"{{{{ first_release_of(sha="{sha}") }}}} [{{{{ first_release_of(sha="{unreleased}") }}}}]"#
+			),
+			false,
+		)?;
+		template
+			.allow_git_read(dir.path().to_path_buf(), Regex::new("^v[0-9]").ok());
+		assert_eq!(
+			"v1.3.0 []",
+			template.render(
+				&Release::default(),
+				Option::<HashMap<&str, String>>::None.as_ref(),
+				&[],
+			)?
+		);
 		Ok(())
 	}
 }
@@ -0,0 +1,238 @@
+use crate::error::Result;
+use crate::template::Template;
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::collections::HashMap;
+#[cfg(feature = "repo")]
+use {
+	crate::commit::Commit,
+	crate::config::{
+		GitConfig,
+		WorkspaceConfig,
+	},
+	crate::error::Error,
+	crate::repo::Repository,
+	glob::Pattern,
+};
+
+/// Default template of the umbrella changelog.
+pub const DEFAULT_TEMPLATE: &str = r#"## {{ timestamp | date(format="%Y-%m-%d") }}
+
+| Package | Version |
+| ------- | ------- |
+{% for package in packages -%}
+| {% if package.changelog %}[{{ package.name }}]({{ package.changelog }}){% else %}{{ package.name }}{% endif %} | {% if package.previous_version %}{{ package.previous_version }} → {% endif %}{{ package.version }} |
+{% endfor %}
+{%- for package in packages %}{% if package.highlights %}
+### {{ package.name }}
+{% for highlight in package.highlights %}
+- {% if highlight.breaking %}**BREAKING** {% endif %}{{ highlight.message | upper_first }}
+{%- endfor %}
+{% endif %}{% endfor %}"#;
+
+/// Notable change of a package release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Highlight {
+	/// Description of the change.
+	pub message:  String,
+	/// Whether if the change is breaking.
+	pub breaking: bool,
+}
+
+/// Release of a package.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageRelease {
+	/// Name of the package.
+	pub name:             String,
+	/// Previous version of the package.
+	pub previous_version: Option<String>,
+	/// Released version of the package.
+	pub version:          String,
+	/// Path or URL of the changelog of the package.
+	pub changelog:        Option<String>,
+	/// Breaking changes and features of the release.
+	pub highlights:       Vec<Highlight>,
+}
+
+/// Summary of the packages that are released together.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Umbrella {
+	/// SHA1 of the commit that the packages are released at.
+	pub commit_id: String,
+	/// Time of the release (Unix timestamp).
+	pub timestamp: i64,
+	/// Releases of the packages.
+	pub packages:  Vec<PackageRelease>,
+}
+
+impl Umbrella {
+	/// Collects the latest release of the workspace from the repository.
+	///
+	/// The packages that are released together are the ones whose latest tags
+	/// point to the same commit as the most recent package release. The
+	/// highlights are selected with the configured commit types (see
+	/// [`Commit::is_highlight`]).
+	#[cfg(feature = "repo")]
+	pub fn from_repository(
+		repository: &Repository,
+		config: &WorkspaceConfig,
+		git_config: &GitConfig,
+	) -> Result<Self> {
+		let mut latest_tags = Vec::new();
+		for package in &config.packages {
+			let tags = repository.tags(&Some(package.tag_pattern()?), false)?;
+			let Some((commit_id, version)) = tags.last() else {
+				debug!("Package is not released: {}", package.name);
+				continue;
+			};
+			let previous_version = tags
+				.len()
+				.checked_sub(2)
+				.and_then(|i| tags.get_index(i))
+				.map(|(_, v)| v.to_string());
+			let timestamp = repository
+				.find_commit(commit_id.to_string())
+				.map(|v| v.time().seconds())
+				.unwrap_or_default();
+			latest_tags.push((
+				package,
+				commit_id.to_string(),
+				timestamp,
+				version.to_string(),
+				previous_version,
+			));
+		}
+		let Some((_, commit_id, timestamp, ..)) = latest_tags
+			.iter()
+			.max_by_key(|(_, _, timestamp, ..)| *timestamp)
+			.cloned()
+		else {
+			return Err(Error::TagError(String::from(
+				"No releases of the workspace packages are found",
+			)));
+		};
+		let mut umbrella = Self {
+			commit_id,
+			timestamp,
+			packages: Vec::new(),
+		};
+		for (package, commit_id, _, version, previous_version) in latest_tags {
+			if commit_id != umbrella.commit_id {
+				continue;
+			}
+			let range = match &previous_version {
+				Some(previous) => format!("{previous}..{version}"),
+				None => version.to_string(),
+			};
+			let include_path =
+				Pattern::new(&format!("{}/**", package.path.trim_end_matches('/')))
+					.map_err(|e| {
+						Error::ArgumentError(format!(
+							"invalid path of {}: {e}",
+							package.name
+						))
+					})?;
+			let highlights = repository
+				.commits(
+					Some(range),
+					Some(vec![include_path]),
+					None,
+					git_config.path_renames.as_deref().unwrap_or_default(),
+				)?
+				.iter()
+				.rev()
+				.filter_map(|v| Commit::from(v).into_conventional().ok())
+				.filter_map(|commit| {
+					let conv = commit.conv.as_ref()?;
+					commit.is_highlight(git_config).then(|| Highlight {
+						message:  conv.description().to_string(),
+						breaking: commit.is_breaking(),
+					})
+				})
+				.collect();
+			umbrella.packages.push(PackageRelease {
+				name: package.name.to_string(),
+				previous_version,
+				version,
+				changelog: package.changelog.clone(),
+				highlights,
+			});
+		}
+		Ok(umbrella)
+	}
+
+	/// Renders the umbrella changelog with the given template.
+	///
+	/// The [default template](DEFAULT_TEMPLATE) is used if the template is not
+	/// given.
+	pub fn render(&self, template: Option<&str>) -> Result<String> {
+		Template::new(template.unwrap_or(DEFAULT_TEMPLATE).to_string(), false)?
+			.render(self, Option::<HashMap<&str, String>>::None.as_ref(), &[])
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn render_umbrella() -> Result<()> {
+		let umbrella = Umbrella {
+			commit_id: String::from("1a2b3c4d"),
+			timestamp: 1714953600,
+			packages:  vec![
+				PackageRelease {
+					name:             String::from("core"),
+					previous_version: Some(String::from("core-v1.2.0")),
+					version:          String::from("core-v2.0.0"),
+					changelog:        Some(String::from("crates/core/CHANGELOG.md")),
+					highlights:       vec![
+						Highlight {
+							message:  String::from("remove the deprecated API"),
+							breaking: true,
+						},
+						Highlight {
+							message:  String::from("add streaming support"),
+							breaking: false,
+						},
+					],
+				},
+				PackageRelease {
+					name:             String::from("cli"),
+					previous_version: None,
+					version:          String::from("cli-v0.1.0"),
+					changelog:        None,
+					highlights:       vec![],
+				},
+			],
+		};
+		assert_eq!(
+			[
+				"## 2024-05-06",
+				"",
+				"| Package | Version |",
+				"| ------- | ------- |",
+				"| [core](crates/core/CHANGELOG.md) | core-v1.2.0 → core-v2.0.0 |",
+				"| cli | cli-v0.1.0 |",
+				"",
+				"### core",
+				"",
+				"- **BREAKING** Remove the deprecated API",
+				"- Add streaming support",
+				"",
+			]
+			.join("\n"),
+			umbrella.render(None)?
+		);
+		assert_eq!(
+			"core, cli",
+			umbrella.render(Some(
+				"{{ packages | map(attribute=\"name\") | join(sep=\", \") }}"
+			))?
+		);
+		Ok(())
+	}
+}
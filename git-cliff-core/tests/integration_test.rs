@@ -20,8 +20,8 @@ use std::fmt::Write;
 #[test]
 fn generate_changelog() -> Result<()> {
 	let changelog_config = ChangelogConfig {
-		header:         Some(String::from("this is a changelog")),
-		body:           Some(String::from(
+		header:            Some(String::from("this is a changelog")),
+		body:              Some(String::from(
 			r#"
 ## Release {{ version }} - <DATE>
 {% for group, commits in commits | group_by(attribute="group") %}
@@ -38,19 +38,37 @@ fn generate_changelog() -> Result<()> {
 {% endfor -%}
 {% endfor %}"#,
 		)),
-		footer:         Some(String::from("eoc - end of changelog")),
-		trim:           None,
-		postprocessors: None,
+		footer:            Some(String::from("eoc - end of changelog")),
+		trim:              None,
+		postprocessors:    None,
+		commit_template:   None,
+		tag_message:       None,
+		url_shortener:     None,
+		max_body_size:     None,
+		elision_note:      None,
+		git_read_path:     None,
+		heading_level:     None,
+		heading_numbering: None,
+		commit_groups:     None,
+		collation:         None,
+		output_flavor:     None,
+		internal_domains:  None,
+		normalize:         None,
+		pagination:        None,
+		template_limits:   None,
 	};
 	let git_config = GitConfig {
 		conventional_commits:     Some(true),
 		filter_unconventional:    Some(true),
 		split_commits:            Some(false),
+		pull_request_merges:      None,
+		raw_entries:              None,
 		commit_preprocessors:     Some(vec![TextProcessor {
 			pattern:         Regex::new(r"\(fixes (#[1-9]+)\)").unwrap(),
 			replace:         Some(String::from("[closes Issue${1}]")),
 			replace_command: None,
 		}]),
+		commit_types:             None,
 		commit_parsers:           Some(vec![
 			CommitParser {
 				sha:           Some(String::from("coffee")),
@@ -62,6 +80,10 @@ fn generate_changelog() -> Result<()> {
 				skip:          None,
 				field:         None,
 				pattern:       None,
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			},
 			CommitParser {
 				sha:           None,
@@ -73,6 +95,10 @@ fn generate_changelog() -> Result<()> {
 				skip:          None,
 				field:         None,
 				pattern:       None,
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			},
 			CommitParser {
 				sha:           None,
@@ -84,6 +110,10 @@ fn generate_changelog() -> Result<()> {
 				skip:          None,
 				field:         None,
 				pattern:       None,
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			},
 			CommitParser {
 				sha:           None,
@@ -95,6 +125,10 @@ fn generate_changelog() -> Result<()> {
 				skip:          None,
 				field:         None,
 				pattern:       None,
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			},
 			CommitParser {
 				sha:           None,
@@ -106,6 +140,10 @@ fn generate_changelog() -> Result<()> {
 				skip:          None,
 				field:         Some(String::from("author.name")),
 				pattern:       Regex::new("John Doe").ok(),
+				pattern_not:   None,
+				and:           None,
+				or:            None,
+				continue_:     None,
 			},
 		]),
 		protect_breaking_commits: None,
@@ -115,6 +153,7 @@ fn generate_changelog() -> Result<()> {
 		ignore_tags:              None,
 		topo_order:               None,
 		sort_commits:             None,
+		sort_group_commits:       None,
 		link_parsers:             Some(vec![
 			LinkParser {
 				pattern: Regex::new("#(\\d+)").unwrap(),
@@ -128,6 +167,19 @@ fn generate_changelog() -> Result<()> {
 			},
 		]),
 		limit_commits:            None,
+		release_period:           None,
+		commit_date:              None,
+		signing_keys:             None,
+		require_signed_commits:   None,
+		path_renames:             None,
+		embargoes:                None,
+		lift_embargoes:           false,
+		default_scope:            None,
+		scope_aliases:            None,
+		commit_filter:            None,
+		min_scope_commits:        None,
+		other_scope:              None,
+		hide_small_scopes:        None,
 	};
 
 	let mut commit_with_author = Commit::new(
@@ -188,9 +240,14 @@ fn generate_changelog() -> Result<()> {
 			commit_id: None,
 			timestamp: 0,
 			previous:  None,
+			moved_from: None,
+			authors: None,
+			assets: Vec::new(),
+			stats: Default::default(),
 			#[cfg(feature = "github")]
 			github: git_cliff_core::github::GitHubReleaseMetadata {
 				contributors: vec![],
+				milestone:    None,
 			},
 		},
 		Release {
@@ -216,9 +273,14 @@ fn generate_changelog() -> Result<()> {
 			commit_id: None,
 			timestamp: 0,
 			previous:  None,
+			moved_from: None,
+			authors: None,
+			assets: Vec::new(),
+			stats: Default::default(),
 			#[cfg(feature = "github")]
 			github: git_cliff_core::github::GitHubReleaseMetadata {
 				contributors: vec![],
+				milestone:    None,
 			},
 		},
 	];
@@ -0,0 +1,280 @@
+//! Derive macros of [git-cliff](https://github.com/orhun/git-cliff).
+//!
+//! These are used by `git-cliff-core` and are not meant to be used directly.
+#![warn(missing_docs, clippy::unwrap_used)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+	parse_macro_input,
+	Attribute,
+	Data,
+	DeriveInput,
+	Expr,
+	Fields,
+	Lit,
+	LitStr,
+	Meta,
+};
+
+/// Case conversion of `rename_all`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RenameRule {
+	/// `lowercase`
+	Lower,
+	/// `UPPERCASE`
+	Upper,
+	/// `PascalCase`
+	Pascal,
+	/// `camelCase`
+	Camel,
+	/// `snake_case`
+	Snake,
+	/// `SCREAMING_SNAKE_CASE`
+	ScreamingSnake,
+	/// `kebab-case`
+	Kebab,
+	/// `SCREAMING-KEBAB-CASE`
+	ScreamingKebab,
+}
+
+impl RenameRule {
+	/// Parses the rule from the value of `rename_all`.
+	fn parse(value: &LitStr) -> syn::Result<Self> {
+		match value.value().as_str() {
+			"lowercase" => Ok(Self::Lower),
+			"UPPERCASE" => Ok(Self::Upper),
+			"PascalCase" => Ok(Self::Pascal),
+			"camelCase" => Ok(Self::Camel),
+			"snake_case" => Ok(Self::Snake),
+			"SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+			"kebab-case" => Ok(Self::Kebab),
+			"SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+			rule => Err(syn::Error::new_spanned(
+				value,
+				format!("unknown rename rule `rename_all = {rule:?}`"),
+			)),
+		}
+	}
+
+	/// Applies the rule to the name of a variant (in `PascalCase`).
+	fn apply_to_variant(self, name: &str) -> String {
+		match self {
+			Self::Lower => name.to_ascii_lowercase(),
+			Self::Upper => name.to_ascii_uppercase(),
+			Self::Pascal => name.to_string(),
+			Self::Camel => {
+				let mut chars = name.chars();
+				chars
+					.next()
+					.map(|v| v.to_ascii_lowercase().to_string() + chars.as_str())
+					.unwrap_or_default()
+			}
+			Self::Snake |
+			Self::ScreamingSnake |
+			Self::Kebab |
+			Self::ScreamingKebab => {
+				let mut snake = String::new();
+				for (i, c) in name.char_indices() {
+					if i > 0 && c.is_uppercase() {
+						snake.push('_');
+					}
+					snake.push(c.to_ascii_lowercase());
+				}
+				self.apply_to_field(&snake)
+			}
+		}
+	}
+
+	/// Applies the rule to the name of a field (in `snake_case`).
+	fn apply_to_field(self, name: &str) -> String {
+		match self {
+			Self::Lower | Self::Snake => name.to_string(),
+			Self::Upper | Self::ScreamingSnake => name.to_ascii_uppercase(),
+			Self::Pascal | Self::Camel => {
+				let mut pascal = String::new();
+				let mut capitalize = self == Self::Pascal;
+				for c in name.chars() {
+					if c == '_' {
+						capitalize = true;
+					} else if capitalize {
+						pascal.push(c.to_ascii_uppercase());
+						capitalize = false;
+					} else {
+						pascal.push(c);
+					}
+				}
+				pascal
+			}
+			Self::Kebab => name.replace('_', "-"),
+			Self::ScreamingKebab => name.replace('_', "-").to_ascii_uppercase(),
+		}
+	}
+}
+
+/// Serde attributes of a struct, enum, field or variant.
+#[derive(Default)]
+struct SerdeAttributes {
+	/// Name of the item in the configuration file.
+	rename:     Option<String>,
+	/// Case of the renamed fields or variants.
+	rename_all: Option<RenameRule>,
+	/// Whether the item is not deserialized.
+	skip:       bool,
+	/// Whether the fields are flattened into the parent.
+	flatten:    bool,
+}
+
+impl SerdeAttributes {
+	/// Parses the serde attributes.
+	fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+		let mut attributes = Self::default();
+		for attr in attrs.iter().filter(|v| v.path().is_ident("serde")) {
+			attr.parse_nested_meta(|meta| {
+				let value = if meta.input.peek(syn::Token![=]) {
+					Some(meta.value()?.parse::<LitStr>()?)
+				} else {
+					None
+				};
+				if meta.path.is_ident("rename") {
+					attributes.rename = value.map(|v| v.value());
+				} else if meta.path.is_ident("rename_all") {
+					attributes.rename_all =
+						value.as_ref().map(RenameRule::parse).transpose()?;
+				} else if meta.path.is_ident("skip") ||
+					meta.path.is_ident("skip_deserializing")
+				{
+					attributes.skip = true;
+				} else if meta.path.is_ident("flatten") {
+					attributes.flatten = true;
+				}
+				Ok(())
+			})?;
+		}
+		Ok(attributes)
+	}
+}
+
+/// Returns the doc comments as paragraphs.
+fn docs(attrs: &[Attribute]) -> String {
+	let lines = attrs
+		.iter()
+		.filter_map(|attr| match &attr.meta {
+			Meta::NameValue(meta) if meta.path.is_ident("doc") => {
+				match &meta.value {
+					Expr::Lit(expr) => match &expr.lit {
+						Lit::Str(value) => Some(value.value().trim().to_string()),
+						_ => None,
+					},
+					_ => None,
+				}
+			}
+			_ => None,
+		})
+		.collect::<Vec<String>>();
+	lines
+		.split(|v| v.is_empty())
+		.map(|v| v.join(" "))
+		.filter(|v| !v.is_empty())
+		.collect::<Vec<String>>()
+		.join("\n\n")
+}
+
+/// Derives `ConfigReference` for the configuration structs and enums.
+///
+/// The options are generated from the fields along with their doc comments
+/// and serde attributes (`rename`, `rename_all` and `skip`). The case
+/// conversions of `rename_all` are the same as serde's.
+#[proc_macro_derive(ConfigReference, attributes(serde))]
+pub fn derive_config_reference(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	expand(&input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Expands the `ConfigReference` implementation.
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+	let name = &input.ident;
+	let attributes = SerdeAttributes::parse(&input.attrs)?;
+	let value_type = match &input.data {
+		Data::Struct(data) => {
+			let Fields::Named(named) = &data.fields else {
+				return Err(syn::Error::new_spanned(
+					name,
+					"only the structs with named fields are supported",
+				));
+			};
+			let mut fields = Vec::new();
+			for field in &named.named {
+				let field_attributes = SerdeAttributes::parse(&field.attrs)?;
+				if field_attributes.skip {
+					continue;
+				}
+				if field_attributes.flatten {
+					return Err(syn::Error::new_spanned(
+						field,
+						"flattened fields are not supported",
+					));
+				}
+				let field_name = field_attributes.rename.unwrap_or_else(|| {
+					let name = field
+						.ident
+						.as_ref()
+						.map(|v| v.to_string())
+						.unwrap_or_default();
+					match attributes.rename_all {
+						Some(rule) => rule.apply_to_field(&name),
+						None => name,
+					}
+				});
+				let description = docs(&field.attrs);
+				let field_type = &field.ty;
+				fields.push(quote! {
+					crate::reference::ConfigField {
+						name: #field_name,
+						description: #description,
+						value_type: <#field_type as crate::reference::ConfigReference>::value_type(),
+					}
+				});
+			}
+			quote! { crate::reference::ValueType::Table(vec![#(#fields),*]) }
+		}
+		Data::Enum(data) => {
+			let mut variants = Vec::new();
+			for variant in &data.variants {
+				if !matches!(variant.fields, Fields::Unit) {
+					return Err(syn::Error::new_spanned(
+						variant,
+						"only the unit variants are supported",
+					));
+				}
+				let variant_attributes = SerdeAttributes::parse(&variant.attrs)?;
+				if variant_attributes.skip {
+					continue;
+				}
+				variants.push(variant_attributes.rename.unwrap_or_else(|| {
+					let name = variant.ident.to_string();
+					match attributes.rename_all {
+						Some(rule) => rule.apply_to_variant(&name),
+						None => name,
+					}
+				}));
+			}
+			quote! { crate::reference::ValueType::Enum(vec![#(#variants),*]) }
+		}
+		Data::Union(_) => {
+			return Err(syn::Error::new_spanned(name, "unions are not supported"));
+		}
+	};
+	let (impl_generics, type_generics, where_clause) =
+		input.generics.split_for_impl();
+	Ok(quote! {
+		impl #impl_generics crate::reference::ConfigReference for #name #type_generics #where_clause {
+			fn value_type() -> crate::reference::ValueType {
+				#value_type
+			}
+		}
+	})
+}
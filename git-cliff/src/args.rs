@@ -10,10 +10,14 @@ use clap::{
 	},
 	ArgAction,
 	Parser,
+	Subcommand,
 	ValueEnum,
 };
 use git_cliff_core::{
-	config::Remote,
+	config::{
+		ReleasePeriod,
+		Remote,
+	},
 	DEFAULT_CONFIG,
 	DEFAULT_OUTPUT,
 };
@@ -27,14 +31,152 @@ pub enum Strip {
 	All,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+	Mermaid,
+	Dot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+	Text,
+	Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+	Junit,
+	Github,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DigestPeriod {
+	Weekly,
+	Monthly,
+	Quarterly,
+	Yearly,
+}
+
+impl From<DigestPeriod> for ReleasePeriod {
+	fn from(period: DigestPeriod) -> Self {
+		match period {
+			DigestPeriod::Weekly => Self::Week,
+			DigestPeriod::Monthly => Self::Month,
+			DigestPeriod::Quarterly => Self::Quarter,
+			DigestPeriod::Yearly => Self::Year,
+		}
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum Sort {
 	Oldest,
 	Newest,
 }
 
+/// Subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+	/// Merges the changelog without conflicts (for using as a git merge
+	/// driver).
+	MergeDriver {
+		/// Sets the common ancestor version of the changelog (%O).
+		#[arg(value_name = "BASE")]
+		base:    PathBuf,
+		/// Sets the current version of the changelog (%A).
+		///
+		/// The merged changelog is written to this file.
+		#[arg(value_name = "CURRENT")]
+		current: PathBuf,
+		/// Sets the other branch's version of the changelog (%B).
+		#[arg(value_name = "OTHER")]
+		other:   PathBuf,
+	},
+	/// Queries the changelog context with a jq-like path.
+	///
+	/// e.g. `.[].commits[][?breaking].message`
+	Query {
+		/// Sets the path to query.
+		#[arg(value_name = "PATH")]
+		path: String,
+	},
+	/// Checks the commit messages against the lint rules.
+	Lint,
+	/// Lists the scopes of the conventional commits.
+	Scopes {
+		/// Sets the allowed scopes and fails if another scope is used.
+		#[arg(long, value_name = "SCOPE", num_args(1..))]
+		allow: Option<Vec<String>>,
+	},
+	/// Reports the anomalies in the tag history.
+	Audit {
+		/// Sets the branch that the tags are expected to be in.
+		#[arg(long, value_name = "REF", default_value = "HEAD")]
+		branch: String,
+	},
+	/// Generates a randomized changelog context for developing templates.
+	Fixture {
+		/// Sets the number of releases.
+		#[arg(long, value_name = "NUMBER", default_value = "3")]
+		releases:   usize,
+		/// Sets the total number of commits.
+		#[arg(long, value_name = "NUMBER", default_value = "20")]
+		commits:    usize,
+		/// Leaves the latest release unreleased.
+		#[arg(long)]
+		unreleased: bool,
+		/// Sets the seed for generating the same context again.
+		#[arg(long, value_name = "SEED")]
+		seed:       Option<u64>,
+	},
+	/// Renders the summary of the workspace packages that are released
+	/// together.
+	Umbrella,
+	/// Explains how a commit is processed by the preprocessors and parsers.
+	///
+	/// e.g. `git cliff explain HEAD` or `git cliff explain "feat: add x"`
+	Explain {
+		/// Sets the revision or the message of the commit.
+		#[arg(value_name = "COMMIT")]
+		commit: String,
+	},
+	/// Generates a digest of the changes that are grouped by the calendar
+	/// period instead of the tags.
+	///
+	/// e.g. `git cliff digest --period weekly`
+	Digest {
+		/// Sets the calendar period for grouping the changes.
+		#[arg(long, value_name = "PERIOD", value_enum, default_value_t = DigestPeriod::Weekly)]
+		period: DigestPeriod,
+	},
+	/// Manages the configuration file.
+	#[command(arg_required_else_help = true)]
+	Config {
+		/// Lists the options of the configuration file.
+		#[arg(long, conflicts_with = "explain")]
+		list:    bool,
+		/// Prints the type, the default value and the description of the
+		/// option.
+		#[arg(long, value_name = "KEY")]
+		explain: Option<String>,
+		#[command(subcommand)]
+		command: Option<ConfigCommand>,
+	},
+}
+
+/// Subcommands of the `config` command.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigCommand {
+	/// Rewrites the deprecated keys and values of the configuration file to
+	/// the current schema.
+	Migrate,
+	/// Reports the commit preprocessors, scope aliases, commit parsers and
+	/// link parsers that do not match any commit.
+	Lint,
+}
+
 /// Command-line arguments to parse.
-#[derive(Debug, Parser)]
+#[derive(Debug, Clone, Parser)]
 #[command(
     version,
     author = clap::crate_authors!("\n"),
@@ -52,6 +194,7 @@ pub enum Sort {
     next_help_heading = Some("OPTIONS"),
 	disable_help_flag = true,
 	disable_version_flag = true,
+	propagate_version = true,
 )]
 pub struct Opt {
 	#[arg(
@@ -154,7 +297,9 @@ pub struct Opt {
 	    value_parser = Opt::parse_dir
 	)]
 	pub prepend:        Option<PathBuf>,
-	/// Writes output to the given file.
+	/// Writes output to the given file (`-` for stdout).
+	///
+	/// The changelog is written to each of the given files.
 	#[arg(
 	    short,
 	    long,
@@ -162,9 +307,74 @@ pub struct Opt {
 	    value_name = "PATH",
 	    value_parser = Opt::parse_dir,
 	    num_args = 0..=1,
-	    default_missing_value = DEFAULT_OUTPUT
+	    default_missing_value = DEFAULT_OUTPUT,
+	    action = ArgAction::Append
+	)]
+	pub output:         Vec<PathBuf>,
+	/// Sets the file for storing the state between runs.
+	#[arg(
+	    long,
+	    env = "GIT_CLIFF_STATE_FILE",
+	    value_name = "PATH",
+	    value_parser = Opt::parse_dir
+	)]
+	pub state_file:     Option<PathBuf>,
+	/// Sets the file for recording the generations (e.g.
+	/// .git/cliff-history.jsonl).
+	#[arg(
+	    long,
+	    env = "GIT_CLIFF_HISTORY_FILE",
+	    value_name = "PATH",
+	    value_parser = Opt::parse_dir
+	)]
+	pub history_file:   Option<PathBuf>,
+	/// Writes the metrics of the generation to the given file in the
+	/// Prometheus text format (e.g. cliff.prom).
+	#[arg(
+	    long,
+	    env = "GIT_CLIFF_METRICS_FILE",
+	    value_name = "PATH",
+	    value_parser = Opt::parse_dir
+	)]
+	pub metrics_file:   Option<PathBuf>,
+	/// Writes the shields.io endpoint data of the latest release to the given
+	/// file (e.g. badge.json).
+	#[arg(
+	    long,
+	    env = "GIT_CLIFF_BADGE",
+	    value_name = "PATH",
+	    value_parser = Opt::parse_dir
 	)]
-	pub output:         Option<PathBuf>,
+	pub badge:          Option<PathBuf>,
+	/// Sets the artifacts of the latest release for computing their
+	/// checksums (e.g. dist/*).
+	#[arg(long, env = "GIT_CLIFF_ASSETS", value_name = "PATH", num_args(1..))]
+	pub assets:         Option<Vec<PathBuf>>,
+	/// Exports the releases to the given SQLite database or SQL script.
+	///
+	/// e.g. `sqlite:changelog.db` or `sql:changelog.sql`
+	#[arg(
+	    long,
+	    env = "GIT_CLIFF_EXPORT",
+	    value_name = "TARGET",
+	    value_parser = Opt::parse_export
+	)]
+	pub export:         Option<ExportTarget>,
+	/// Marks the commits that are already included in the given reference.
+	#[arg(long, env = "GIT_CLIFF_DEPLOYED_REF", value_name = "REF")]
+	pub deployed_ref:   Option<String>,
+	/// Processes only the commits that are not present in the given upstream
+	/// reference.
+	#[arg(long, env = "GIT_CLIFF_UPSTREAM_REF", value_name = "REF")]
+	pub upstream_ref:   Option<String>,
+	/// Sets the JSON or CSV file of the external commit metadata.
+	#[arg(
+	    long,
+	    env = "GIT_CLIFF_METADATA",
+	    value_name = "PATH",
+	    value_parser = Opt::parse_dir
+	)]
+	pub metadata:       Option<PathBuf>,
 	/// Sets the tag for the latest version.
 	#[arg(
 		short,
@@ -174,12 +384,32 @@ pub struct Opt {
 		allow_hyphen_values = true
 	)]
 	pub tag:            Option<String>,
+	/// Sets the package for the templates in the configuration file.
+	///
+	/// e.g. `tag_pattern = "{{ package }}-v[0-9].*"`
+	#[arg(long, env = "GIT_CLIFF_PACKAGE", value_name = "NAME")]
+	pub package:        Option<String>,
 	/// Bumps the version for unreleased changes.
 	#[arg(long, help_heading = Some("FLAGS"))]
 	pub bump:           bool,
 	/// Prints bumped version for unreleased changes.
 	#[arg(long, help_heading = Some("FLAGS"))]
 	pub bumped_version: bool,
+	/// Creates an annotated tag for the latest release after writing the
+	/// changelog.
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub tag_annotate:   bool,
+	/// Copies the notes of the latest release to the clipboard.
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub copy:           bool,
+	/// Writes the explanation of the version bump to the given file as JSON.
+	#[arg(
+	    long,
+	    env = "GIT_CLIFF_BUMP_RATIONALE",
+	    value_name = "PATH",
+	    value_parser = Opt::parse_dir
+	)]
+	pub bump_rationale: Option<PathBuf>,
 	/// Sets the template for the changelog body.
 	#[arg(
 		short,
@@ -204,9 +434,65 @@ pub struct Opt {
 	/// Disables the external command execution.
 	#[arg(long, help_heading = Some("FLAGS"))]
 	pub no_exec:        bool,
+	/// Allows reading the git objects in templates (e.g. via `git_show`).
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub allow_git_read: bool,
+	/// Disables all network access.
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub offline:        bool,
+	/// Prints the changes to the files and the planned operations (e.g.
+	/// creating a tag) instead of running them.
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub dry_run:        bool,
+	/// Includes the commits that are under embargo.
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub lift_embargoes: bool,
+	/// Exits with a status code that describes the outcome.
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub exit_code:      bool,
+	/// Regenerates the changelog when the repository or the configuration
+	/// file changes.
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub watch:          bool,
 	/// Prints changelog context as JSON.
 	#[arg(short = 'x', long, help_heading = Some("FLAGS"))]
 	pub context:        bool,
+	/// Prints the releases as a compact JSON timeline.
+	#[arg(long, help_heading = Some("FLAGS"))]
+	pub timeline:       bool,
+	/// Sets the maximum size of the changelog in bytes.
+	#[arg(long, env = "GIT_CLIFF_MAX_BODY_SIZE", value_name = "BYTES")]
+	pub max_body_size:  Option<usize>,
+	/// Sets the expression for filtering the commits.
+	#[arg(long, env = "GIT_CLIFF_FILTER", value_name = "EXPR")]
+	pub filter:         Option<String>,
+	/// Prints the releases as a graph in the given format.
+	#[arg(long, value_name = "FORMAT", value_enum)]
+	pub format:         Option<Format>,
+	/// Sets the format of the error output.
+	#[arg(
+		long,
+		value_name = "FORMAT",
+		value_enum,
+		default_value_t = ErrorFormat::Text
+	)]
+	pub error_format:   ErrorFormat,
+	/// Reports the findings of the checks in the given format.
+	#[arg(
+		long,
+		env = "GIT_CLIFF_REPORT_FORMAT",
+		value_name = "FORMAT",
+		value_enum
+	)]
+	pub report_format:  Option<ReportFormat>,
+	/// Writes the report of the findings to the given file instead of stderr.
+	#[arg(
+	    long,
+	    env = "GIT_CLIFF_REPORT_FILE",
+	    value_name = "PATH",
+	    value_parser = Opt::parse_dir
+	)]
+	pub report_file:    Option<PathBuf>,
 	/// Strips the given parts from the changelog.
 	#[arg(short, long, value_name = "PART", value_enum)]
 	pub strip:          Option<Strip>,
@@ -236,6 +522,17 @@ pub struct Opt {
 	    value_name = "OWNER/REPO"
 	)]
 	pub github_repo:    Option<RemoteValue>,
+	#[command(subcommand)]
+	pub command:        Option<Command>,
+}
+
+/// Target of exporting the releases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExportTarget {
+	/// SQLite database (e.g. `sqlite:changelog.db`).
+	Sqlite(PathBuf),
+	/// SQL script for SQLite (e.g. `sql:changelog.sql`).
+	Sql(PathBuf),
 }
 
 /// Custom type for the remote value.
@@ -290,6 +587,25 @@ impl Opt {
 	fn parse_dir(dir: &str) -> Result<PathBuf, String> {
 		Ok(PathBuf::from(shellexpand::tilde(dir).to_string()))
 	}
+
+	/// Custom string parser for the export targets.
+	///
+	/// The target consists of the format and the path (e.g.
+	/// `sqlite:changelog.db`).
+	fn parse_export(target: &str) -> Result<ExportTarget, String> {
+		match target.split_once(':') {
+			Some(("sqlite", path)) if !path.is_empty() => {
+				Ok(ExportTarget::Sqlite(Self::parse_dir(path)?))
+			}
+			Some(("sql", path)) if !path.is_empty() => {
+				Ok(ExportTarget::Sql(Self::parse_dir(path)?))
+			}
+			_ => Err(String::from(
+				"expected `sqlite:<PATH>` or `sql:<PATH>` (e.g. \
+				 sqlite:changelog.db)",
+			)),
+		}
+	}
 }
 
 #[cfg(test)]
@@ -310,6 +626,20 @@ mod tests {
 		assert_eq!(home_dir, dir);
 	}
 
+	#[test]
+	fn export_target_parser() {
+		assert_eq!(
+			Ok(ExportTarget::Sqlite(PathBuf::from("changelog.db"))),
+			Opt::parse_export("sqlite:changelog.db")
+		);
+		assert_eq!(
+			Ok(ExportTarget::Sql(PathBuf::from("changelog.sql"))),
+			Opt::parse_export("sql:changelog.sql")
+		);
+		assert!(Opt::parse_export("changelog.db").is_err());
+		assert!(Opt::parse_export("sqlite:").is_err());
+	}
+
 	#[test]
 	fn remote_value_parser() -> Result<(), clap::Error> {
 		let remote_value_parser = RemoteValueParser;
@@ -14,17 +14,31 @@ pub mod logger;
 extern crate log;
 
 use args::{
+	Command,
+	ConfigCommand,
+	ExportTarget,
+	Format,
 	Opt,
+	ReportFormat,
 	Sort,
 	Strip,
 };
 use clap::ValueEnum;
+use git_cliff_core::audit;
 use git_cliff_core::changelog::Changelog;
-use git_cliff_core::commit::Commit;
+use git_cliff_core::collation::Collator;
+use git_cliff_core::command;
+use git_cliff_core::commit::{
+	Commit,
+	SignatureStatus,
+};
 use git_cliff_core::config::{
 	CommitParser,
 	Config,
+	ConfigContext,
+	LintSeverity,
 };
+use git_cliff_core::diff;
 use git_cliff_core::embed::{
 	BuiltinConfig,
 	EmbeddedConfig,
@@ -33,27 +47,68 @@ use git_cliff_core::error::{
 	Error,
 	Result,
 };
-use git_cliff_core::release::Release;
+use git_cliff_core::explain::Explanation;
+use git_cliff_core::fixture::Fixture;
+use git_cliff_core::history::HistoryEntry;
+#[cfg(feature = "link-check")]
+use git_cliff_core::link_check::{
+	LinkCache,
+	LinkChecker,
+};
+use git_cliff_core::lint::{
+	Linter,
+	OutputLinter,
+};
+use git_cliff_core::merge;
+use git_cliff_core::metadata::CommitMetadata;
+use git_cliff_core::metrics::Metrics;
+use git_cliff_core::migrate;
+use git_cliff_core::reference;
+use git_cliff_core::release::{
+	GraphFormat,
+	Release,
+	ReleaseAsset,
+};
 use git_cliff_core::repo::Repository;
+use git_cliff_core::report::{
+	Finding,
+	Report,
+};
+use git_cliff_core::rules;
+use git_cliff_core::scope::ScopeInventory;
+use git_cliff_core::state::State;
+use git_cliff_core::template::TemplateCache;
+use git_cliff_core::umbrella::Umbrella;
 use git_cliff_core::{
 	DEFAULT_CONFIG,
 	IGNORE_FILE,
 };
 use secrecy::Secret;
-use std::env;
-use std::fs::{
-	self,
-	File,
+use std::collections::{
+	HashMap,
+	HashSet,
 };
+use std::env;
+use std::fs;
 use std::io::{
 	self,
 	Write,
 };
+use std::path::{
+	Path,
+	PathBuf,
+};
+use std::thread;
 use std::time::{
+	Duration,
+	Instant,
 	SystemTime,
 	UNIX_EPOCH,
 };
 
+/// Interval of checking the changes in watch mode.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Checks for a new version on crates.io
 #[cfg(feature = "update-informer")]
 fn check_new_version() {
@@ -84,13 +139,19 @@ fn process_repository<'a>(
 	repository: &'static Repository,
 	config: &mut Config,
 	args: &Opt,
+	state: Option<&mut State>,
 ) -> Result<Vec<Release<'a>>> {
 	let mut tags = repository.tags(&config.git.tag_pattern, args.topo_order)?;
+	// The digests are grouped by the calendar period regardless of the tags.
+	if matches!(args.command, Some(Command::Digest { .. })) {
+		tags.clear();
+	}
 	let skip_regex = config.git.skip_tags.as_ref();
 	let ignore_regex = config.git.ignore_tags.as_ref();
+	let mut ignored_tags = HashMap::new();
 	tags = tags
 		.into_iter()
-		.filter(|(_, name)| {
+		.filter(|(commit_id, name)| {
 			// Keep skip tags to drop commits in the later stage.
 			let skip = skip_regex.map(|r| r.is_match(name)).unwrap_or_default();
 
@@ -107,6 +168,9 @@ fn process_repository<'a>(
 					ignore_tag
 				})
 				.unwrap_or_default();
+			if ignore && !skip {
+				ignored_tags.insert(commit_id.to_string(), name.to_string());
+			}
 
 			skip || !ignore
 		})
@@ -137,7 +201,7 @@ fn process_repository<'a>(
 		}
 	} else if args.latest || args.current {
 		if tags.len() < 2 {
-			let commits = repository.commits(None, None, None)?;
+			let commits = repository.commits(None, None, None, &[])?;
 			if let (Some(tag1), Some(tag2)) = (
 				commits.last().map(|c| c.id().to_string()),
 				tags.get_index(0).map(|(k, _)| k),
@@ -157,14 +221,14 @@ fn process_repository<'a>(
 					match current_tag_index.checked_sub(1) {
 						Some(i) => tag_index = i,
 						None => {
-							return Err(Error::ChangelogError(String::from(
+							return Err(Error::TagError(String::from(
 								"No suitable tags found. Maybe run with \
 								 '--topo-order'?",
 							)));
 						}
 					}
 				} else {
-					return Err(Error::ChangelogError(String::from(
+					return Err(Error::TagError(String::from(
 						"No tag exists for the current commit",
 					)));
 				}
@@ -177,11 +241,42 @@ fn process_repository<'a>(
 			}
 		}
 	}
+	if let (None, Some(upstream_ref)) = (&commit_range, &args.upstream_ref) {
+		commit_range = Some(format!("{upstream_ref}..HEAD"));
+	}
 	let mut commits = repository.commits(
 		commit_range,
 		args.include_path.clone(),
 		args.exclude_path.clone(),
+		config.git.path_renames.as_deref().unwrap_or_default(),
 	)?;
+
+	// Record the last commit of the range for prepending the next changes.
+	if let (Some(state), Some(commit)) = (state, commits.first()) {
+		state.last_commit = Some(commit.id().to_string());
+	}
+
+	// Drop the commits that are already in the upstream (e.g. for a fork).
+	if let Some(ref upstream_ref) = args.upstream_ref {
+		let upstream_commits = repository.included_in(&commits, upstream_ref)?;
+		debug!("Skipping {} upstream commit(s)", upstream_commits.len());
+		commits
+			.retain(|commit| !upstream_commits.contains(&commit.id().to_string()));
+	}
+
+	// Drop the commits that are merged by the pull requests.
+	if config.git.pull_request_merges.unwrap_or(false) {
+		let merges = commits
+			.iter()
+			.filter(|commit| {
+				commit.parent_count() > 1 &&
+					Commit::from(*commit).pull_request_merge().is_some()
+			})
+			.collect::<Vec<_>>();
+		let merged_commits = repository.merged_commits(&merges)?;
+		debug!("Skipping {} merged commit(s)", merged_commits.len());
+		commits.retain(|commit| !merged_commits.contains(&commit.id().to_string()));
+	}
 	if let Some(commit_limit_value) = config.git.limit_commits {
 		commits = commits
 			.drain(..commits.len().min(commit_limit_value))
@@ -202,19 +297,54 @@ fn process_repository<'a>(
 		}
 	}
 
+	// Find the commits that are already deployed.
+	let deployed_commits = match args.deployed_ref {
+		Some(ref reference) => repository.included_in(&commits, reference)?,
+		None => HashSet::new(),
+	};
+
+	// Load the external metadata of the commits.
+	let metadata = args
+		.metadata
+		.as_ref()
+		.map(|path| CommitMetadata::load(path))
+		.transpose()?;
+
+	// Verify the signatures of the commits.
+	let signatures = if config.git.verify_signatures() {
+		repository.signatures(&commits)?
+	} else {
+		HashMap::new()
+	};
+
 	// Process releases.
 	let mut releases = vec![Release::default()];
 	let mut release_index = 0;
 	let mut previous_release = Release::default();
 	let mut first_processed_tag = None;
 	for git_commit in commits.iter().rev() {
-		let commit = Commit::from(git_commit);
+		let mut commit = Commit::from(git_commit);
 		let commit_id = commit.id.to_string();
+		commit.deployed = deployed_commits.contains(&commit_id);
+		commit.metadata = metadata.as_ref().and_then(|v| v.get(&commit_id)).cloned();
+		if let Some((status, key)) = signatures.get(&commit_id) {
+			commit.signature_status = Some(*status);
+			commit.signing_key = key.clone();
+		}
 		if args.sort == Sort::Newest {
 			releases[release_index].commits.insert(0, commit);
 		} else {
 			releases[release_index].commits.push(commit);
 		}
+		// Record the ignored tag (e.g. a release candidate) that the commits
+		// are first seen in before they are folded into the release.
+		if let Some(tag) = ignored_tags.get(&commit_id) {
+			releases[release_index]
+				.commits
+				.iter_mut()
+				.filter(|v| v.first_seen_in.is_none())
+				.for_each(|v| v.first_seen_in = Some(tag.to_string()));
+		}
 		if let Some(tag) = tags.get(&commit_id) {
 			releases[release_index].version = Some(tag.to_string());
 			releases[release_index].commit_id = Some(commit_id);
@@ -253,6 +383,18 @@ fn process_repository<'a>(
 		}
 	}
 
+	// Group the untagged commits into releases by date.
+	if let Some(period) = config.git.release_period {
+		if releases.last().is_some_and(|r| r.version.is_none()) {
+			if let Some(release) = releases.pop() {
+				releases.extend(release.split_by_period(
+					period,
+					config.git.commit_date.unwrap_or_default(),
+				));
+			}
+		}
+	}
+
 	// Set the previous release if the first release does not have one set.
 	if !releases.is_empty() &&
 		releases
@@ -291,11 +433,205 @@ fn process_repository<'a>(
 	Ok(releases)
 }
 
+/// Outcome of a successful run.
+///
+/// The outcome is reported via the exit code if `--exit-code` is given. If
+/// multiple outcomes apply, the one with the lowest exit code is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+	/// Changelog is generated.
+	Generated,
+	/// No conventional commits are found.
+	NoConventionalCommits,
+	/// Output file or the prepended changelog is not changed.
+	Unchanged,
+	/// Remote data is not fetched completely.
+	RemoteDegraded,
+	/// There are unreleased changes.
+	UnreleasedChanges,
+}
+
+impl Outcome {
+	/// Returns the exit code of the outcome.
+	pub fn exit_code(&self) -> i32 {
+		match self {
+			Self::Generated => 0,
+			Self::NoConventionalCommits => 3,
+			Self::Unchanged => 4,
+			Self::RemoteDegraded => 5,
+			Self::UnreleasedChanges => 6,
+		}
+	}
+}
+
+/// Returns `true` if the output path stands for stdout (i.e. `-`).
+fn is_stdout(path: &Path) -> bool {
+	path == Path::new("-")
+}
+
+/// Writes the contents to the given file.
+///
+/// If `dry_run` is set, the unified diff of the changes is printed instead.
+fn write_file(path: &Path, contents: &[u8], dry_run: bool) -> Result<()> {
+	if !dry_run {
+		fs::write(path, contents)?;
+		return Ok(());
+	}
+	let previous = fs::read(path).ok();
+	print!(
+		"{}",
+		diff::unified_diff(
+			&path.to_string_lossy(),
+			previous.as_deref().map(String::from_utf8_lossy).as_deref(),
+			&String::from_utf8_lossy(contents),
+		)
+	);
+	Ok(())
+}
+
+/// Writes the output to the given file or stdout (see [`write_file`]).
+fn write_output(path: Option<&PathBuf>, output: &[u8], dry_run: bool) -> Result<()> {
+	match path {
+		Some(path) => write_file(path, output, dry_run),
+		None => Ok(io::stdout().write_all(output)?),
+	}
+}
+
+/// Prints the operation that would be run if it was not a dry run.
+fn print_planned(operation: &str) {
+	println!("[dry-run] {operation}");
+}
+
+/// Writes the report of the findings in the given format.
+///
+/// The report is written to stderr if the file is not given.
+fn write_report(
+	format: Option<ReportFormat>,
+	path: &Option<PathBuf>,
+	report: &Report,
+	dry_run: bool,
+) -> Result<()> {
+	let Some(format) = format else {
+		return Ok(());
+	};
+	let mut output = Vec::new();
+	match format {
+		ReportFormat::Junit => report.write_junit(&mut output)?,
+		ReportFormat::Github => report.write_github(&mut output)?,
+	}
+	match path {
+		Some(path) => write_file(path, &output, dry_run),
+		None => Ok(io::stderr().write_all(&output)?),
+	}
+}
+
+/// Sets the time of the generation and writes the metrics to the given file.
+fn write_metrics(metrics: &mut Metrics, path: &Path, dry_run: bool) -> Result<()> {
+	metrics.timestamp = SystemTime::now()
+		.duration_since(UNIX_EPOCH)?
+		.as_secs()
+		.try_into()?;
+	if dry_run {
+		write_file(path, metrics.to_string().as_bytes(), true)?;
+	} else {
+		metrics.write(path)?;
+		debug!("Wrote the metrics to {}", path.display());
+	}
+	Ok(())
+}
+
 /// Runs `git-cliff`.
-pub fn run(mut args: Opt) -> Result<()> {
+///
+/// The metrics are written even if the generation fails.
+pub fn run(args: Opt) -> Result<Outcome> {
+	if args.watch {
+		watch(args)
+	} else {
+		run_with_cache(args, &mut TemplateCache::default())
+	}
+}
+
+/// Runs `git-cliff` with the given cache of the parsed templates.
+fn run_with_cache(args: Opt, cache: &mut TemplateCache) -> Result<Outcome> {
+	let metrics_file = args.metrics_file.as_ref().map(|path| match &args.workdir {
+		Some(workdir) => workdir.join(path),
+		None => path.clone(),
+	});
+	let dry_run = args.dry_run;
+	let mut metrics = Metrics::default();
+	let result = generate(args, &mut metrics, cache);
+	if let (Err(_), Some(path)) = (&result, metrics_file) {
+		metrics.failed = true;
+		if let Err(e) = write_metrics(&mut metrics, &path, dry_run) {
+			warn!("Failed to write the metrics: {e}");
+		}
+	}
+	result
+}
+
+/// Regenerates the changelog whenever the references of the repository (e.g.
+/// new commits and tags) or the configuration file change.
+///
+/// The parsed templates are kept between the runs so that only the changed
+/// templates are parsed again. The errors are logged and watching continues.
+fn watch(args: Opt) -> Result<Outcome> {
+	if args.command.is_some() {
+		return Err(Error::ArgumentError(String::from(
+			"--watch cannot be used with a subcommand",
+		)));
+	}
+	let config = match &args.workdir {
+		Some(workdir) => workdir.join(&args.config),
+		None => args.config.clone(),
+	};
+	let repositories = match (&args.repository, &args.workdir) {
+		(Some(repositories), Some(workdir)) => {
+			repositories.iter().map(|v| workdir.join(v)).collect()
+		}
+		(Some(repositories), None) => repositories.clone(),
+		(None, Some(workdir)) => vec![workdir.clone()],
+		(None, None) => vec![env::current_dir()?],
+	};
+	let mut cache = TemplateCache::default();
+	let mut last_state = None;
+	info!("Watching the changes, press Ctrl-C to stop.");
+	loop {
+		let mut references = Vec::new();
+		for repository in &repositories {
+			references.push(Repository::init(repository.clone())?.references()?);
+		}
+		let state = (
+			references,
+			fs::metadata(&config).and_then(|v| v.modified()).ok(),
+		);
+		if last_state.as_ref() != Some(&state) {
+			let started = Instant::now();
+			match run_with_cache(args.clone(), &mut cache) {
+				Ok(_) => {
+					info!("Generated the changelog in {:?}", started.elapsed());
+				}
+				Err(e) => error!("{e}"),
+			}
+			last_state = Some(state);
+		}
+		thread::sleep(WATCH_INTERVAL);
+	}
+}
+
+/// Generates the changelog (or runs the given command) and records the
+/// metrics of the generation.
+fn generate(
+	mut args: Opt,
+	metrics: &mut Metrics,
+	cache: &mut TemplateCache,
+) -> Result<Outcome> {
+	let mut phase_started = Instant::now();
+
 	// Check if there is a new version available.
 	#[cfg(feature = "update-informer")]
-	check_new_version();
+	if !args.offline {
+		check_new_version();
+	}
 
 	// Create the configuration file if init flag is given.
 	if let Some(init_config) = args.init {
@@ -308,8 +644,45 @@ pub fn run(mut args: Opt) -> Result<()> {
 			init_config.map(|v| format!(" ({v})")).unwrap_or_default(),
 			DEFAULT_CONFIG
 		);
-		fs::write(DEFAULT_CONFIG, contents)?;
-		return Ok(());
+		write_file(Path::new(DEFAULT_CONFIG), contents.as_bytes(), args.dry_run)?;
+		return Ok(Outcome::Generated);
+	}
+
+	// Run the subcommand if given.
+	if let Some(Command::MergeDriver {
+		base,
+		current,
+		other,
+	}) = &args.command
+	{
+		let changelog = merge::merge(
+			&fs::read_to_string(base)?,
+			&fs::read_to_string(current)?,
+			&fs::read_to_string(other)?,
+		);
+		write_file(current, changelog.as_bytes(), args.dry_run)?;
+		return Ok(Outcome::Generated);
+	}
+
+	// Print the reference of the configuration options if requested.
+	if let Some(Command::Config { list, explain, .. }) = &args.command {
+		if *list {
+			let options = reference::options()?;
+			let width = options.iter().map(|v| v.key.len()).max().unwrap_or(0);
+			let mut output = io::stdout();
+			for option in options {
+				writeln!(output, "{:width$}  {}", option.key, option.summary())?;
+			}
+			return Ok(Outcome::Generated);
+		} else if let Some(key) = explain {
+			println!("{}", reference::explain(key)?);
+			return Ok(Outcome::Generated);
+		}
+	}
+
+	// Check if the releases can be exported before generating the changelog.
+	if let Some(ExportTarget::Sqlite(_)) = args.export {
+		command::check_sqlite()?;
 	}
 
 	// Retrieve the built-in configuration.
@@ -330,6 +703,24 @@ pub fn run(mut args: Opt) -> Result<()> {
 		if let Some(changelog) = args.prepend {
 			args.prepend = Some(workdir.join(changelog));
 		}
+		if let Some(state_file) = args.state_file {
+			args.state_file = Some(workdir.join(state_file));
+		}
+		if let Some(history_file) = args.history_file {
+			args.history_file = Some(workdir.join(history_file));
+		}
+		if let Some(metrics_file) = args.metrics_file {
+			args.metrics_file = Some(workdir.join(metrics_file));
+		}
+		if let Some(assets) = args.assets.as_mut() {
+			assets.iter_mut().for_each(|v| *v = workdir.join(v.clone()));
+		}
+		if let Some(metadata) = args.metadata {
+			args.metadata = Some(workdir.join(metadata));
+		}
+		if let Some(report_file) = args.report_file {
+			args.report_file = Some(workdir.join(report_file));
+		}
 	}
 
 	// Parse the configuration file.
@@ -342,12 +733,37 @@ pub fn run(mut args: Opt) -> Result<()> {
 		}
 	}
 
+	// Migrate the configuration file if requested.
+	if let Some(Command::Config {
+		command: Some(ConfigCommand::Migrate),
+		..
+	}) = &args.command
+	{
+		let (content, migrations) = migrate::migrate(&fs::read_to_string(&path)?)?;
+		for migration in &migrations {
+			println!("{migration}");
+		}
+		if migrations.is_empty() {
+			info!("{path:?} is up to date.");
+		} else {
+			write_file(&path, content.as_bytes(), args.dry_run)?;
+			if !args.dry_run {
+				info!("{path:?} is migrated ({} change(s)).", migrations.len());
+			}
+		}
+		return Ok(Outcome::Generated);
+	}
+
 	// Load the default configuration if necessary.
+	let config_context = ConfigContext {
+		package: args.package.clone(),
+		version: args.tag.clone(),
+	};
 	let mut config = if let Ok((config, name)) = builtin_config {
 		info!("Using built-in configuration file: {name}");
 		config
 	} else if path.exists() {
-		Config::parse(&path)?
+		Config::parse_with_context(&path, &config_context)?
 	} else {
 		if !args.context {
 			warn!(
@@ -362,6 +778,11 @@ pub fn run(mut args: Opt) -> Result<()> {
 		config.changelog.body = EmbeddedConfig::parse()?.changelog.body;
 	}
 
+	// Render the templates in the output paths.
+	for output in args.output.iter_mut() {
+		*output = PathBuf::from(config_context.render(&output.to_string_lossy())?);
+	}
+
 	// Update the configuration based on command line arguments and vice versa.
 	match args.strip {
 		Some(Strip::Header) => {
@@ -376,8 +797,21 @@ pub fn run(mut args: Opt) -> Result<()> {
 		}
 		None => {}
 	}
+	let mut state = args
+		.state_file
+		.as_ref()
+		.map(|path| State::load(path))
+		.transpose()?;
 	if args.prepend.is_some() {
 		config.changelog.footer = None;
+		if !(args.unreleased || args.latest || args.range.is_some()) {
+			if let Some(last_commit) =
+				state.as_ref().and_then(|v| v.last_commit.as_ref())
+			{
+				debug!("Prepending the commits after {last_commit}");
+				args.range = Some(format!("{last_commit}..HEAD"));
+			}
+		}
 		if !(args.unreleased || args.latest || args.range.is_some()) {
 			return Err(Error::ArgumentError(String::from(
 				"'-u' or '-l' is not specified",
@@ -387,6 +821,12 @@ pub fn run(mut args: Opt) -> Result<()> {
 	if args.body.is_some() {
 		config.changelog.body = args.body.clone();
 	}
+	if args.filter.is_some() {
+		config.git.commit_filter = args.filter.clone();
+	}
+	if args.max_body_size.is_some() {
+		config.changelog.max_body_size = args.max_body_size;
+	}
 	if args.sort == Sort::Oldest {
 		if let Some(ref sort_commits) = config.git.sort_commits {
 			args.sort = Sort::from_str(sort_commits, true)
@@ -416,12 +856,194 @@ pub fn run(mut args: Opt) -> Result<()> {
 				.iter_mut()
 				.for_each(|v| v.replace_command = None);
 		}
+		config.changelog.url_shortener = None;
+	}
+	if let Some(Command::Digest { period }) = args.command {
+		config.git.release_period = Some(period.into());
+	}
+	config.remote.offline = args.offline;
+	config.git.lift_embargoes = args.lift_embargoes;
+	for era in config.eras.iter_mut().flatten() {
+		if let Some(ref mut git) = era.git {
+			git.lift_embargoes = args.lift_embargoes;
+		}
+	}
+	if args.allow_git_read {
+		config.changelog.git_read_path =
+			match args.repository.as_ref().and_then(|v| v.first()) {
+				Some(repository) => Some(repository.clone()),
+				None => Some(env::current_dir()?),
+			};
 	}
 	config.git.skip_tags = config.git.skip_tags.filter(|r| !r.as_str().is_empty());
 
+	// Generate the context without a repository if requested.
+	if let Some(Command::Fixture {
+		releases,
+		commits,
+		unreleased,
+		seed,
+	}) = args.command
+	{
+		let seed = seed.unwrap_or_else(|| {
+			SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map(|v| v.as_nanos() as u64)
+				.unwrap_or_default()
+		});
+		info!("Generating the changelog context with the seed: {seed}");
+		let releases = Fixture {
+			releases,
+			commits,
+			unreleased,
+			seed,
+		}
+		.generate();
+		let changelog = Changelog::with_template_cache(releases, &config, cache)?;
+		let mut output = Vec::new();
+		changelog.write_context(&mut output)?;
+		write_output(args.output.first(), &output, args.dry_run)?;
+		return Ok(Outcome::Generated);
+	}
+
+	// Audit the tags if requested.
+	if let Some(Command::Audit { branch }) = &args.command {
+		let mut findings = Vec::new();
+		for repository in
+			args.repository.clone().unwrap_or(vec![env::current_dir()?])
+		{
+			let repository = Repository::init(repository)?;
+			findings.extend(audit::audit(
+				&repository.tag_details(&config.git.tag_pattern, branch)?,
+			));
+		}
+		let mut output = Vec::new();
+		for finding in &findings {
+			writeln!(output, "{finding}")?;
+		}
+		write_output(args.output.first(), &output, args.dry_run)?;
+		if !findings.is_empty() {
+			return Err(Error::AuditError(format!(
+				"{} issue(s) are found in the tags",
+				findings.len()
+			)));
+		}
+		return Ok(Outcome::Generated);
+	}
+
+	// Render the umbrella changelog if requested.
+	if let Some(Command::Umbrella) = args.command {
+		let Some(workspace) = &config.workspace else {
+			return Err(Error::ArgumentError(String::from(
+				"The workspace packages are not configured",
+			)));
+		};
+		let repository = args
+			.repository
+			.as_ref()
+			.and_then(|v| v.first().cloned())
+			.map_or_else(env::current_dir, Ok)?;
+		let umbrella = Umbrella::from_repository(
+			&Repository::init(repository)?,
+			workspace,
+			&config.git,
+		)?;
+		let content = umbrella.render(workspace.umbrella_template.as_deref())?;
+		write_output(args.output.first(), content.as_bytes(), args.dry_run)?;
+		return Ok(Outcome::Generated);
+	}
+
+	// Report the rules that do not match any commit if requested.
+	if let Some(Command::Config {
+		command: Some(ConfigCommand::Lint),
+		..
+	}) = &args.command
+	{
+		let mut commits = Vec::new();
+		for repository in
+			args.repository.clone().unwrap_or(vec![env::current_dir()?])
+		{
+			let repository = Box::leak(Box::new(Repository::init(repository)?));
+			commits.extend(
+				repository
+					.commits(
+						args.range.clone(),
+						args.include_path.clone(),
+						args.exclude_path.clone(),
+						config.git.path_renames.as_deref().unwrap_or_default(),
+					)?
+					.iter()
+					.map(Commit::from),
+			);
+		}
+		let dead_rules = rules::rule_usage(&commits, &config.git)?
+			.into_iter()
+			.filter(|v| v.is_dead())
+			.collect::<Vec<_>>();
+		let mut output = Vec::new();
+		for rule in &dead_rules {
+			writeln!(output, "{rule}")?;
+		}
+		write_output(args.output.first(), &output, args.dry_run)?;
+		write_report(
+			args.report_format,
+			&args.report_file,
+			&Report::new("config", dead_rules.iter().map(Finding::from).collect()),
+			args.dry_run,
+		)?;
+		if !dead_rules.is_empty() {
+			return Err(Error::LintError(format!(
+				"{} rule(s) do not match any of the {} commit(s)",
+				dead_rules.len(),
+				commits.len()
+			)));
+		}
+		return Ok(Outcome::Generated);
+	}
+
+	// Explain the processing of a commit if requested.
+	if let Some(Command::Explain { commit }) = &args.command {
+		let repository = args
+			.repository
+			.as_ref()
+			.and_then(|v| v.first().cloned())
+			.map_or_else(env::current_dir, Ok)?;
+		let repository = Repository::init(repository).ok();
+		let git_commit = repository
+			.as_ref()
+			.filter(|_| !commit.contains(char::is_whitespace))
+			.and_then(|v| v.resolve_commit(commit));
+		let commit = match &git_commit {
+			Some(git_commit) => Commit::from(git_commit),
+			None => Commit::new("0".repeat(40), commit.to_string()),
+		};
+		let explanation = Explanation::new(commit.clone(), &config.git)?;
+		let mut output = Vec::new();
+		writeln!(output, "{explanation}")?;
+		if explanation.commit.is_some() {
+			let mut entry_config = config.clone();
+			entry_config.changelog.header = None;
+			entry_config.changelog.footer = None;
+			let changelog = Changelog::new(
+				vec![Release {
+					commits: vec![commit],
+					..Default::default()
+				}],
+				&entry_config,
+			)?;
+			writeln!(output)?;
+			changelog.generate(&mut output)?;
+		}
+		write_output(args.output.first(), &output, args.dry_run)?;
+		return Ok(Outcome::Generated);
+	}
+
 	// Process the repositories.
+	metrics.add_phase("config", phase_started.elapsed());
+	phase_started = Instant::now();
 	let repositories = args.repository.clone().unwrap_or(vec![env::current_dir()?]);
 	let mut releases = Vec::<Release>::new();
+	let mut repository_metadata = None;
 	for repository in repositories {
 		// Skip commits
 		let mut skip_list = Vec::new();
@@ -450,49 +1072,493 @@ pub fn run(mut args: Opt) -> Result<()> {
 
 		// Process the repository.
 		let repository = Repository::init(repository)?;
+		repository_metadata.get_or_insert_with(|| repository.metadata());
 		releases.extend(process_repository(
 			Box::leak(Box::new(repository)),
 			&mut config,
 			&args,
+			state.as_mut(),
 		)?);
 	}
+	metrics.commits = releases.iter().map(|v| v.commits.len()).sum();
+	metrics.add_phase("repository", phase_started.elapsed());
+	phase_started = Instant::now();
+
+	// Check if the commits are signed by the allowed keys.
+	if config.git.verify_signatures() {
+		let keys = config.git.signing_keys.clone().unwrap_or_default();
+		let mut violations = 0;
+		for release in &releases {
+			for commit in release
+				.commits
+				.iter()
+				.filter(|commit| commit.signature_status.is_some())
+				.filter(|commit| !commit.is_signed_by(&keys))
+			{
+				violations += 1;
+				let status =
+					commit.signature_status.unwrap_or(SignatureStatus::Unsigned);
+				warn!(
+					"{} ({}) is not signed by an allowed key: {status} {}",
+					commit.id.get(..7).unwrap_or(&commit.id),
+					release.version.as_deref().unwrap_or("unreleased"),
+					commit.signing_key.as_deref().unwrap_or_default(),
+				);
+			}
+		}
+		if violations > 0 && config.git.require_signed_commits.unwrap_or(false) {
+			return Err(Error::SignatureError(format!(
+				"{violations} commit(s) are not signed by an allowed key"
+			)));
+		}
+	}
+
+	// Lint the commits if requested.
+	if let Some(Command::Lint) = args.command {
+		let linter = Linter::new(&config)?;
+		let violations = releases
+			.iter()
+			.flat_map(|release| release.commits.iter())
+			.flat_map(|commit| linter.lint(commit))
+			.collect::<Vec<_>>();
+		let mut output = Vec::new();
+		for violation in &violations {
+			writeln!(output, "{violation}")?;
+		}
+		write_output(args.output.first(), &output, args.dry_run)?;
+		write_report(
+			args.report_format,
+			&args.report_file,
+			&Report::new("lint", violations.iter().map(Finding::from).collect()),
+			args.dry_run,
+		)?;
+		let errors = violations
+			.iter()
+			.filter(|v| v.severity == LintSeverity::Error)
+			.count();
+		if errors > 0 {
+			return Err(Error::LintError(format!(
+				"{errors} error(s) and {} warning(s) are found",
+				violations.len() - errors
+			)));
+		}
+		return Ok(Outcome::Generated);
+	}
+
+	// List the scopes if requested.
+	if let Some(Command::Scopes { allow }) = &args.command {
+		let mut inventory = ScopeInventory::new(
+			releases.iter().flat_map(|release| release.commits.iter()),
+		);
+		if let Some(locale) = &config.changelog.collation {
+			inventory.collate(&Collator::new(locale));
+		}
+		let mut output = Vec::new();
+		inventory.write(&mut output)?;
+		write_output(args.output.first(), &output, args.dry_run)?;
+		if let Some(allowed) = allow {
+			let disallowed = inventory
+				.disallowed(allowed)
+				.iter()
+				.map(|v| v.name.to_string())
+				.collect::<Vec<String>>();
+			write_report(
+				args.report_format,
+				&args.report_file,
+				&Report::new(
+					"scopes",
+					disallowed
+						.iter()
+						.map(|scope| Finding {
+							rule:      String::from("allowed_scopes"),
+							severity:  LintSeverity::Error,
+							message:   format!("scope `{scope}` is not allowed"),
+							file:      None,
+							line:      None,
+							commit_id: None,
+						})
+						.collect(),
+				),
+				args.dry_run,
+			)?;
+			if !disallowed.is_empty() {
+				return Err(Error::ScopeError(format!(
+					"Scopes are not allowed: {}",
+					disallowed.join(", ")
+				)));
+			}
+		}
+		return Ok(Outcome::Generated);
+	}
+
+	// Compare the releases with the state of the previous run.
+	if let Some(ref mut state) = state {
+		state.annotate_moved_tags(&mut releases);
+		state.update(&releases);
+	}
 
 	// Process commits and releases for the changelog.
-	let mut changelog = Changelog::new(releases, &config)?;
+	let mut changelog = Changelog::with_template_cache(releases, &config, cache)?;
+	if let Some(metadata) = repository_metadata {
+		changelog.set_repository(metadata);
+	}
+	if let Some(assets) = &args.assets {
+		changelog.set_assets(ReleaseAsset::from_paths(assets)?);
+	}
 
 	// Print the result.
 	if args.bump || args.bumped_version {
-		if let Some(next_version) = changelog.bump_version()? {
+		if let Some(rationale) = changelog.bump_version_with_rationale()? {
+			if let Some(path) = &args.bump_rationale {
+				write_file(path, rationale.as_json()?.as_bytes(), args.dry_run)?;
+			}
+			let next_version = rationale.next_version;
 			if args.bumped_version {
-				if let Some(path) = args.output {
-					let mut output = File::create(path)?;
-					output.write_all(next_version.as_bytes())?;
-				} else {
-					println!("{next_version}");
+				match args.output.first() {
+					Some(path) => {
+						write_file(path, next_version.as_bytes(), args.dry_run)?
+					}
+					None => println!("{next_version}"),
 				}
-				return Ok(());
+				return Ok(Outcome::Generated);
 			}
 		}
 	}
+	if let Some(Command::Query { path: query }) = &args.command {
+		let mut output = Vec::new();
+		changelog.write_query(&mut output, query)?;
+		write_output(args.output.first(), &output, args.dry_run)?;
+		return Ok(Outcome::Generated);
+	}
 	if args.context {
-		return if let Some(path) = args.output {
-			let mut output = File::create(path)?;
-			changelog.write_context(&mut output)
-		} else {
-			changelog.write_context(&mut io::stdout())
+		let mut output = Vec::new();
+		changelog.write_context(&mut output)?;
+		write_output(args.output.first(), &output, args.dry_run)?;
+		return Ok(Outcome::Generated);
+	}
+	if args.timeline {
+		let mut output = Vec::new();
+		changelog.write_timeline(&mut output)?;
+		write_output(args.output.first(), &output, args.dry_run)?;
+		return Ok(Outcome::Generated);
+	}
+	if let Some(format) = args.format {
+		let format = match format {
+			Format::Mermaid => GraphFormat::Mermaid,
+			Format::Dot => GraphFormat::Dot,
 		};
+		let mut output = Vec::new();
+		changelog.write_graph(&mut output, format)?;
+		write_output(args.output.first(), &output, args.dry_run)?;
+		return Ok(Outcome::Generated);
 	}
+	let pages_dir = args
+		.output
+		.iter()
+		.find(|path| !is_stdout(path))
+		.or(args.prepend.as_ref())
+		.and_then(|path| path.parent())
+		.map(PathBuf::from)
+		.unwrap_or_default();
+	let mut unchanged = None;
+	let mut rendered = Vec::new();
 	if let Some(path) = args.prepend {
-		changelog.prepend(fs::read_to_string(&path)?, &mut File::create(path)?)?;
+		let previous = fs::read_to_string(&path)?;
+		let mut output = Vec::new();
+		changelog.prepend(previous.clone(), &mut output)?;
+		write_file(&path, &output, args.dry_run)?;
+		let content = String::from_utf8_lossy(&output).to_string();
+		unchanged = Some(content == previous);
+		rendered.push((path, content));
 	}
-	if let Some(path) = args.output {
-		let mut output = File::create(path)?;
-		if args.context {
-			changelog.write_context(&mut output)
-		} else {
-			changelog.generate(&mut output)
+	if !args.output.is_empty() {
+		let mut output = Vec::new();
+		changelog.generate(&mut output)?;
+		for path in &args.output {
+			if is_stdout(path) {
+				io::stdout().write_all(&output)?;
+				rendered.push((
+					PathBuf::from("<stdout>"),
+					String::from_utf8_lossy(&output).to_string(),
+				));
+				continue;
+			}
+			let previous = fs::read(path).ok();
+			write_file(path, &output, args.dry_run)?;
+			unchanged = Some(
+				unchanged.unwrap_or(true) && previous.as_ref() == Some(&output),
+			);
+			rendered
+				.push((path.clone(), String::from_utf8_lossy(&output).to_string()));
 		}
+	} else if config.lint.output.is_some() || args.history_file.is_some() {
+		let mut output = Vec::new();
+		changelog.generate(&mut output)?;
+		io::stdout().write_all(&output)?;
+		rendered.push((
+			PathBuf::from("<stdout>"),
+			String::from_utf8_lossy(&output).to_string(),
+		));
 	} else {
-		changelog.generate(&mut io::stdout())
+		changelog.generate(&mut io::stdout())?;
+	}
+
+	// Write the continuation pages of the long releases.
+	for (path, page) in changelog.pages()? {
+		let path = pages_dir.join(path);
+		if args.dry_run {
+			write_file(&path, page.as_bytes(), true)?;
+			rendered.push((path, page));
+			continue;
+		}
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		fs::write(&path, &page)?;
+		info!("Wrote the continuation page: {}", path.display());
+		rendered.push((path, page));
+	}
+	metrics.releases = changelog.releases().len();
+	#[cfg(feature = "github")]
+	{
+		metrics.remote_requests = changelog.github_requests();
+	}
+	metrics.add_phase("render", phase_started.elapsed());
+	phase_started = Instant::now();
+
+	// Check the rendered changelog.
+	if let Some(lint_config) = &config.lint.output {
+		let linter = OutputLinter::new(lint_config);
+		let check_urls = lint_config.check_urls.unwrap_or(false);
+		let dead_urls = if check_urls && config.remote.offline {
+			warn!("The URLs are not checked in offline mode.");
+			HashMap::new()
+		} else if check_urls {
+			#[cfg(feature = "link-check")]
+			{
+				let mut cache = match &lint_config.url_check_cache {
+					Some(path) => LinkCache::load(path)?,
+					None => LinkCache::default(),
+				};
+				let dead_urls = LinkChecker::new(lint_config.url_check_concurrency)?
+					.dead_urls(
+						rendered
+							.iter()
+							.flat_map(|(_, content)| OutputLinter::urls(content)),
+						&mut cache,
+					)?;
+				if let Some(path) = &lint_config.url_check_cache {
+					cache.save(path)?;
+				}
+				dead_urls
+			}
+			#[cfg(not(feature = "link-check"))]
+			{
+				warn!(
+					"The URLs are not checked since git-cliff is built without the \
+					 `link-check` feature."
+				);
+				HashMap::new()
+			}
+		} else {
+			HashMap::new()
+		};
+		let (mut errors, mut warnings) = (0, 0);
+		let mut findings = Vec::new();
+		for (path, content) in &rendered {
+			let base_dir = match path.parent() {
+				Some(parent) if path.is_file() => parent.to_path_buf(),
+				_ => env::current_dir()?,
+			};
+			let mut violations = linter.lint(content, &base_dir);
+			violations.extend(linter.lint_dead_links(content, &dead_urls));
+			violations.sort_by_key(|v| v.line);
+			for violation in violations {
+				if violation.severity == LintSeverity::Error {
+					errors += 1;
+					error!("{}:{violation}", path.display());
+				} else {
+					warnings += 1;
+					warn!("{}:{violation}", path.display());
+				}
+				findings.push(Finding::from((path.as_path(), &violation)));
+			}
+		}
+		write_report(
+			args.report_format,
+			&args.report_file,
+			&Report::new("changelog", findings),
+			args.dry_run,
+		)?;
+		if errors > 0 {
+			return Err(Error::LintError(format!(
+				"{errors} error(s) and {warnings} warning(s) are found in the \
+				 changelog"
+			)));
+		}
+	}
+	metrics.add_phase("lint", phase_started.elapsed());
+	phase_started = Instant::now();
+
+	// Copy the notes of the latest release to the clipboard.
+	if args.copy {
+		match changelog.release_notes()? {
+			Some(notes) => {
+				command::copy_to_clipboard(&notes)?;
+				info!("Copied the notes of the latest release to the clipboard");
+			}
+			None => warn!("There is no release to copy to the clipboard"),
+		}
+	}
+
+	// Create the annotated tag for the latest release.
+	if args.tag_annotate {
+		let Some((version, message)) = changelog.tag_message()? else {
+			return Err(Error::ArgumentError(String::from(
+				"The latest release does not have a version for tagging (use --tag \
+				 or --bump)",
+			)));
+		};
+		if args.dry_run {
+			print_planned(&format!(
+				"Create the annotated tag {version} with the message:\n{message}"
+			));
+		} else {
+			let repository = args
+				.repository
+				.as_ref()
+				.and_then(|v| v.first().cloned())
+				.map_or_else(env::current_dir, Ok)?;
+			Repository::init(repository)?
+				.create_annotated_tag(&version, &message)?;
+			info!("Created the annotated tag: {version}");
+		}
 	}
+
+	// Close the milestone of the latest release.
+	#[cfg(feature = "github")]
+	if config.remote.close_milestone.unwrap_or(false) {
+		if config.remote.offline {
+			warn!("The milestone is not closed in offline mode.");
+		} else if args.dry_run {
+			if let Some(milestone) = changelog.open_github_milestone()? {
+				print_planned(&format!(
+					"Close the milestone {}: PATCH /repos/{}/{}/milestones/{} \
+					 {{\"state\":\"closed\"}}",
+					milestone.title,
+					config.remote.github.owner,
+					config.remote.github.repo,
+					milestone.number
+				));
+			}
+		} else if let Some(milestone) = changelog.close_github_milestone()? {
+			info!("Closed the milestone: {}", milestone.title);
+		}
+	}
+
+	// Write the badge data of the latest release.
+	if let Some(path) = &args.badge {
+		let mut output = Vec::new();
+		changelog.write_badge(&mut output)?;
+		if args.dry_run {
+			write_file(path, &output, true)?;
+		} else {
+			fs::write(path, &output)?;
+			info!("Wrote the badge data: {}", path.display());
+		}
+	}
+
+	// Export the releases.
+	if let Some(target) = &args.export {
+		let timestamp = SystemTime::now()
+			.duration_since(UNIX_EPOCH)?
+			.as_secs()
+			.try_into()?;
+		match target {
+			ExportTarget::Sqlite(path) if args.dry_run => {
+				print_planned(&format!(
+					"Export the releases to {} via sqlite3",
+					path.display()
+				));
+			}
+			ExportTarget::Sqlite(path) => {
+				let mut script = Vec::new();
+				changelog.write_sql(&mut script, timestamp)?;
+				command::run_sqlite(path, &String::from_utf8_lossy(&script))?;
+				info!("Exported the releases to {}", path.display());
+			}
+			ExportTarget::Sql(path) => {
+				let mut script = Vec::new();
+				changelog.write_sql(&mut script, timestamp)?;
+				if args.dry_run {
+					write_file(path, &script, true)?;
+				} else {
+					fs::write(path, &script)?;
+					info!("Exported the releases to {}", path.display());
+				}
+			}
+		}
+	}
+
+	// Save the state for the next run.
+	if let (Some(path), Some(state)) = (args.state_file, state) {
+		if args.dry_run {
+			write_file(&path, state.as_json()?.as_bytes(), true)?;
+		} else {
+			state.save(&path)?;
+		}
+	}
+
+	// Record the generation in the history.
+	if let Some(path) = args.history_file {
+		let mut entry = HistoryEntry::new(
+			SystemTime::now()
+				.duration_since(UNIX_EPOCH)?
+				.as_secs()
+				.try_into()?,
+			&config,
+			&rendered
+				.iter()
+				.map(|(path, content)| (path.as_path(), content.as_bytes()))
+				.collect::<Vec<(&Path, &[u8])>>(),
+		)?;
+		entry.range = args.range.clone();
+		if let Some(release) = changelog.latest_release() {
+			entry.version = release.version.clone();
+			entry.commit_id = release.commit_id.clone();
+		}
+		if args.dry_run {
+			let history = fs::read_to_string(&path).unwrap_or_default();
+			let contents = format!("{history}{}\n", entry.as_json()?);
+			write_file(&path, contents.as_bytes(), true)?;
+		} else {
+			entry.append(&path)?;
+			debug!("Recorded the generation in {}", path.display());
+		}
+	}
+
+	// Write the metrics of the generation.
+	if let Some(path) = args.metrics_file {
+		metrics.add_phase("publish", phase_started.elapsed());
+		#[cfg(feature = "github")]
+		{
+			metrics.remote_requests = changelog.github_requests();
+		}
+		write_metrics(metrics, &path, args.dry_run)?;
+	}
+
+	let outcome = if config.git.conventional_commits.unwrap_or(true) &&
+		!changelog.has_conventional_commits()
+	{
+		Outcome::NoConventionalCommits
+	} else if unchanged == Some(true) {
+		Outcome::Unchanged
+	} else if changelog.is_remote_degraded() {
+		Outcome::RemoteDegraded
+	} else if changelog.has_unreleased_changes() {
+		Outcome::UnreleasedChanges
+	} else {
+		Outcome::Generated
+	};
+	Ok(outcome)
 }
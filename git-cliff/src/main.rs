@@ -1,5 +1,8 @@
 use clap::Parser;
-use git_cliff::args::Opt;
+use git_cliff::args::{
+	ErrorFormat,
+	Opt,
+};
 use git_cliff::logger;
 use git_cliff_core::error::Result;
 use std::env;
@@ -15,10 +18,16 @@ fn main() -> Result<()> {
 		env::set_var("RUST_LOG", "info");
 	}
 	logger::init()?;
+	let error_format = args.error_format;
+	let exit_code = args.exit_code;
 	match git_cliff::run(args) {
+		Ok(outcome) if exit_code => process::exit(outcome.exit_code()),
 		Ok(_) => process::exit(0),
 		Err(e) => {
-			log::error!("{}", e);
+			match error_format {
+				ErrorFormat::Text => log::error!("{}", e),
+				ErrorFormat::Json => eprintln!("{}", e.to_json()),
+			}
 			process::exit(1)
 		}
 	}